@@ -1,45 +1,20 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::params::ParamInfo;
+use crate::common::log;
 
+use super::inventory::{get_top, read_inventory};
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut max_elf = 0;
-    let mut max_cals = 0;
-    let mut this_elf = 0;
-    let mut this_cals = 0;
-    if let Ok(lines) = read_lines(file_name) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(val) = line {
-                if val == "" {
-                    if this_cals > max_cals {
-                        max_elf = this_elf;
-                        max_cals = this_cals;
-                    }
-                    this_elf = this_elf + 1;
-                    this_cals = 0;
-                }
-                else {
-                    let int_val = val.parse::<i64>().unwrap();
-                    this_cals = this_cals + int_val;
-                }
-            }
-        }
-    }
+pub const PARAMS: &[&ParamInfo] = &[];
 
-    println!("Total cals: {}, carried by: elf {}", max_cals, max_elf)
-}
-
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let elves = read_inventory(input)?;
+    let max_cals = match get_top(&elves, 1).first() {
+        Some(elf) => {
+            log::detail("carried_by_elf", elf.index);
+            elf.total
+        },
+        None => 0,
+    };
+    log::detail("max_calories", max_cals);
+    return Ok(Answer::from(max_cals));
 }
@@ -0,0 +1,83 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::common::parse::{lines, ParseError};
+
+// What one elf is carrying, in the order the snacks are listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    // Counting from 0, in the order they appear in the input
+    pub index: usize,
+    // The line the elf's list starts on
+    pub line: usize,
+    pub snacks: Vec<i64>,
+    pub total: i64,
+}
+
+// Elves are separated by blank lines, and the last one doesn't need one after it. A run of blank
+// lines is just one separator.
+pub fn read_inventory(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves: Vec<Elf> = Vec::new();
+    let mut snacks: Vec<i64> = Vec::new();
+    let mut first_line = 0;
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            add_elf(&mut elves, &mut snacks, first_line);
+            continue;
+        }
+        if snacks.is_empty() {
+            first_line = line.number;
+        }
+        snacks.push(line.parse(line.text.trim(), "a number of calories")?);
+    }
+    add_elf(&mut elves, &mut snacks, first_line);
+    return Ok(elves);
+}
+
+fn add_elf(elves: &mut Vec<Elf>, snacks: &mut Vec<i64>, line: usize) {
+    if snacks.is_empty() {
+        return;
+    }
+    let snacks = std::mem::take(snacks);
+    let total = snacks.iter().sum();
+    elves.push(Elf {index: elves.len(), line: line, snacks: snacks, total: total});
+}
+
+// The n elves carrying the most, most first. Only n of them are ever kept, in a heap with the
+// smallest on top so it's the one to go when a bigger one comes along. Ties go to the earlier elf.
+pub fn get_top(elves: &[Elf], n: usize) -> Vec<&Elf> {
+    let mut heap: BinaryHeap<Reverse<(i64, Reverse<usize>)>> = BinaryHeap::with_capacity(n.min(elves.len()) + 1);
+    for elf in elves.iter() {
+        heap.push(Reverse((elf.total, Reverse(elf.index))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    return heap.into_sorted_vec().into_iter().map(|Reverse((_, Reverse(index)))| &elves[index]).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_elf_counts() {
+        let elves = read_inventory("1000\n2000\n\n\n\n3000\n\n4000\n5000").unwrap();
+        let totals: Vec<(usize, usize, i64)> = elves.iter().map(|elf| (elf.index, elf.line, elf.total)).collect();
+        assert_eq!(totals, vec![(0, 1, 3000), (1, 6, 3000), (2, 8, 9000)]);
+        assert_eq!(elves[2].snacks, vec![4000, 5000]);
+        assert_eq!(read_inventory("").unwrap(), vec![]);
+        assert_eq!(read_inventory("1000\nlots\n").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn top_elves() {
+        let elves = read_inventory("5\n\n7\n\n5\n\n9\n\n1\n").unwrap();
+        let top = |n: usize| get_top(&elves, n).iter().map(|elf| elf.index).collect::<Vec<usize>>();
+        assert_eq!(top(0), vec![]);
+        assert_eq!(top(1), vec![3]);
+        assert_eq!(top(3), vec![3, 1, 0]);
+        assert_eq!(top(10), vec![3, 1, 0, 2, 4]);
+        assert_eq!(top(usize::MAX), vec![3, 1, 0, 2, 4]);
+    }
+}
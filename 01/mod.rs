@@ -0,0 +1,63 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "01_part1.rs"]
+pub mod part1;
+#[path = "01_part2.rs"]
+pub mod part2;
+
+pub mod inventory;
+pub mod stats;
+pub mod tally;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/01.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(24000)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(45000)));
+    }
+
+    #[test]
+    fn last_elf_without_a_blank_line() {
+        assert_eq!(solve(Part::One, "100\n\n200\n300"), Ok(Answer::from(500)));
+        assert_eq!(solve(Part::One, "100\n\n200\n300\n\n"), Ok(Answer::from(500)));
+        assert_eq!(solve(Part::One, ""), Ok(Answer::from(0)));
+    }
+
+    #[test]
+    fn other_top_counts() {
+        let mut params = Params::new();
+        params.insert("top", "1");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(24000)));
+        params.insert("top", "10");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(55000)));
+    }
+}
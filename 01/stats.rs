@@ -0,0 +1,97 @@
+// A summary of how many calories the elves are carrying between them
+
+pub const PERCENTILES: [u32; 5] = [10, 25, 50, 75, 90];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub total: i64,
+    pub min: i64,
+    pub max: i64,
+    pub mean: f64,
+    // The middle one, or halfway between the middle two
+    pub median: f64,
+    // Nearest-rank, so each one is what some elf is actually carrying
+    pub percentiles: Vec<(u32, i64)>,
+    pub histogram: Vec<Bucket>,
+}
+
+// The elves carrying from low to high calories, inclusive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub low: i64,
+    pub high: i64,
+    pub count: usize,
+}
+
+// None when there are no elves to summarise
+pub fn get_stats(totals: &[i64], num_buckets: usize) -> Option<Stats> {
+    if totals.is_empty() {
+        return None;
+    }
+    let mut sorted = totals.to_vec();
+    sorted.sort();
+    let count = sorted.len();
+    let total: i64 = sorted.iter().sum();
+    let median = if count % 2 == 1 {
+        sorted[count / 2] as f64
+    } else {
+        (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+    };
+    let percentiles = PERCENTILES.iter().map(|&p| (p, get_percentile(&sorted, p))).collect();
+
+    return Some(Stats {
+        count: count,
+        total: total,
+        min: sorted[0],
+        max: sorted[count - 1],
+        mean: total as f64 / count as f64,
+        median: median,
+        percentiles: percentiles,
+        histogram: get_histogram(&sorted, num_buckets),
+    });
+}
+
+fn get_percentile(sorted: &[i64], percentile: u32) -> i64 {
+    let rank = (percentile as usize * sorted.len()).div_ceil(100);
+    return sorted[rank.max(1) - 1];
+}
+
+// Buckets of the same width from the smallest total to the biggest. There can be fewer than asked
+// for when the totals are close together, but never an empty one at the end.
+fn get_histogram(sorted: &[i64], num_buckets: usize) -> Vec<Bucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = ((max - min + 1) as u64).div_ceil(num_buckets.max(1) as u64) as i64;
+    let mut buckets: Vec<Bucket> = Vec::new();
+    let mut low = min;
+    while low <= max {
+        let high = low + width - 1;
+        let count = sorted.iter().filter(|&&total| (low..=high).contains(&total)).count();
+        buckets.push(Bucket {low: low, high: high, count: count});
+        low = high + 1;
+    }
+    return buckets;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_stats() {
+        let stats = get_stats(&[6000, 4000, 11000, 24000, 10000], 4).unwrap();
+        assert_eq!((stats.count, stats.total, stats.min, stats.max), (5, 55000, 4000, 24000));
+        assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+        assert_eq!(stats.percentiles, vec![(10, 4000), (25, 6000), (50, 10000), (75, 11000), (90, 24000)]);
+        let counts: Vec<(i64, i64, usize)> = stats.histogram.iter().map(|bucket| (bucket.low, bucket.high, bucket.count)).collect();
+        assert_eq!(counts, vec![(4000, 9000, 2), (9001, 14001, 2), (14002, 19002, 0), (19003, 24003, 1)]);
+    }
+
+    #[test]
+    fn small_inventories() {
+        assert_eq!(get_stats(&[], 10), None);
+        let stats = get_stats(&[3, 5], 10).unwrap();
+        assert_eq!(stats.median, 4.0);
+        assert_eq!(stats.histogram, vec![Bucket {low: 3, high: 3, count: 1}, Bucket {low: 4, high: 4, count: 0}, Bucket {low: 5, high: 5, count: 1}]);
+    }
+}
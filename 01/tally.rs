@@ -0,0 +1,226 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+
+// Only this many bad lines are kept to report, though they're all counted
+pub const MAX_BAD_LINES: usize = 20;
+// Long enough to see what went wrong without keeping a whole stray blob around
+const MAX_BAD_TEXT: usize = 60;
+
+// One of the elves carrying the most. The shard is which of the inputs it came from, when a tally
+// has been merged from several.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopElf {
+    pub total: i64,
+    pub shard: usize,
+    // Counting from 0 within the shard
+    pub index: usize,
+    pub line: usize,
+}
+
+// Bigger is better: more calories, and then earlier in the input
+impl Ord for TopElf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.total.cmp(&other.total).then((other.shard, other.index).cmp(&(self.shard, self.index)));
+    }
+}
+
+impl PartialOrd for TopElf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadLine {
+    pub shard: usize,
+    pub line: usize,
+    pub text: String,
+}
+
+// Totals up an inventory a line at a time, for ones too big to read in whole. Only the top elves
+// and a few bad lines are kept, so it takes the same memory however long the input is. Lines that
+// aren't a number of calories are skipped and reported rather than stopping the whole thing.
+#[derive(Debug, Clone)]
+pub struct Tally {
+    shard: usize,
+    top_n: usize,
+    top: BinaryHeap<Reverse<TopElf>>,
+    pub num_lines: usize,
+    pub num_elves: usize,
+    pub total: i64,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub num_bad_lines: usize,
+    bad_lines: Vec<BadLine>,
+    // Where the elf being read started, and what it's carrying so far
+    current: Option<(usize, i64)>,
+}
+
+impl Tally {
+    pub fn new(top_n: usize, shard: usize) -> Tally {
+        return Tally {
+            shard: shard,
+            top_n: top_n,
+            top: BinaryHeap::new(),
+            num_lines: 0,
+            num_elves: 0,
+            total: 0,
+            min: None,
+            max: None,
+            num_bad_lines: 0,
+            bad_lines: Vec::new(),
+            current: None,
+        };
+    }
+
+    // The whole of one shard. Lines that aren't UTF-8 are bad lines like any other, so only an
+    // error reading the stream itself stops it.
+    pub fn read<R: BufRead>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut buffer: Vec<u8> = Vec::new();
+        loop {
+            buffer.clear();
+            if reader.read_until(b'\n', &mut buffer)? == 0 {
+                break;
+            }
+            self.add_line(&buffer);
+        }
+        self.end_elf();
+        return Ok(());
+    }
+
+    pub fn add_line(&mut self, bytes: &[u8]) {
+        self.num_lines += 1;
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim();
+        if text.is_empty() {
+            self.end_elf();
+            return;
+        }
+        let calories = std::str::from_utf8(bytes).ok().and_then(|_| text.parse::<i64>().ok());
+        let (line, total) = self.current.unwrap_or((self.num_lines, 0));
+        match calories.and_then(|calories| total.checked_add(calories)) {
+            Some(total) => self.current = Some((line, total)),
+            None => self.add_bad_line(self.shard, self.num_lines, text),
+        }
+    }
+
+    // Done by read at the end of a shard, and only needed otherwise after the last add_line
+    pub fn end_elf(&mut self) {
+        if let Some((line, total)) = self.current.take() {
+            let elf = TopElf {total: total, shard: self.shard, index: self.num_elves, line: line};
+            self.num_elves += 1;
+            self.total = self.total.saturating_add(total);
+            self.min = Some(self.min.map_or(total, |min| min.min(total)));
+            self.max = Some(self.max.map_or(total, |max| max.max(total)));
+            self.push_top(elf);
+        }
+    }
+
+    fn push_top(&mut self, elf: TopElf) {
+        self.top.push(Reverse(elf));
+        if self.top.len() > self.top_n {
+            self.top.pop();
+        }
+    }
+
+    fn add_bad_line(&mut self, shard: usize, line: usize, text: &str) {
+        self.num_bad_lines += 1;
+        if self.bad_lines.len() < MAX_BAD_LINES {
+            self.bad_lines.push(BadLine {shard: shard, line: line, text: text.chars().take(MAX_BAD_TEXT).collect()});
+        }
+    }
+
+    // Adds in a tally of another shard. The elves keep the shard they came from, so merging in any
+    // order gives the same top elves.
+    pub fn merge(&mut self, other: Tally) {
+        self.num_lines += other.num_lines;
+        self.num_elves += other.num_elves;
+        self.total = self.total.saturating_add(other.total);
+        self.min = [self.min, other.min].into_iter().flatten().min();
+        self.max = [self.max, other.max].into_iter().flatten().max();
+        for Reverse(elf) in other.top.into_iter() {
+            self.push_top(elf);
+        }
+        // Keeping the first bad lines of the shards put together, whatever order they're merged in
+        self.num_bad_lines += other.num_bad_lines;
+        self.bad_lines.extend(other.bad_lines);
+        self.bad_lines.sort_by_key(|bad_line| (bad_line.shard, bad_line.line));
+        self.bad_lines.truncate(MAX_BAD_LINES);
+    }
+
+    // Most first
+    pub fn get_top(&self) -> Vec<TopElf> {
+        return self.top.clone().into_sorted_vec().into_iter().map(|Reverse(elf)| elf).collect();
+    }
+
+    pub fn bad_lines(&self) -> &[BadLine] {
+        return &self.bad_lines;
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        return if self.num_elves == 0 {None} else {Some(self.total as f64 / self.num_elves as f64)};
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(input: &[u8], top_n: usize, shard: usize) -> Tally {
+        let mut tally = Tally::new(top_n, shard);
+        tally.read(input).unwrap();
+        return tally;
+    }
+
+    #[test]
+    fn streams_and_skips_bad_lines() {
+        let tally = tally(b"1000\n2000\n\n\n3000\nabc\n4000\n\n5000\n9223372036854775807\n\n\xff\n6000", 2, 0);
+        assert_eq!((tally.num_lines, tally.num_elves, tally.total, tally.min, tally.max), (13, 4, 21000, Some(3000), Some(7000)));
+        assert_eq!(tally.mean(), Some(5250.0));
+        let top: Vec<(i64, usize, usize)> = tally.get_top().iter().map(|elf| (elf.total, elf.index, elf.line)).collect();
+        assert_eq!(top, vec![(7000, 1, 5), (6000, 3, 13)]);
+        let bad: Vec<(usize, &str)> = tally.bad_lines().iter().map(|bad_line| (bad_line.line, bad_line.text.as_str())).collect();
+        assert_eq!(bad, vec![(6, "abc"), (10, "9223372036854775807"), (12, "\u{fffd}")]);
+        assert_eq!(tally.num_bad_lines, 3);
+    }
+
+    #[test]
+    fn bad_lines_are_capped() {
+        let tally = tally("x\n".repeat(MAX_BAD_LINES + 5).as_bytes(), 1, 0);
+        assert_eq!((tally.num_bad_lines, tally.bad_lines().len(), tally.num_elves), (MAX_BAD_LINES + 5, MAX_BAD_LINES, 0));
+        assert_eq!(tally.mean(), None);
+    }
+
+    #[test]
+    fn shards_merge() {
+        let input = include_str!("../examples/01.txt");
+        let whole = tally(input.as_bytes(), 3, 0);
+        let (first, second) = input.split_at(input.find("\n\n5000").unwrap() + 2);
+        let mut merged = tally(second.as_bytes(), 3, 1);
+        merged.merge(tally(first.as_bytes(), 3, 0));
+        assert_eq!((merged.num_elves, merged.total, merged.min, merged.max), (whole.num_elves, whole.total, whole.min, whole.max));
+        let totals = |tally: &Tally| tally.get_top().iter().map(|elf| elf.total).collect::<Vec<i64>>();
+        assert_eq!(totals(&merged), vec![24000, 11000, 10000]);
+        assert_eq!(totals(&merged), totals(&whole));
+        assert_eq!(merged.get_top()[0], TopElf {total: 24000, shard: 1, index: 1, line: 4});
+    }
+
+    #[test]
+    fn merged_bad_lines_are_the_first_ones() {
+        let input = "x\n".repeat(MAX_BAD_LINES);
+        let mut forwards = tally(input.as_bytes(), 1, 0);
+        forwards.merge(tally(input.as_bytes(), 1, 1));
+        let mut backwards = tally(input.as_bytes(), 1, 1);
+        backwards.merge(tally(input.as_bytes(), 1, 0));
+        assert_eq!(forwards.bad_lines(), backwards.bad_lines());
+        assert!(forwards.bad_lines().iter().all(|bad_line| bad_line.shard == 0));
+        assert_eq!((forwards.num_bad_lines, backwards.num_bad_lines), (2 * MAX_BAD_LINES, 2 * MAX_BAD_LINES));
+    }
+
+    #[test]
+    fn huge_top_n() {
+        let tally = tally(b"1000\n\n2000\n", usize::MAX, 0);
+        assert_eq!(tally.get_top().len(), 2);
+    }
+}
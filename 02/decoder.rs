@@ -0,0 +1,177 @@
+use crate::common::{Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+
+use super::game::{get_game, read_move, Game, Outcome};
+
+// Trying every way of reading a column of nine letters is already 362880 of them
+pub const MAX_DECODE_MOVES: usize = 9;
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+// What the second column of the guide could mean: the move to play, as part 1 has it, or how the
+// round has to end, as part 2 does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    Moves,
+    Outcomes,
+}
+
+// How many times each letter of the second column comes up against each of the opponent's moves,
+// which is all the scoring needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub letters: Vec<char>,
+    counts: Vec<Vec<i64>>,
+}
+
+// One way of reading the second column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoding {
+    // What each letter stands for, in order
+    pub meanings: Vec<String>,
+    pub score: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub reading: Reading,
+    pub num_decodings: usize,
+    // The letters in order, as the puzzle reads them
+    pub usual: Decoding,
+    // The first of them in that order when several score the same
+    pub best: Decoding,
+    pub worst: Decoding,
+    // The average over every decoding, as if they were all as likely
+    pub expected: f64,
+}
+
+impl Reading {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Reading::Moves => "moves",
+            Reading::Outcomes => "outcomes",
+        };
+    }
+}
+
+pub fn read_guide(game: &Game, input: &str) -> Result<Guide, ParseError> {
+    let first_letter = (b'Z' + 1 - game.num_moves() as u8) as char;
+    let mut counts = vec![vec![0; game.num_moves()]; game.num_moves()];
+    for line in lines(input) {
+        let theirs = read_move(game, &line, 1, 'A', "the opponent's move")?;
+        let letter = read_move(game, &line, 3, first_letter, "the second column")?;
+        counts[theirs.0][letter.0] += 1;
+    }
+    let letters = (0..game.num_moves()).map(|i| (first_letter as u8 + i as u8) as char).collect();
+    return Ok(Guide {letters: letters, counts: counts});
+}
+
+// Reading the letters as outcomes only works when there are three of them
+pub fn analyse(params: &Params, input: &str) -> Result<(Guide, Vec<Analysis>), SolveError> {
+    let game = get_game(params)?;
+    if game.num_moves() > MAX_DECODE_MOVES {
+        return Err(SolveError::BadParam {name: "moves".to_string(), value: format!("{} moves (at most {} can be decoded)", game.num_moves(), MAX_DECODE_MOVES)});
+    }
+    let guide = read_guide(&game, input)?;
+    let mut analyses = vec![analyse_reading(&game, &guide, Reading::Moves)];
+    if guide.letters.len() == OUTCOMES.len() {
+        analyses.push(analyse_reading(&game, &guide, Reading::Outcomes));
+    }
+    return Ok((guide, analyses));
+}
+
+fn analyse_reading(game: &Game, guide: &Guide, reading: Reading) -> Analysis {
+    // What each meaning scores against each of the opponent's moves
+    let (names, values): (Vec<String>, Vec<Vec<i64>>) = match reading {
+        Reading::Moves => (
+            game.moves().map(|m| game.name(m).to_string()).collect(),
+            game.moves().map(|theirs| game.moves().map(|mine| game.score(mine, theirs)).collect()).collect(),
+        ),
+        Reading::Outcomes => (
+            OUTCOMES.iter().map(|outcome| format!("{:?}", outcome).to_lowercase()).collect(),
+            game.moves().map(|theirs| OUTCOMES.iter().map(|&outcome| game.score(game.move_for(theirs, outcome), theirs)).collect()).collect(),
+        ),
+    };
+    let get_decoding = |meanings: &[usize]| {
+        let score = guide.counts.iter().zip(values.iter())
+            .map(|(counts, values)| counts.iter().zip(meanings.iter()).map(|(count, &meaning)| count * values[meaning]).sum::<i64>())
+            .sum();
+        return Decoding {meanings: meanings.iter().map(|&meaning| names[meaning].clone()).collect(), score: score};
+    };
+
+    let permutations = get_permutations(guide.letters.len());
+    let decodings: Vec<Decoding> = permutations.iter().map(|meanings| get_decoding(meanings)).collect();
+    let best = decodings.iter().fold(&decodings[0], |best, decoding| if decoding.score > best.score {decoding} else {best});
+    let worst = decodings.iter().fold(&decodings[0], |worst, decoding| if decoding.score < worst.score {decoding} else {worst});
+    let total: i64 = decodings.iter().map(|decoding| decoding.score).sum();
+    return Analysis {
+        reading: reading,
+        num_decodings: decodings.len(),
+        usual: decodings[0].clone(),
+        best: best.clone(),
+        worst: worst.clone(),
+        expected: total as f64 / decodings.len() as f64,
+    };
+}
+
+// Every ordering of 0..n, in lexicographic order so the first is 0, 1, 2...
+fn get_permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut permutations: Vec<Vec<usize>> = Vec::new();
+    for first in 0..n {
+        for rest in get_permutations(n - 1) {
+            let mut permutation = vec![first];
+            permutation.extend(rest.iter().map(|&i| if i >= first {i + 1} else {i}));
+            permutations.push(permutation);
+        }
+    }
+    return permutations;
+}
+
+// Like "X=rock, Y=paper, Z=scissors"
+pub fn describe(guide_letters: &[char], decoding: &Decoding) -> String {
+    let pairs: Vec<String> = guide_letters.iter().zip(decoding.meanings.iter()).map(|(letter, meaning)| format!("{}={}", letter, meaning)).collect();
+    return pairs.join(", ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/02.txt");
+
+    #[test]
+    fn permutations() {
+        assert_eq!(get_permutations(3), vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 0, 2], vec![1, 2, 0], vec![2, 0, 1], vec![2, 1, 0]]);
+        assert_eq!(get_permutations(5).len(), 120);
+    }
+
+    #[test]
+    fn example_decodings() {
+        let (guide, analyses) = analyse(&Params::new(), EXAMPLE).unwrap();
+        let (moves, outcomes) = (&analyses[0], &analyses[1]);
+        assert_eq!((moves.reading, moves.num_decodings, moves.usual.score), (Reading::Moves, 6, 15));
+        assert_eq!((outcomes.reading, outcomes.num_decodings, outcomes.usual.score), (Reading::Outcomes, 6, 12));
+        // A Y, B X, C Z: read as moves there's a way to win every round and one to lose them all,
+        // but read as outcomes each one has to come up once
+        assert_eq!((moves.best.score, moves.best.meanings.clone()), (24, vec!["scissors".to_string(), "paper".to_string(), "rock".to_string()]));
+        assert_eq!((moves.worst.score, moves.worst.meanings.clone()), (6, vec!["rock".to_string(), "scissors".to_string(), "paper".to_string()]));
+        assert_eq!((outcomes.best.score, outcomes.worst.score, outcomes.expected), (18, 12, 15.0));
+        assert_eq!(moves.expected, 15.0);
+        assert_eq!(describe(&guide.letters, &outcomes.usual), "X=loss, Y=draw, Z=win");
+    }
+
+    #[test]
+    fn bigger_games() {
+        let mut params = Params::new();
+        params.insert("moves", "rock spock paper lizard scissors");
+        let (guide, analyses) = analyse(&params, "A V\nB Z\n").unwrap();
+        assert_eq!(guide.letters, vec!['V', 'W', 'X', 'Y', 'Z']);
+        assert_eq!(analyses.len(), 1);
+        assert_eq!(analyses[0].num_decodings, 120);
+        params.insert("moves", "a b c d e f g h i j k");
+        assert!(matches!(analyse(&params, "A V\n"), Err(SolveError::BadParam {..})));
+    }
+}
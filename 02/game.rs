@@ -0,0 +1,240 @@
+use std::str::FromStr;
+
+use crate::common::{Params, SolveError};
+use crate::common::params::Param;
+use crate::common::parse::{Line, ParseError};
+
+pub const MOVES: Param<Game> = Param::new("moves", "rock paper scissors",
+    "the moves in their cycle, each beating the half before it, as names or name=score (scores default to 1, 2, 3...)");
+pub const OUTCOME_SCORES: Param<OutcomeScores> = Param::new("outcome_scores", "0 3 6", "the scores for a loss, a draw and a win");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+// A move by its place in the cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Move(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeScores {
+    pub loss: i64,
+    pub draw: i64,
+    pub win: i64,
+}
+
+impl FromStr for OutcomeScores {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let scores: Vec<i64> = text.split_whitespace().map(|score| score.parse::<i64>()).collect::<Result<_, _>>()
+            .map_err(|_| format!("'{}' should be three whole numbers", text))?;
+        return match scores[..] {
+            [loss, draw, win] => Ok(OutcomeScores {loss: loss, draw: draw, win: win}),
+            _ => Err(format!("'{}' should be three whole numbers", text)),
+        };
+    }
+}
+
+// Rock, paper, scissors with any odd number of moves. They go round in a circle, and each one beats
+// the half of the others just before it and loses to the half just after, so every pair of moves
+// has a winner. Three is the usual game; five in the right order is rock, paper, scissors, lizard, Spock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    move_scores: Vec<i64>,
+    outcome_scores: OutcomeScores,
+}
+
+impl Game {
+    pub fn new(names: &[&str]) -> Result<Game, String> {
+        if (names.len() < 3) || names.len().is_multiple_of(2) {
+            return Err(format!("A game needs an odd number of moves, at least three, not {}", names.len()));
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for &name in names.iter() {
+            if seen.contains(&name) {
+                return Err(format!("'{}' is in the game twice", name));
+            }
+            seen.push(name);
+        }
+        return Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            move_scores: (1..=names.len() as i64).collect(),
+            outcome_scores: OutcomeScores {loss: 0, draw: 3, win: 6},
+        });
+    }
+
+    pub fn with_move_scores(mut self, scores: &[i64]) -> Result<Game, String> {
+        if scores.len() != self.names.len() {
+            return Err(format!("{} scores for {} moves", scores.len(), self.names.len()));
+        }
+        self.move_scores = scores.to_vec();
+        return Ok(self);
+    }
+
+    pub fn with_outcome_scores(mut self, scores: OutcomeScores) -> Game {
+        self.outcome_scores = scores;
+        return self;
+    }
+
+    pub fn num_moves(&self) -> usize {
+        return self.names.len();
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        return (0..self.names.len()).map(Move);
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        return &self.names[m.0];
+    }
+
+    // How `mine` does against `theirs`
+    pub fn outcome(&self, mine: Move, theirs: Move) -> Outcome {
+        let n = self.num_moves();
+        let ahead = (mine.0 + n - theirs.0) % n;
+        return if ahead == 0 {
+            Outcome::Draw
+        } else if ahead <= n / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        };
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> i64 {
+        return match outcome {
+            Outcome::Loss => self.outcome_scores.loss,
+            Outcome::Draw => self.outcome_scores.draw,
+            Outcome::Win => self.outcome_scores.win,
+        };
+    }
+
+    pub fn move_score(&self, m: Move) -> i64 {
+        return self.move_scores[m.0];
+    }
+
+    // What playing `mine` against `theirs` is worth
+    pub fn score(&self, mine: Move, theirs: Move) -> i64 {
+        return self.move_score(mine) + self.outcome_score(self.outcome(mine, theirs));
+    }
+
+    // With more than three moves there are several that win (or lose), so this picks the one worth
+    // the most, and the first of those if they tie
+    pub fn move_for(&self, theirs: Move, outcome: Outcome) -> Move {
+        return self.moves()
+            .filter(|&mine| self.outcome(mine, theirs) == outcome)
+            .max_by_key(|&mine| (self.move_score(mine), std::cmp::Reverse(mine)))
+            .expect("Every outcome is possible against every move");
+    }
+}
+
+// Each move needs a letter in the strategy guide
+const MAX_MOVES: usize = 25;
+
+pub fn get_game(params: &Params) -> Result<Game, SolveError> {
+    let game = params.value(&MOVES)?;
+    if game.num_moves() > MAX_MOVES {
+        return Err(SolveError::BadParam {name: MOVES.info.name.to_string(), value: format!("{} moves", game.num_moves())});
+    }
+    return Ok(game.with_outcome_scores(params.value(&OUTCOME_SCORES)?));
+}
+
+// The guide gives the moves as consecutive letters starting from `first`: A, B, C for the opponent,
+// or for yours, ones that end at Z so the usual three are X, Y, Z
+pub fn read_move(game: &Game, line: &Line, column: usize, first: char, whose: &str) -> Result<Move, ParseError> {
+    let letters: Vec<char> = (0..game.num_moves()).map(|i| (first as u8 + i as u8) as char).collect();
+    let expected = format!("{} ({})", whose, describe_letters(&letters));
+    let letter = line.char_at(column, &expected)?;
+    return match letters.iter().position(|&c| c == letter) {
+        Some(index) => Ok(Move(index)),
+        None => Err(line.error_at(column, &letter.to_string(), &expected)),
+    };
+}
+
+pub fn describe_letters(letters: &[char]) -> String {
+    let names: Vec<String> = letters.iter().map(|c| c.to_string()).collect();
+    return match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => names.join(""),
+    };
+}
+
+// Names separated by spaces, each optionally with its score, like "rock=1 paper=2 scissors=3"
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut names: Vec<&str> = Vec::new();
+        let mut scores: Vec<Option<i64>> = Vec::new();
+        for word in text.split_whitespace() {
+            let (name, score) = match word.split_once('=') {
+                Some((name, score)) => (name, Some(score.parse::<i64>().map_err(|_| format!("'{}' isn't a whole number", score))?)),
+                None => (word, None),
+            };
+            names.push(name);
+            scores.push(score);
+        }
+        let game = Game::new(&names)?;
+        let scores: Vec<i64> = scores.iter().zip(game.move_scores.iter()).map(|(score, default)| score.unwrap_or(*default)).collect();
+        return game.with_move_scores(&scores);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_move(game: &Game, name: &str) -> Move {
+        return game.moves().find(|&m| game.name(m) == name).unwrap();
+    }
+
+    #[test]
+    fn rock_paper_scissors() {
+        let game: Game = MOVES.info.default.parse().unwrap();
+        let [rock, paper, scissors] = [Move(0), Move(1), Move(2)];
+        assert_eq!(game.outcome(paper, rock), Outcome::Win);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(game.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(game.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!((game.score(paper, rock), game.score(rock, paper), game.score(scissors, scissors)), (8, 1, 6));
+        assert_eq!(game.move_for(rock, Outcome::Loss), scissors);
+    }
+
+    // Each move beats the two before it
+    #[test]
+    fn lizard_spock() {
+        let game: Game = "rock spock paper lizard scissors".parse().unwrap();
+        let beats = [
+            ("rock", ["scissors", "lizard"]),
+            ("paper", ["rock", "spock"]),
+            ("scissors", ["paper", "lizard"]),
+            ("lizard", ["spock", "paper"]),
+            ("spock", ["scissors", "rock"]),
+        ];
+        for (winner, losers) in beats {
+            for loser in losers {
+                assert_eq!(game.outcome(get_move(&game, winner), get_move(&game, loser)), Outcome::Win, "{} should beat {}", winner, loser);
+                assert_eq!(game.outcome(get_move(&game, loser), get_move(&game, winner)), Outcome::Loss);
+            }
+        }
+        // Both paper and spock beat rock, and spock is only worth 2
+        assert_eq!(game.move_for(get_move(&game, "rock"), Outcome::Win), get_move(&game, "paper"));
+    }
+
+    #[test]
+    fn scores_and_bad_games() {
+        let game: Game = "a=10 b c=-1".parse().unwrap();
+        let game = game.with_outcome_scores("1 2 3".parse().unwrap());
+        assert_eq!((game.score(Move(0), Move(2)), game.score(Move(1), Move(1)), game.score(Move(2), Move(0))), (13, 4, 0));
+        assert!("rock paper".parse::<Game>().is_err());
+        assert!("rock paper rock".parse::<Game>().is_err());
+        assert!("rock=x paper scissors".parse::<Game>().is_err());
+        assert!("1 2".parse::<OutcomeScores>().is_err());
+        assert!(game.with_move_scores(&[1, 2]).is_err());
+    }
+}
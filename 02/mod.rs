@@ -0,0 +1,63 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "02_part1.rs"]
+pub mod part1;
+#[path = "02_part2.rs"]
+pub mod part2;
+
+pub mod decoder;
+pub mod game;
+pub mod tournament;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/02.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(15)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(12)));
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let mut params = Params::new();
+        params.insert("moves", "rock spock paper lizard scissors");
+        // With five moves yours are V to Z, so this is paper against rock and scissors against spock.
+        // Needing a loss against rock it's lizard or scissors, and a win against spock it's paper or
+        // lizard, and the one worth more is played.
+        let input = "A X\nB Z\n";
+        assert_eq!(solve_with_params(Part::One, input, &params), Ok(Answer::from(9 + 5)));
+        assert_eq!(solve_with_params(Part::Two, input, &params), Ok(Answer::from(5 + 10)));
+        params.insert("outcome_scores", "0 1 2");
+        assert_eq!(solve_with_params(Part::Two, input, &params), Ok(Answer::from(5 + 6)));
+        let error = solve_with_params(Part::One, "A F\n", &params).unwrap_err();
+        assert!(error.to_string().contains("your move (V, W, X, Y or Z)"), "{}", error);
+    }
+}
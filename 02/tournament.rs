@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::common::parse::{lines, ParseError};
+use crate::common::random::Rng;
+
+use super::game::{read_move, Game, Move, Outcome};
+
+// How a player picks its moves. The guide ones need the strategy guide to play from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Strategy {
+    // The second column of the guide read as moves, over and over
+    Guide,
+    // The first column, which is what the elf in the puzzle plays
+    Elf,
+    // Each move equally likely
+    Random,
+    // Random, weighted by how often each move should come up
+    Biased(Vec<f64>),
+    // Whatever beats the opponent's last move
+    Counter,
+    // Whatever beats the move the opponent has most often played after their last one
+    Markov,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    // Like "markov", or "biased:3,1,1" with a weight for each move
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        return match text.split_once(':') {
+            None => match text {
+                "guide" => Ok(Strategy::Guide),
+                "elf" => Ok(Strategy::Elf),
+                "random" => Ok(Strategy::Random),
+                "counter" => Ok(Strategy::Counter),
+                "markov" => Ok(Strategy::Markov),
+                _ => Err(format!("'{}' isn't a strategy (expected guide, elf, random, biased:<weights>, counter or markov)", text)),
+            },
+            Some(("biased", weights)) => {
+                let weights: Vec<f64> = weights.split(',').map(|weight| weight.trim().parse::<f64>()).collect::<Result<_, _>>()
+                    .map_err(|_| format!("'{}' should be weights separated by commas", weights))?;
+                if weights.iter().any(|&weight| !weight.is_finite() || weight < 0.0) || weights.iter().sum::<f64>() <= 0.0 {
+                    return Err(format!("'{}' needs weights that aren't negative and aren't all zero", text));
+                }
+                Ok(Strategy::Biased(weights))
+            },
+            Some(_) => Err(format!("'{}' isn't a strategy (only biased takes weights)", text)),
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub strategy: Strategy,
+}
+
+impl FromStr for Player {
+    type Err = String;
+
+    // The strategy, optionally named, like "cautious=biased:3,1,1". Without a name it's named after
+    // its strategy.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (name, strategy) = match text.split_once('=') {
+            Some((name, strategy)) => (name, strategy),
+            None => (text, text),
+        };
+        return Ok(Player {name: name.to_string(), strategy: strategy.parse()?});
+    }
+}
+
+// Both columns of the guide, read as moves
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GuideMoves {
+    pub elf: Vec<Move>,
+    pub guide: Vec<Move>,
+}
+
+pub fn read_guide_moves(game: &Game, input: &str) -> Result<GuideMoves, ParseError> {
+    let first_letter = (b'Z' + 1 - game.num_moves() as u8) as char;
+    let mut moves = GuideMoves::default();
+    for line in lines(input) {
+        moves.elf.push(read_move(game, &line, 1, 'A', "the opponent's move")?);
+        moves.guide.push(read_move(game, &line, 3, first_letter, "your move")?);
+    }
+    // The players following it go round it again and again
+    if moves.guide.is_empty() {
+        return Err(ParseError::whole_input("an empty guide", "at least one round"));
+    }
+    return Ok(moves);
+}
+
+// What a player knows in the middle of a match
+struct Seat<'a> {
+    strategy: &'a Strategy,
+    played: Vec<Move>,
+    // How often the opponent has played each move straight after each one
+    transitions: HashMap<(Move, Move), usize>,
+}
+
+impl<'a> Seat<'a> {
+    fn choose(&self, game: &Game, guide: &GuideMoves, theirs: &[Move], rng: &mut Rng) -> Move {
+        let round = self.played.len();
+        return match self.strategy {
+            Strategy::Guide => guide.guide[round % guide.guide.len()],
+            Strategy::Elf => guide.elf[round % guide.elf.len()],
+            Strategy::Random => Move(rng.index(game.num_moves())),
+            Strategy::Biased(weights) => pick_weighted(weights, game.num_moves(), rng),
+            Strategy::Counter => match theirs.last() {
+                Some(&last) => game.move_for(last, Outcome::Win),
+                None => Move(rng.index(game.num_moves())),
+            },
+            Strategy::Markov => {
+                let predicted = theirs.last().and_then(|&last| game.moves()
+                    .map(|next| (self.transitions.get(&(last, next)).copied().unwrap_or(0), next))
+                    .filter(|&(count, _)| count > 0)
+                    .max_by_key(|&(count, next)| (count, std::cmp::Reverse(next)))
+                    .map(|(_, next)| next));
+                match predicted {
+                    Some(predicted) => game.move_for(predicted, Outcome::Win),
+                    None => Move(rng.index(game.num_moves())),
+                }
+            },
+        };
+    }
+
+    fn remember(&mut self, mine: Move, their_last: Option<Move>, theirs: Move) {
+        self.played.push(mine);
+        if let Some(their_last) = their_last {
+            *self.transitions.entry((their_last, theirs)).or_insert(0) += 1;
+        }
+    }
+}
+
+// Weights past the number of moves are ignored, and missing ones count as zero
+fn pick_weighted(weights: &[f64], num_moves: usize, rng: &mut Rng) -> Move {
+    let weights = &weights[..weights.len().min(num_moves)];
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Move(rng.index(num_moves));
+    }
+    let mut draw = rng.next_f64() * total;
+    for (i, &weight) in weights.iter().enumerate() {
+        if draw < weight {
+            return Move(i);
+        }
+        draw -= weight;
+    }
+    return Move(weights.iter().rposition(|&weight| weight > 0.0).unwrap_or(0));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchResult {
+    // Rounds won by each player, and drawn
+    pub wins: [usize; 2],
+    pub draws: usize,
+    pub scores: [i64; 2],
+}
+
+pub fn play_match(game: &Game, guide: &GuideMoves, players: [&Player; 2], rounds: usize, rng: &mut Rng) -> MatchResult {
+    let mut seats = players.map(|player| Seat {strategy: &player.strategy, played: Vec::new(), transitions: HashMap::new()});
+    let mut result = MatchResult::default();
+    for _ in 0..rounds {
+        let moves = [
+            seats[0].choose(game, guide, &seats[1].played, rng),
+            seats[1].choose(game, guide, &seats[0].played, rng),
+        ];
+        for i in 0..2 {
+            result.scores[i] += game.score(moves[i], moves[1 - i]);
+        }
+        match game.outcome(moves[0], moves[1]) {
+            Outcome::Win => result.wins[0] += 1,
+            Outcome::Loss => result.wins[1] += 1,
+            Outcome::Draw => result.draws += 1,
+        }
+        let last = [seats[0].played.last().copied(), seats[1].played.last().copied()];
+        seats[0].remember(moves[0], last[1], moves[1]);
+        seats[1].remember(moves[1], last[0], moves[0]);
+    }
+    return result;
+}
+
+// A player's matches: won is winning more rounds than the other player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub rounds_won: usize,
+    pub score: i64,
+}
+
+// Every player plays every other once, and the standings come back best first: by matches won, then
+// drawn, then total score
+pub fn play_tournament(game: &Game, guide: &GuideMoves, players: &[Player], rounds: usize, rng: &mut Rng) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players.iter()
+        .map(|player| Standing {name: player.name.clone(), wins: 0, draws: 0, losses: 0, rounds_won: 0, score: 0})
+        .collect();
+    for i in 0..players.len() {
+        for j in (i + 1)..players.len() {
+            let result = play_match(game, guide, [&players[i], &players[j]], rounds, rng);
+            for (k, player) in [i, j].into_iter().enumerate() {
+                let standing = &mut standings[player];
+                standing.rounds_won += result.wins[k];
+                standing.score += result.scores[k];
+                match result.wins[k].cmp(&result.wins[1 - k]) {
+                    std::cmp::Ordering::Greater => standing.wins += 1,
+                    std::cmp::Ordering::Equal => standing.draws += 1,
+                    std::cmp::Ordering::Less => standing.losses += 1,
+                }
+            }
+        }
+    }
+    standings.sort_by_key(|standing| (std::cmp::Reverse((standing.wins, standing.draws, standing.score)), standing.name.clone()));
+    return standings;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/02.txt");
+
+    fn get_players(specs: &[&str]) -> Vec<Player> {
+        return specs.iter().map(|spec| spec.parse().unwrap()).collect();
+    }
+
+    #[test]
+    fn players() {
+        assert_eq!("markov".parse::<Player>(), Ok(Player {name: "markov".to_string(), strategy: Strategy::Markov}));
+        assert_eq!("rocky=biased:1, 0,0".parse::<Player>().unwrap().strategy, Strategy::Biased(vec![1.0, 0.0, 0.0]));
+        assert!("biased:0,0".parse::<Strategy>().is_err());
+        assert!("biased:1,x".parse::<Strategy>().is_err());
+        assert!("counter:1".parse::<Strategy>().is_err());
+        assert!("clever".parse::<Strategy>().is_err());
+    }
+
+    // The guide's own match against the elf scores what part 1 does
+    #[test]
+    fn guide_against_elf() {
+        let game: Game = "rock paper scissors".parse().unwrap();
+        let guide = read_guide_moves(&game, EXAMPLE).unwrap();
+        let players = get_players(&["guide", "elf"]);
+        let result = play_match(&game, &guide, [&players[0], &players[1]], 3, &mut Rng::new(1));
+        assert_eq!((result.scores[0], result.wins, result.draws), (15, [1, 1], 1));
+    }
+
+    #[test]
+    fn learning_strategies() {
+        let game: Game = "rock paper scissors".parse().unwrap();
+        let guide = read_guide_moves(&game, EXAMPLE).unwrap();
+        // Always rock is beaten by paper from the second round on, and the guide's cycle of three
+        // is worked out by markov once it's been round once
+        let players = get_players(&["rocky=biased:1,0,0", "counter", "guide", "markov"]);
+        let counter = play_match(&game, &guide, [&players[0], &players[1]], 100, &mut Rng::new(7));
+        assert!(counter.wins[1] >= 99);
+        let markov = play_match(&game, &guide, [&players[2], &players[3]], 100, &mut Rng::new(7));
+        assert!(markov.wins[1] >= 95);
+    }
+
+    #[test]
+    fn seeded_tournaments() {
+        let game: Game = "rock paper scissors".parse().unwrap();
+        let guide = read_guide_moves(&game, EXAMPLE).unwrap();
+        let players = get_players(&["guide", "elf", "random", "counter", "markov"]);
+        let standings = play_tournament(&game, &guide, &players, 200, &mut Rng::new(2022));
+        assert_eq!(standings, play_tournament(&game, &guide, &players, 200, &mut Rng::new(2022)));
+        for standing in standings.iter() {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 4);
+        }
+        assert_eq!(standings[0].name, "markov");
+    }
+}
@@ -1,44 +1,30 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+#![allow(clippy::useless_vec)]
 
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, Line, ParseError};
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
+
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
     let mut output = 0;
-    if let Ok(lines) = read_lines(file_name) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(val) = line {
-                let knapsack_priority = get_knapsack_priority(val);
-                output += knapsack_priority;
-            }
-        }
+    for line in lines(input) {
+        line.check_chars(|c| c.is_ascii_alphabetic(), "an item letter (a-z or A-Z)")?;
+        let knapsack_priority = get_knapsack_priority(&line)?;
+        output += knapsack_priority;
     }
-    println!("Output: {}", output);
+    return Ok(Answer::from(output));
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
 
-fn get_knapsack_priority(val: String) -> i32 {
-    let (compartment_1, compartment_2) = val.split_at(val.len()/2);
-    let common: char = find_common_letter(compartment_1, compartment_2);
-    return get_char_priority(common);
+fn get_knapsack_priority(line: &Line) -> Result<i32, ParseError> {
+    let (compartment_1, compartment_2) = line.text.split_at(line.text.len()/2);
+    let common: char = find_common_letter(compartment_1, compartment_2)
+        .ok_or(line.error(line.text, "an item in both compartments"))?;
+    return Ok(get_char_priority(common));
 }
 
 
-fn find_common_letter(str_1: &str, str_2: &str) -> char {
+fn find_common_letter(str_1: &str, str_2: &str) -> Option<char> {
     let mut exists = vec![false; 52];
     for c in str_1.chars() {
         exists[get_char_priority(c) as usize - 1] = true;
@@ -46,10 +32,10 @@ fn find_common_letter(str_1: &str, str_2: &str) -> char {
     for c in str_2.chars() {
         let is_common = exists[get_char_priority(c) as usize - 1];
         if is_common {
-            return c;
+            return Some(c);
         }
     }
-    panic!("No common characters!")
+    return None;
 }
 
 fn get_char_priority(letter: char) -> i32 {
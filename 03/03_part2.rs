@@ -1,61 +1,48 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, Line, ParseError};
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
     let mut output = 0;
     let mut group = Vec::new();
-    if let Ok(lines) = read_lines(file_name) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(val) = line {
-                group.push(val);
-                if group.len() == 3 {
-                    let group_priority = get_group_priority(&group[0], &group[1], &group[2]);
-                    output += group_priority;
-                    group = Vec::new();
-                }
-            }
+    for line in lines(input) {
+        line.check_chars(|c| c.is_ascii_alphabetic(), "an item letter (a-z or A-Z)")?;
+        group.push(line);
+        if group.len() == 3 {
+            let group_priority = get_group_priority(&group[0], &group[1], &group[2])?;
+            output += group_priority;
+            group = Vec::new();
         }
     }
-    println!("Output: {}", output);
+    if let Some(line) = group.last() {
+        return Err(line.error_at(line.text.chars().count() + 1, "end of input", "a complete group of three elves").into());
+    }
+    return Ok(Answer::from(output));
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
 
-fn get_group_priority(str_1: &String, str_2: &String, str_3: &String) -> i32 {
-    let common: char = find_common_letter(str_1, str_2, str_3);
-    return get_char_priority(common);
+fn get_group_priority(line_1: &Line, line_2: &Line, line_3: &Line) -> Result<i32, ParseError> {
+    let common: char = find_common_letter(line_1.text, line_2.text, line_3.text)
+        .ok_or(line_3.error(line_3.text, "an item carried by all three elves"))?;
+    return Ok(get_char_priority(common));
 }
 
 
-fn find_common_letter(str_1: &String, str_2: &String, str_3: &String) -> char {
+fn find_common_letter(str_1: &str, str_2: &str, str_3: &str) -> Option<char> {
     let exists_1 = get_letters(str_1);
     let exists_2 = get_letters(str_2);
     for c in str_3.chars() {
         let is_common_1 = exists_1[get_char_priority(c) as usize - 1];
         let is_common_2 = exists_2[get_char_priority(c) as usize - 1];
         if is_common_1 & is_common_2 {
-            return c;
+            return Some(c);
         }
     }
-    panic!("No common characters!")
+    return None;
 }
 
-fn get_letters(in_str: &String) -> Vec<bool> {
+fn get_letters(in_str: &str) -> Vec<bool> {
     let mut exists = vec![false; 52];
     for c in in_str.chars() {
         exists[get_char_priority(c) as usize - 1] = true;
@@ -0,0 +1,35 @@
+use crate::common::{Answer, Params, Part, SolveError};
+
+#[path = "03_part1.rs"]
+pub mod part1;
+#[path = "03_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/03.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(157)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(70)));
+    }
+}
@@ -1,7 +1,5 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, Line, ParseError};
 
 
 struct CrateStack {
@@ -52,13 +50,15 @@ impl CrateStacks {
         }
     }
 
-    fn read_top_crates(&self) {
+    fn read_top_crates(&self) -> String {
         let mut output: String = "".to_string();
         for i in 0..self.num_stacks {
-            let new_char = self.stacks[i as usize].crates.last().expect("Stack should have a crate!"); 
-            output += &new_char.to_string();
+            // The moves can empty a stack, and then there's nothing on top of it
+            if let Some(top_crate) = self.stacks[i as usize].crates.last() {
+                output += &top_crate.to_string();
+            }
         }
-        println!("{}", output);
+        return output;
     }
 
     fn reverse_stacks_after_init(&mut self) {
@@ -76,91 +76,94 @@ impl CrateStacks {
     }
 }
 
-#[derive(PartialEq)]
-enum InputState {
-    Initialise,
-    Rearrange,
-}
-
-
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut line_num = 0;
-    let mut state = InputState::Initialise;
-    let mut stacks: Option<CrateStacks> = None;
-    if let Ok(lines) = read_lines(file_name) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(val) = line {
-                if line_num == 0 {
-                    stacks = initialise_stacks(val);
-                }
-                else if val == "" {
-                    state = InputState::Rearrange;
-                }
-                else if state == InputState::Initialise {
-                    stacks = update_init_stack(stacks, val);
-                }
-                else {
-                    stacks = update_stack(stacks, val);
-                }
-                line_num += 1;
-                // if state == InputState::Rearrange {&stacks.expect("Stack should exist!").read_top_crates();}
-            }
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let input_lines: Vec<Line> = lines(input).collect();
+    // The drawing runs up to the first blank line, and the moves come after it
+    let drawing_end = input_lines.iter().position(|line| line.text.is_empty()).unwrap_or(input_lines.len());
+    let mut stacks = read_drawing(&input_lines[..drawing_end])?;
+    for line in input_lines.iter().skip(drawing_end + 1) {
+        if !line.text.is_empty() {
+            update_stack(&mut stacks, line)?;
         }
     }
-    stacks.expect("Stack should exist!").read_top_crates();
-}
-
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    return Ok(Answer::from(stacks.read_top_crates()));
 }
 
 
-fn initialise_stacks(top_slice: String) -> Option<CrateStacks> {
-    let length: i32 = top_slice.len() as i32;
-    let num_stacks = (length + 1)/4;
 
-    let mut stacks = Some(CrateStacks::new(num_stacks));
-    stacks = update_init_stack(stacks, top_slice);
-    return stacks;
+// The row of stack numbers along the bottom says how many stacks there are
+fn read_drawing(drawing: &[Line]) -> Result<CrateStacks, ParseError> {
+    let (number_row, crate_rows) = drawing.split_last().ok_or(ParseError::whole_input("an empty input", "a drawing of the crate stacks"))?;
+    let mut stacks = CrateStacks::new(read_stack_numbers(number_row)?);
+    for row in crate_rows {
+        update_init_stack(&mut stacks, row)?;
+    }
+    stacks.reverse_stacks_after_init();
+    return Ok(stacks);
 }
 
 
-fn update_init_stack(option_stacks: Option<CrateStacks>, stack_slice: String) -> Option<CrateStacks> {
-    let mut stacks = option_stacks.expect("Should have a stack here!");
-    for i in 0..stacks.num_stacks {
-        let crate_ind: i32 = i*4 + 1;
-        let crate_value: char = stack_slice.chars().nth(crate_ind as usize).unwrap();
-        if crate_value == '1' {
-            stacks.reverse_stacks_after_init();
-            return Some(stacks);
+fn read_stack_numbers(number_row: &Line) -> Result<i32, ParseError> {
+    let mut num_stacks: i32 = 0;
+    for word in number_row.text.split_whitespace() {
+        let expected = format!("stack number {}", num_stacks + 1);
+        let stack_num: i32 = number_row.parse(word, &expected)?;
+        if stack_num != num_stacks + 1 {
+            return Err(number_row.error(word, &expected));
         }
-        else if crate_value == ' ' {
+        num_stacks += 1;
+    }
+    if num_stacks == 0 {
+        return Err(number_row.error_at(1, number_row.text, "the stack numbers, like ' 1   2   3'"));
+    }
+    return Ok(num_stacks);
+}
 
-        }
-        else {
-            stacks.put_crate_on_stack_a(i, crate_value);
+
+fn update_init_stack(stacks: &mut CrateStacks, stack_slice: &Line) -> Result<(), ParseError> {
+    let row: Vec<char> = stack_slice.text.chars().collect();
+    let row_width = (stacks.num_stacks * 4 - 1) as usize;
+    if row.len() > row_width {
+        let extra: String = row[row_width..].iter().collect();
+        return Err(stack_slice.error_at(row_width + 1, &extra, &format!("the end of the row after {} stacks", stacks.num_stacks)));
+    }
+    for i in 0..stacks.num_stacks {
+        let crate_ind: usize = (i*4) as usize;
+        // Editors often strip the trailing spaces of a row, so a short row just has empty stacks at the end
+        let cell: Vec<char> = (crate_ind..crate_ind + 4).map(|j| row.get(j).copied().unwrap_or(' ')).collect();
+        match cell[..] {
+            ['[', crate_value, ']', ' '] if crate_value != ' ' => stacks.put_crate_on_stack_a(i, crate_value),
+            [' ', ' ', ' ', ' '] => (),
+            _ => {
+                let found: String = cell.iter().collect();
+                return Err(stack_slice.error_at(crate_ind + 1, found.trim_end(), "a crate like '[A]' or a gap"));
+            },
         }
     }
-    return Some(stacks);
+    return Ok(());
 }
 
-fn update_stack(option_stacks: Option<CrateStacks>, instructions: String) -> Option<CrateStacks> {
-    let mut stacks = option_stacks.expect("Should have a stack here!");
-    let instr_vec: Vec<&str> = instructions.split(" ").collect();
-    let num_crates: i32 = instr_vec[1].parse::<i32>().unwrap();
-    let src_stack: i32 = instr_vec[3].parse::<i32>().unwrap() - 1;
-    let dest_stack: i32 = instr_vec[5].parse::<i32>().unwrap() -1;
+fn update_stack(stacks: &mut CrateStacks, instructions: &Line) -> Result<(), ParseError> {
+    instructions.after("move ")?;
+    let num_crates: i32 = instructions.parse_word(1, "a number of crates")?;
+    let src_stack: i32 = get_stack_index(stacks, instructions, 3)?;
+    let dest_stack: i32 = get_stack_index(stacks, instructions, 5)?;
+    let available = stacks.stacks[src_stack as usize].crates.len();
+    if (num_crates < 0) || (num_crates as usize > available) {
+        let word = instructions.word(1, "a number of crates")?;
+        return Err(instructions.error(word, &format!("at most {} crates", available)));
+    }
 
     stacks.move_n_crates_from_a_to_b(num_crates, src_stack, dest_stack);
-    return Some(stacks);
+    return Ok(());
+}
+
+fn get_stack_index(stacks: &CrateStacks, line: &Line, word_index: usize) -> Result<i32, ParseError> {
+    let word = line.word(word_index, "a stack number")?;
+    let stack_num: i32 = line.parse(word, "a stack number")?;
+    if (stack_num < 1) || (stack_num > stacks.num_stacks) {
+        return Err(line.error(word, &format!("a stack number from 1 to {}", stacks.num_stacks)));
+    }
+    return Ok(stack_num - 1);
 }
 
@@ -0,0 +1,58 @@
+use crate::common::{Answer, Params, Part, SolveError};
+
+#[path = "05_part1.rs"]
+pub mod part1;
+#[path = "05_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/05.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from("CMZ")));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from("MCD")));
+    }
+
+    // Moving every crate off a stack leaves it out of the answer
+    #[test]
+    fn emptied_stacks() {
+        let input = "[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 2 to 1\n";
+        assert_eq!(solve(Part::One, input), Ok(Answer::from("C")));
+        assert_eq!(solve(Part::Two, input), Ok(Answer::from("C")));
+    }
+
+    #[test]
+    fn bad_drawings() {
+        let expected = |input: &str| match solve(Part::One, input) {
+            Err(SolveError::Parse(error)) => (error.line, error.column, error.expected),
+            other => panic!("{:?} should have been a parse error", other),
+        };
+        assert_eq!(expected(""), (0, 0, "a drawing of the crate stacks".to_string()));
+        assert_eq!(expected("hello world\n\nmove 1 from 1 to 2\n"), (1, 1, "stack number 1".to_string()));
+        assert_eq!(expected("[A] [B]\n 1   3\n"), (2, 6, "stack number 2".to_string()));
+        assert_eq!(expected("[A] (B)\n 1   2\n"), (1, 5, "a crate like '[A]' or a gap".to_string()));
+        assert_eq!(expected("[A] [B] [C]\n 1   2\n"), (1, 8, "the end of the row after 2 stacks".to_string()));
+        assert_eq!(expected("[A] [B]\n 1   2\n\nmove 1 from 3 to 1\n"), (4, 13, "a stack number from 1 to 2".to_string()));
+        assert_eq!(expected("[A] [B]\n 1   2\n\nmove 2 from 1 to 2\n"), (4, 6, "at most 1 crates".to_string()));
+    }
+}
@@ -1,34 +1,35 @@
-use std::env;
-use std::fs;
+#![allow(clippy::explicit_counter_loop, clippy::needless_borrow)]
 
-const MARKER_LENGTH: i32 = 4;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::check_chars;
 
+pub const MARKER_LENGTH: Param<i32> = Param::new("marker_length", "4", "how many different letters in a row make the marker");
+pub const PARAMS: &[&ParamInfo] = &[&MARKER_LENGTH.info];
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let marker_length: i32 = params.value(&MARKER_LENGTH)?;
+    check_chars(input.trim_end(), |c| c.is_ascii_alphabetic(), "a letter")?;
     let mut current_window: Vec<char> = Vec::new();
     let mut char_ind: i32 = 0;
-    for c in input.chars() {
+    for c in input.trim_end().chars() {
         current_window.push(c);
-        if current_window.len() as i32 == MARKER_LENGTH {
-            let is_unique = check_chars_for_uniqueness(&current_window);
+        if current_window.len() as i32 == marker_length {
+            let is_unique = check_chars_for_uniqueness(&current_window, marker_length);
             if is_unique {
-                println!("Output: {}", char_ind + 1);
-                break;
+                return Ok(Answer::from(char_ind + 1));
             }
             current_window.remove(0);
         }
         char_ind += 1;
     } 
+    return Err(SolveError::NoSolution(format!("No marker of length {} found", marker_length)));
 }
 
 
-fn check_chars_for_uniqueness(window: &Vec<char>) -> bool {
-    return get_num_letters(&window) == MARKER_LENGTH;
+fn check_chars_for_uniqueness(window: &Vec<char>, marker_length: i32) -> bool {
+    return get_num_letters(&window) == marker_length;
 }
 
 
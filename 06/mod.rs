@@ -0,0 +1,67 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "06_part1.rs"]
+pub mod part1;
+#[path = "06_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/06.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(7)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(19)));
+    }
+
+    // The puzzle gives a few more buffers with just their answers
+    #[test]
+    fn other_examples() {
+        let examples = [
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (buffer, marker_1, marker_2) in examples {
+            assert_eq!(solve(Part::One, buffer), Ok(Answer::from(marker_1)));
+            assert_eq!(solve(Part::Two, buffer), Ok(Answer::from(marker_2)));
+        }
+    }
+
+    #[test]
+    fn other_marker_lengths() {
+        let mut params = Params::new();
+        params.insert("marker_length", "3");
+        assert_eq!(solve_with_params(Part::One, "bvwbjplbgvbhsrlpgdmjqwftvncz", &params), Ok(Answer::from(3)));
+        params.insert("marker_length", "four");
+        assert!(matches!(solve_with_params(Part::Two, "bvwbjplbgvbhsrlpgdmjqwftvncz", &params), Err(SolveError::BadParam {..})));
+    }
+}
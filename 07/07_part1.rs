@@ -1,24 +1,28 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+#![allow(clippy::for_kv_map, clippy::needless_borrow)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{get_answer, use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::{lines, Line, ParseError};
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-const SIZE_BOUND: u32 = 100000;
+pub const SIZE_BOUND: Param<i64> = Param::new("size_bound", "100000", "directories smaller than this are counted");
+pub const PARAMS: &[&ParamInfo] = &[&SIZE_BOUND.info, &BIGINT.info];
 
 
-struct Directory {
+struct Directory<T> {
     _name: String ,
-    files: HashMap<String, u32>,
-    directories: HashMap<String, Link>,
-    parent: Link,
-    size: Option<u32>,
+    files: HashMap<String, T>,
+    directories: HashMap<String, Link<T>>,
+    parent: Link<T>,
+    size: Option<T>,
 }
 
-impl Directory {
-    fn new(name: String, parent: Link) -> Directory {
+impl<T: Integer> Directory<T> {
+    fn new(name: String, parent: Link<T>) -> Directory<T> {
         return Directory {
             _name: name.to_string(), 
             files: HashMap::new(), 
@@ -28,53 +32,92 @@ impl Directory {
         };
     }
 
-    fn get_size(&mut self) -> u32 {
-        return match self.size {
-            Some(bytes)=>bytes,
+    fn get_size(&mut self) -> T {
+        return match &self.size {
+            Some(bytes)=>bytes.clone(),
             None=>self._calculate_size(),
         };
     }
 
-    fn _calculate_size(&mut self) -> u32 {
-        let mut sum_of_own_files: u32 = 0;
+    fn _calculate_size(&mut self) -> T {
+        let mut sum_of_own_files: T = T::from(0);
         for (_name, size) in &self.files {
-            sum_of_own_files += size;
+            sum_of_own_files = sum_of_own_files + size.clone();
         }
 
-        let mut sum_of_directories: u32 = 0;
+        let mut sum_of_directories: T = T::from(0);
         for (_name, dir) in &self.directories {
-            let this_dir_size: u32 = dir.as_ref().unwrap().borrow_mut().get_size();
-            sum_of_directories += this_dir_size;
+            let this_dir_size: T = dir.as_ref().unwrap().borrow_mut().get_size();
+            sum_of_directories = sum_of_directories + this_dir_size;
         }
-        let full_size:u32 = sum_of_own_files + sum_of_directories;
-        self.size = Some(full_size);
+        let full_size: T = sum_of_own_files + sum_of_directories;
+        self.size = Some(full_size.clone());
         return full_size;
     }
 }
 
-type Link = Option<Rc<RefCell<Directory>>>;
+type Link<T> = Option<Rc<RefCell<Directory<T>>>>;
 
-struct FileSystem {
-    root: Link,
-    cwd: Link,
+struct FileSystem<T> {
+    root: Link<T>,
+    cwd: Link<T>,
 }
 
-impl FileSystem {
+impl<T: Integer> FileSystem<T> {
     fn new() -> Self {
         let root = Rc::new(RefCell::new(Directory::new("/".to_string(), None)));
         Self {root: Some(root), cwd: None}
     }
 
-    fn update_file_system(&mut self, val: String) {
+    fn update_file_system(&mut self, line: &Line) -> Result<(), ParseError> {
+        let val = line.text.trim();
         if val.starts_with("$ cd") {
-            let new_dir: String = val.split(" ").collect::<Vec<&str>>()[2].to_string();
-            self.cd(new_dir);
+            let new_dir = line.word(2, "a directory name")?;
+            if new_dir != "/" {
+                self.check_cd(line, new_dir)?;
+            }
+            self.cd(new_dir.to_string());
+        }
+        else if val.starts_with("$ ls") {
+            self.check_cwd(line)?;
+        }
+        else if val.starts_with("$") {
+            return Err(line.error(val, "'$ cd' or '$ ls'"));
         }
-        else if val.starts_with("$ ls") {}
         else {
-            let ls_item: Vec<&str> = val.split(" ").collect();
+            self.check_cwd(line)?;
+            let ls_item: Vec<&str> = val.split_whitespace().collect();
+            if ls_item.len() != 2 {
+                return Err(line.error(val, "'dir <name>' or '<size> <name>'"));
+            }
+            if (ls_item[0] != "dir") && (ls_item[0].starts_with('-') || line.parse::<T>(ls_item[0], "'dir' or a file size").is_err()) {
+                return Err(line.error(ls_item[0], "'dir' or a file size"));
+            }
             self.process_ls_item(ls_item);
         }
+        return Ok(());
+    }
+
+    // Everything before the first '$ cd /' has nowhere to go
+    fn check_cwd(&self, line: &Line) -> Result<(), ParseError> {
+        if self.cwd.is_none() {
+            return Err(line.error(line.text.trim(), "'$ cd /' first"));
+        }
+        return Ok(());
+    }
+
+    fn check_cd(&self, line: &Line, dir: &str) -> Result<(), ParseError> {
+        self.check_cwd(line)?;
+        let cwd = self.cwd.as_ref().unwrap().borrow();
+        if dir == ".." {
+            if cwd.parent.is_none() {
+                return Err(line.error(dir, "a directory below the root"));
+            }
+        }
+        else if !cwd.directories.contains_key(dir) {
+            return Err(line.error(dir, "a directory listed by '$ ls'"));
+        }
+        return Ok(());
     }
 
     fn cd(&mut self, dir: String) {
@@ -94,7 +137,7 @@ impl FileSystem {
             self.add_dir_to_cwd(ls_item[1].to_string());
         }
         else {
-            self.add_file_to_cwd(ls_item[1].to_string(), ls_item[0].parse::<u32>().unwrap());
+            self.add_file_to_cwd(ls_item[1].to_string(), ls_item[0].parse::<T>().ok().unwrap());
         }
     }
 
@@ -105,7 +148,7 @@ impl FileSystem {
         };
     }
 
-    fn get_cwd_parent(&self) -> Link {
+    fn get_cwd_parent(&self) -> Link<T> {
         return Some(Rc::clone(self.cwd.as_ref().unwrap().borrow().parent.as_ref().unwrap()));
     }
 
@@ -116,7 +159,7 @@ impl FileSystem {
         };
     }
 
-    fn get_cwd_child(&self, name: String) -> Link {
+    fn get_cwd_child(&self, name: String) -> Link<T> {
         return Some(Rc::clone(self.cwd.as_ref().unwrap().borrow().directories.get(&name).unwrap().as_ref().unwrap()))
     }
 
@@ -129,65 +172,62 @@ impl FileSystem {
 
     fn add_dir_to_cwd(&mut self, dir_name: String) {
         let cwd_to_pass = Some(Rc::clone(&self.cwd.as_ref().unwrap()));
-        let new_dir: Link = Some(Rc::new(RefCell::new(Directory::new(dir_name.to_string(), cwd_to_pass))));
+        let new_dir: Link<T> = Some(Rc::new(RefCell::new(Directory::new(dir_name.to_string(), cwd_to_pass))));
         self.cwd.as_ref().unwrap().borrow_mut().directories.entry(dir_name).or_insert(new_dir);
     }
 
-    fn add_file_to_cwd(&mut self, file_name: String, file_size: u32) {
+    fn add_file_to_cwd(&mut self, file_name: String, file_size: T) {
         self.cwd.as_ref().unwrap().borrow_mut().files.entry(file_name).or_insert(file_size);
 
     }
 
-    fn get_cwd_size(&mut self) -> u32 {
+    fn get_cwd_size(&mut self) -> T {
         return self.cwd.as_ref().unwrap().borrow_mut().get_size();
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut file_system = FileSystem::new();
-
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                file_system.update_file_system(val.trim().to_string());
-            }
-        }
-    }
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input, params),
+        false => solve_with::<Checked>(input, params),
+    };
+}
 
+fn solve_with<T: Integer>(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let size_bound: T = T::from(params.value(&SIZE_BOUND)?);
+    let mut file_system: FileSystem<T> = build_file_system(input)?;
     file_system.cd("/".to_string());
-    let total: u32 = traverse_fs_and_get_sizes(&mut file_system);
-    println!("Total size of smaller directories: {}", total);
+    let total: T = traverse_fs_and_get_sizes(&mut file_system, &size_bound);
+    return get_answer(total, "The total size of the small directories");
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+
+fn build_file_system<T: Integer>(input: &str) -> Result<FileSystem<T>, ParseError> {
+    let mut file_system = FileSystem::new();
+    for line in lines(input) {
+        file_system.update_file_system(&line)?;
+    }
+    return Ok(file_system);
 }
 
 
-fn traverse_fs_and_get_sizes(fs: &mut FileSystem) -> u32 {
+
+fn traverse_fs_and_get_sizes<T: Integer>(fs: &mut FileSystem<T>, size_bound: &T) -> T {
     let mut dir_vec: Vec<String> = Vec::new();
     for (dir_name, _) in &fs.cwd.as_ref().unwrap().borrow_mut().directories {
         dir_vec.push((&dir_name).to_string());
     }
 
-   let mut total = 0; 
+   let mut total: T = T::from(0); 
     for dir_name in dir_vec {
         fs.cd(dir_name.to_string());
-        total += traverse_fs_and_get_sizes(fs);
+        total = total + traverse_fs_and_get_sizes(fs, size_bound);
         fs.cd("..".to_string());
     }
 
     let current_size = fs.get_cwd_size();
-    if current_size < SIZE_BOUND {
-        total += current_size;
+    if current_size < *size_bound {
+        total = total + current_size;
     }
     return total;
 }
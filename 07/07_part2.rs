@@ -1,25 +1,31 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+#![allow(clippy::for_kv_map, clippy::needless_borrow)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{get_answer, use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::{lines, Line, ParseError};
+use crate::common::log;
+use crate::log_info;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-const TOTAL_SIZE: u32 = 70000000;
-const SIZE_NEEDED: u32 = 30000000;
+pub const TOTAL_SIZE: Param<i64> = Param::new("total_size", "70000000", "the size of the disk");
+pub const SIZE_NEEDED: Param<i64> = Param::new("size_needed", "30000000", "how much free space the update needs");
+pub const PARAMS: &[&ParamInfo] = &[&TOTAL_SIZE.info, &SIZE_NEEDED.info, &BIGINT.info];
 
 
-struct Directory {
+struct Directory<T> {
     _name: String ,
-    files: HashMap<String, u32>,
-    directories: HashMap<String, Link>,
-    parent: Link,
-    size: Option<u32>,
+    files: HashMap<String, T>,
+    directories: HashMap<String, Link<T>>,
+    parent: Link<T>,
+    size: Option<T>,
 }
 
-impl Directory {
-    fn new(name: String, parent: Link) -> Directory {
+impl<T: Integer> Directory<T> {
+    fn new(name: String, parent: Link<T>) -> Directory<T> {
         return Directory {
             _name: name.to_string(), 
             files: HashMap::new(), 
@@ -29,53 +35,92 @@ impl Directory {
         };
     }
 
-    fn get_size(&mut self) -> u32 {
-        return match self.size {
-            Some(bytes)=>bytes,
+    fn get_size(&mut self) -> T {
+        return match &self.size {
+            Some(bytes)=>bytes.clone(),
             None=>self._calculate_size(),
         };
     }
 
-    fn _calculate_size(&mut self) -> u32 {
-        let mut sum_of_own_files: u32 = 0;
+    fn _calculate_size(&mut self) -> T {
+        let mut sum_of_own_files: T = T::from(0);
         for (_name, size) in &self.files {
-            sum_of_own_files += size;
+            sum_of_own_files = sum_of_own_files + size.clone();
         }
 
-        let mut sum_of_directories: u32 = 0;
+        let mut sum_of_directories: T = T::from(0);
         for (_name, dir) in &self.directories {
-            let this_dir_size: u32 = dir.as_ref().unwrap().borrow_mut().get_size();
-            sum_of_directories += this_dir_size;
+            let this_dir_size: T = dir.as_ref().unwrap().borrow_mut().get_size();
+            sum_of_directories = sum_of_directories + this_dir_size;
         }
-        let full_size:u32 = sum_of_own_files + sum_of_directories;
-        self.size = Some(full_size);
+        let full_size: T = sum_of_own_files + sum_of_directories;
+        self.size = Some(full_size.clone());
         return full_size;
     }
 }
 
-type Link = Option<Rc<RefCell<Directory>>>;
+type Link<T> = Option<Rc<RefCell<Directory<T>>>>;
 
-struct FileSystem {
-    root: Link,
-    cwd: Link,
+struct FileSystem<T> {
+    root: Link<T>,
+    cwd: Link<T>,
 }
 
-impl FileSystem {
+impl<T: Integer> FileSystem<T> {
     fn new() -> Self {
         let root = Rc::new(RefCell::new(Directory::new("/".to_string(), None)));
         Self {root: Some(root), cwd: None}
     }
 
-    fn update_file_system(&mut self, val: String) {
+    fn update_file_system(&mut self, line: &Line) -> Result<(), ParseError> {
+        let val = line.text.trim();
         if val.starts_with("$ cd") {
-            let new_dir: String = val.split(" ").collect::<Vec<&str>>()[2].to_string();
-            self.cd(new_dir);
+            let new_dir = line.word(2, "a directory name")?;
+            if new_dir != "/" {
+                self.check_cd(line, new_dir)?;
+            }
+            self.cd(new_dir.to_string());
+        }
+        else if val.starts_with("$ ls") {
+            self.check_cwd(line)?;
+        }
+        else if val.starts_with("$") {
+            return Err(line.error(val, "'$ cd' or '$ ls'"));
         }
-        else if val.starts_with("$ ls") {}
         else {
-            let ls_item: Vec<&str> = val.split(" ").collect();
+            self.check_cwd(line)?;
+            let ls_item: Vec<&str> = val.split_whitespace().collect();
+            if ls_item.len() != 2 {
+                return Err(line.error(val, "'dir <name>' or '<size> <name>'"));
+            }
+            if (ls_item[0] != "dir") && (ls_item[0].starts_with('-') || line.parse::<T>(ls_item[0], "'dir' or a file size").is_err()) {
+                return Err(line.error(ls_item[0], "'dir' or a file size"));
+            }
             self.process_ls_item(ls_item);
         }
+        return Ok(());
+    }
+
+    // Everything before the first '$ cd /' has nowhere to go
+    fn check_cwd(&self, line: &Line) -> Result<(), ParseError> {
+        if self.cwd.is_none() {
+            return Err(line.error(line.text.trim(), "'$ cd /' first"));
+        }
+        return Ok(());
+    }
+
+    fn check_cd(&self, line: &Line, dir: &str) -> Result<(), ParseError> {
+        self.check_cwd(line)?;
+        let cwd = self.cwd.as_ref().unwrap().borrow();
+        if dir == ".." {
+            if cwd.parent.is_none() {
+                return Err(line.error(dir, "a directory below the root"));
+            }
+        }
+        else if !cwd.directories.contains_key(dir) {
+            return Err(line.error(dir, "a directory listed by '$ ls'"));
+        }
+        return Ok(());
     }
 
     fn cd(&mut self, dir: String) {
@@ -95,7 +140,7 @@ impl FileSystem {
             self.add_dir_to_cwd(ls_item[1].to_string());
         }
         else {
-            self.add_file_to_cwd(ls_item[1].to_string(), ls_item[0].parse::<u32>().unwrap());
+            self.add_file_to_cwd(ls_item[1].to_string(), ls_item[0].parse::<T>().ok().unwrap());
         }
     }
 
@@ -106,7 +151,7 @@ impl FileSystem {
         };
     }
 
-    fn get_cwd_parent(&self) -> Link {
+    fn get_cwd_parent(&self) -> Link<T> {
         return Some(Rc::clone(self.cwd.as_ref().unwrap().borrow().parent.as_ref().unwrap()));
     }
 
@@ -117,7 +162,7 @@ impl FileSystem {
         };
     }
 
-    fn get_cwd_child(&self, name: String) -> Link {
+    fn get_cwd_child(&self, name: String) -> Link<T> {
         return Some(Rc::clone(self.cwd.as_ref().unwrap().borrow().directories.get(&name).unwrap().as_ref().unwrap()))
     }
 
@@ -130,16 +175,16 @@ impl FileSystem {
 
     fn add_dir_to_cwd(&mut self, dir_name: String) {
         let cwd_to_pass = Some(Rc::clone(&self.cwd.as_ref().unwrap()));
-        let new_dir: Link = Some(Rc::new(RefCell::new(Directory::new(dir_name.to_string(), cwd_to_pass))));
+        let new_dir: Link<T> = Some(Rc::new(RefCell::new(Directory::new(dir_name.to_string(), cwd_to_pass))));
         self.cwd.as_ref().unwrap().borrow_mut().directories.entry(dir_name).or_insert(new_dir);
     }
 
-    fn add_file_to_cwd(&mut self, file_name: String, file_size: u32) {
+    fn add_file_to_cwd(&mut self, file_name: String, file_size: T) {
         self.cwd.as_ref().unwrap().borrow_mut().files.entry(file_name).or_insert(file_size);
 
     }
 
-    fn get_cwd_size(&mut self) -> u32 {
+    fn get_cwd_size(&mut self) -> T {
         return self.cwd.as_ref().unwrap().borrow_mut().get_size();
     }
 
@@ -148,52 +193,57 @@ impl FileSystem {
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut file_system = FileSystem::new();
-
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                file_system.update_file_system(val.trim().to_string());
-            }
-        }
-    }
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input, params),
+        false => solve_with::<Checked>(input, params),
+    };
+}
 
+fn solve_with<T: Integer>(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let total_size: T = T::from(params.value(&TOTAL_SIZE)?);
+    let size_needed: T = T::from(params.value(&SIZE_NEEDED)?);
+    let mut file_system: FileSystem<T> = build_file_system(input)?;
     file_system.cd("/".to_string());
-    let used: u32 = file_system.get_cwd_size();
-    let free: u32 = TOTAL_SIZE - used;
-    if free > SIZE_NEEDED {
-        println!("No need to delete anything!");
+    let used: T = file_system.get_cwd_size();
+    // Every directory is smaller than the root, so if its size fits they all do
+    if used.overflowed() {
+        return Err(SolveError::Overflow("The size of the root directory".to_string()));
+    }
+    let free: T = total_size - used;
+    if free > size_needed {
+        log_info!("No need to delete anything!");
+        return Ok(Answer::from(0));
     }
     else {
-        let size_to_be_deleted: u32 = SIZE_NEEDED - free;
-        println!("Amount to be deleted: {}", size_to_be_deleted);
-        let dir_to_delete_size = find_smallest_large_enough_file(&mut file_system, size_to_be_deleted);
-        println!("Size of required directory: {}", dir_to_delete_size.expect("We should have a file size!"));
+        let size_to_be_deleted: T = size_needed - free;
+        log::detail("size_to_be_deleted", size_to_be_deleted.to_string());
+        return match find_smallest_large_enough_file(&mut file_system, &size_to_be_deleted) {
+            Some(size) => get_answer(size, "The size of the directory to delete"),
+            None => Err(SolveError::NoSolution(format!("No directory frees up {}", size_to_be_deleted))),
+        };
     }
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+
+fn build_file_system<T: Integer>(input: &str) -> Result<FileSystem<T>, ParseError> {
+    let mut file_system = FileSystem::new();
+    for line in lines(input) {
+        file_system.update_file_system(&line)?;
+    }
+    return Ok(file_system);
 }
 
 
-fn find_smallest_large_enough_file(fs: &mut FileSystem, lower_bound: u32) -> Option<u32> {
+
+fn find_smallest_large_enough_file<T: Integer>(fs: &mut FileSystem<T>, lower_bound: &T) -> Option<T> {
     let mut dir_vec: Vec<String> = Vec::new();
     let current_size = fs.get_cwd_size();
-    if current_size < lower_bound {
+    if current_size < *lower_bound {
         return None;
     }
 
-    let mut current_best: Option<u32> = Some(current_size); 
+    let mut current_best: Option<T> = Some(current_size); 
     for (dir_name, _) in &fs.cwd.as_ref().unwrap().borrow_mut().directories {
         dir_vec.push((&dir_name).to_string());
     }
@@ -209,8 +259,8 @@ fn find_smallest_large_enough_file(fs: &mut FileSystem, lower_bound: u32) -> Opt
     return current_best;
 }
 
-fn get_new_best_from_optionals(current_best: Option<u32>, new_candidate: Option<u32>) -> Option<u32> {
-    return match (current_best, new_candidate) {
+fn get_new_best_from_optionals<T: Integer>(current_best: Option<T>, new_candidate: Option<T>) -> Option<T> {
+    return match (&current_best, &new_candidate) {
         (Some(current_bytes), Some(candidate_bytes)) => if candidate_bytes < current_bytes {new_candidate} else {current_best},
         (None, Some(_)) => new_candidate,
         (_, None) => current_best,
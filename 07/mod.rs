@@ -0,0 +1,52 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "07_part1.rs"]
+pub mod part1;
+#[path = "07_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/07.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(95437)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(24933642)));
+    }
+
+    #[test]
+    fn huge_files() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n9000000000000000000 b.txt\n9000000000000000000 c.txt\n";
+        assert_eq!(solve(Part::Two, input), Err(SolveError::Overflow("The size of the root directory".to_string())));
+        let mut params = Params::new();
+        params.insert("bigint", "true");
+        assert_eq!(solve_with_params(Part::Two, input, &params), Ok(Answer::from("18000000000000000000")));
+    }
+}
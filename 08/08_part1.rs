@@ -1,133 +1,101 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+#![allow(clippy::partialeq_to_none)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, lines, Line, ParseError};
+use crate::common::geometry::Point2;
+use crate::common::grid::{DenseGrid, Grid};
 
 
 type Visibility = Option<bool>;
+type Point = Point2<i32>;
+type Forest = DenseGrid<i32, Tree>;
 
 #[derive(Copy, Clone)]
 struct Tree {
     height: u32,
-    // (from left, from right, from top, from bottom)
-    visibility_array: (Visibility, Visibility, Visibility, Visibility)
+    // [from left, from right, from top, from bottom]
+    visibility_array: [Visibility; 4],
 }
 
 impl Tree {
     fn new(height: u32) -> Tree {
         return Tree {
             height: height, 
-            visibility_array: (None, None, None, None),
+            visibility_array: [None; 4],
         } 
     }
 
     fn is_visible(&self) -> Visibility {
         return match &self.visibility_array {
-            (Some(left), Some(right), Some(top), Some(down)) => Some(left | right | top | down),
+            [Some(left), Some(right), Some(top), Some(down)] => Some(left | right | top | down),
             _ => None,
         };
     }
 }
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut forest = Vec::new();
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                let mut row = Vec::new();
-                for num_char in val.chars() {
-                    row.push(Tree::new(num_char as u32 - '0' as u32));
-                }
-                forest.push(row);
-            }
-        }
-    }
-    let width = forest[0].len();
-    let length = forest.len();
-
-    // from left and right
-    for i in 0..length {
-        // from left
-        let mut tallest = forest[i][0].height;
-        forest[i][0].visibility_array.0 = Some(true);
-        for j in 1..width {
-            let mut this_tree = &mut forest[i][j];
-            if this_tree.height > tallest {
-                this_tree.visibility_array.0 = Some(true);
-                tallest = this_tree.height;
-            }
-            else {
-                this_tree.visibility_array.0 = Some(false);
-            }
-        }
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut forest = read_forest(input)?;
+    mark_visibility(&mut forest);
+    return Ok(Answer::from(count_visible(&forest)));
+}
 
-        //from right
-        tallest = forest[i][width - 1].height;
-        forest[i][width - 1].visibility_array.1 = Some(true);
-        for j in 1..width {
-            let mut this_tree = &mut forest[i][width - 1 - j];
-            if this_tree.height > tallest {
-                this_tree.visibility_array.1 = Some(true);
-                tallest = this_tree.height;
-            }
-            else {
-                this_tree.visibility_array.1 = Some(false);
-            }
+
+fn read_forest(input: &str) -> Result<Forest, ParseError> {
+    check_chars(input, |c| c.is_ascii_digit(), "a tree height (0-9)")?;
+    check_rectangular(input)?;
+    return Ok(Forest::from_chars(input, Point::zero(), |_, num_char| Some(Tree::new(num_char.to_digit(10).unwrap()))));
+}
+
+
+// Every row needs as many trees as the first, and there has to be at least one
+fn check_rectangular(input: &str) -> Result<(), ParseError> {
+    let rows: Vec<Line> = lines(input).collect();
+    let width: usize = rows.first().map(|row| row.text.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(ParseError::whole_input("an empty input", "a grid of tree heights"));
+    }
+    for row in rows {
+        if row.text.len() != width {
+            let found = if row.text.len() < width {"end of line"} else {&row.text[width..]};
+            return Err(row.error_at(row.text.len().min(width) + 1, found, &format!("a row of {} trees", width)));
         }
     }
+    return Ok(());
+}
 
-    // from top and bottom
-    for j in 0..width {
-        let mut tallest = forest[0][j].height;
-        forest[0][j].visibility_array.2 = Some(true);
-        for i in 1..length {
-            let mut this_tree = &mut forest[i][j];
-            if this_tree.height > tallest {
-                this_tree.visibility_array.2 = Some(true);
-                tallest = this_tree.height;
-            }
-            else {
-                this_tree.visibility_array.2 = Some(false);
-            }
-        }
 
-        //from bottom
-        tallest = forest[length - 1][j].height;
-        forest[length - 1][j].visibility_array.3 = Some(true);
-        for i in 1..length {
-            let mut this_tree = &mut forest[length - 1 - i][j];
-            if this_tree.height > tallest {
-                this_tree.visibility_array.3 = Some(true);
-                tallest = this_tree.height;
-            }
-            else {
-                this_tree.visibility_array.3 = Some(false);
-            }
-        }
+fn mark_visibility(forest: &mut Forest) {
+    let rows: Vec<Vec<Point>> = forest.bounds().unwrap().rows();
+    let columns: Vec<Vec<Point>> = (0..rows[0].len()).map(|j| rows.iter().map(|row| row[j]).collect()).collect();
+
+    for row in &rows {
+        mark_visibility_along(forest, row.iter(), 0);
+        mark_visibility_along(forest, row.iter().rev(), 1);
+    }
+    for column in &columns {
+        mark_visibility_along(forest, column.iter(), 2);
+        mark_visibility_along(forest, column.iter().rev(), 3);
     }
+}
+
 
-    // counting
-    let mut visible_count = 0;
-    for i in 0..length {
-        for j in 0..width {
-            if forest[i][j].is_visible().expect("is_visible should be fully calculable!") {
-                visible_count += 1;
-            }
+// Walks a line of trees in from the edge, marking which ones can be seen from that side
+fn mark_visibility_along<'a>(forest: &mut Forest, line: impl Iterator<Item = &'a Point>, side: usize) {
+    let mut tallest: Option<u32> = None;
+    for point in line {
+        let this_tree = forest.get_mut(point).unwrap();
+        if (tallest == None) || (this_tree.height > tallest.unwrap()) {
+            this_tree.visibility_array[side] = Some(true);
+            tallest = Some(this_tree.height);
+        }
+        else {
+            this_tree.visibility_array[side] = Some(false);
         }
     }
-    println!("Number of visible trees: {}", visible_count);
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
+fn count_visible(forest: &Forest) -> usize {
+    return forest.iter().filter(|(_, tree)| tree.is_visible().expect("is_visible should be fully calculable!")).count();
+}
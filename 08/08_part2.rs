@@ -1,83 +1,67 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+#![allow(clippy::needless_borrow)]
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut forest = Vec::new();
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                let mut row = Vec::new();
-                for num_char in val.chars() {
-                    row.push(num_char as u32 - '0' as u32);
-                }
-                forest.push(row);
-            }
-        }
-    }
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, lines, Line, ParseError};
+use crate::common::geometry::{Direction, Point2};
+use crate::common::grid::{DenseGrid, Grid};
 
-    let width = forest[0].len();
-    let length = forest.len();
-    let mut best_score: u32 = 0;
-    for i in 0..length {
-        for j in 0..width {
-            let this_score: u32 = calculate_tree_score(&forest, i, j);
-            best_score = if this_score > best_score {this_score} else {best_score};
-        }
-    }
-    println!("Highest view score: {}", best_score);
+type Point = Point2<i32>;
+type Forest = DenseGrid<i32, u32>;
+
+
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let forest = read_forest(input)?;
+    return Ok(Answer::from(find_best_score(&forest)));
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+
+fn read_forest(input: &str) -> Result<Forest, ParseError> {
+    check_chars(input, |c| c.is_ascii_digit(), "a tree height (0-9)")?;
+    check_rectangular(input)?;
+    return Ok(Forest::from_chars(input, Point::zero(), |_, num_char| Some(num_char.to_digit(10).unwrap())));
 }
 
 
-fn calculate_tree_score(forest: &Vec<Vec<u32>>, row: usize, col: usize) -> u32 {
-    let width = forest[0].len();
-    let length = forest.len();
-    let current_height: u32 = forest[row][col];
-    
-    let mut left_count_to_block: u32 = 0;
-    if col != 0 {
-        for i in 1..(col + 1) {
-            left_count_to_block += 1;
-            if forest[row][col - i] >= current_height {break;}
-        }
+// Every row needs as many trees as the first, and there has to be at least one
+fn check_rectangular(input: &str) -> Result<(), ParseError> {
+    let rows: Vec<Line> = lines(input).collect();
+    let width: usize = rows.first().map(|row| row.text.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(ParseError::whole_input("an empty input", "a grid of tree heights"));
     }
-    
-    let mut right_count_to_block: u32 = 0;
-    if col != width - 1 {
-        for i in 1..(width - col) {
-            right_count_to_block += 1;
-            if forest[row][col + i] >= current_height {break;}
+    for row in rows {
+        if row.text.len() != width {
+            let found = if row.text.len() < width {"end of line"} else {&row.text[width..]};
+            return Err(row.error_at(row.text.len().min(width) + 1, found, &format!("a row of {} trees", width)));
         }
     }
-    
-    let mut up_count_to_block: u32 = 0;
-    if row != 0 {
-        for i in 1..(row + 1) {
-            up_count_to_block += 1;
-            if forest[row - i][col] >= current_height {break;}
-        }
+    return Ok(());
+}
+
+
+fn find_best_score(forest: &Forest) -> u32 {
+    let mut best_score: u32 = 0;
+    for (point, _) in forest.iter() {
+        let this_score: u32 = calculate_tree_score(&forest, &point);
+        best_score = if this_score > best_score {this_score} else {best_score};
     }
-    
-    let mut down_count_to_block: u32 = 0;
-    if row != length - 1 {
-        for i in 1..(length - row) {
-            down_count_to_block += 1;
-            if forest[row + i][col] >= current_height {break;}
+    return best_score;
+}
+
+
+
+fn calculate_tree_score(forest: &Forest, point: &Point) -> u32 {
+    let current_height: u32 = *forest.get(point).unwrap();
+    let mut score: u32 = 1;
+    for direction in Direction::all() {
+        let mut count_to_block: u32 = 0;
+        let mut other_point: Point = *point + direction.as_vector();
+        while let Some(other_height) = forest.get(&other_point) {
+            count_to_block += 1;
+            if *other_height >= current_height {break;}
+            other_point += direction.as_vector();
         }
+        score *= count_to_block;
     }
-    return left_count_to_block * right_count_to_block * up_count_to_block * down_count_to_block;
+    return score;
 }
-
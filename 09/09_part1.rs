@@ -1,11 +1,18 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::geometry::Point2;
+use crate::common::grid::Bounds;
+use crate::common::params::ParamInfo;
+use crate::common::parse::{lines, ParseError};
+use crate::common::recorder;
+use crate::common::render::{Cell, Colour, Render, ScreenPoint};
+use crate::common::simulation::Simulation;
 use std::collections::HashSet;
 
 type Position = (i32, i32);
 
+// The rope is always just a head and a tail here
+pub const PARAMS: &[&ParamInfo] = &[];
+
 struct Rope {
     head: Position,
     tail: Position,
@@ -34,6 +41,7 @@ impl Rope {
         self.head.0 += displacement.0;
         self.head.1 += displacement.1;
         self.update_tail();
+        recorder::frame(self);
     }
 
     fn update_tail(&mut self) {
@@ -51,30 +59,110 @@ impl Rope {
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut rope =  Rope::new();
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                let instr_vec: Vec<&str> = val.split(" ").collect();
-                let direction: char = instr_vec[0].chars().nth(0).unwrap();
-                let distance: i32 = instr_vec[1].parse().unwrap();
-                rope.move_head_some_distance(direction, distance);    
-            }
+// Up is up on the screen, so y flips
+fn get_screen_point(position: &Position) -> ScreenPoint {
+    return Point2::new(position.0 as i64, -position.1 as i64);
+}
+
+impl Render for Rope {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let mut bounds = Bounds::from_point(&get_screen_point(&self.head));
+        for position in self.history.iter().chain([&self.tail]) {
+            bounds.include(&get_screen_point(position));
         }
+        return Some(bounds);
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let position: Position = (point.x as i32, -point.y as i32);
+        return match position {
+            _ if position == self.head => Cell::new('H', Colour::Red),
+            _ if position == self.tail => Cell::new('T', Colour::Yellow),
+            (0, 0) => Cell::new('s', Colour::Green),
+            _ if self.history.contains(&position) => Cell::new('#', Colour::Default),
+            _ => Cell::new('.', Colour::Grey),
+        };
     }
-    println!("Rope has been in {} positions.", rope.history.len());
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+// The head moves one square per step
+struct RopeSimulation {
+    rope: Rope,
+    moves: Vec<char>,
+    num_moves_made: usize,
 }
 
+impl Render for RopeSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.rope.render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return self.rope.cell_at(point);
+    }
+}
+
+impl Simulation for RopeSimulation {
+    fn step(&mut self) -> bool {
+        return match self.moves.get(self.num_moves_made) {
+            Some(direction) => {
+                self.rope.move_head(*direction);
+                self.num_moves_made += 1;
+                true
+            },
+            None => false,
+        };
+    }
+
+    fn reset(&mut self) {
+        self.rope = Rope::new();
+        self.num_moves_made = 0;
+    }
+
+    fn status(&self) -> String {
+        return format!("Move {} of {}: head at {:?}, tail at {:?}, {} positions visited",
+            self.num_moves_made, self.moves.len(), self.rope.head, self.rope.tail, self.rope.history.len());
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let position: Position = (point.x as i32, -point.y as i32);
+        let mut things: Vec<&str> = Vec::new();
+        if position == self.rope.head {things.push("the head");}
+        if position == self.rope.tail {things.push("the tail");}
+        if position == (0, 0) {things.push("the start");}
+        if self.rope.history.contains(&position) {things.push("visited by the tail");}
+        if things.is_empty() {things.push("nothing");}
+        return format!("{:?}: {}", position, things.join(", "));
+    }
+}
+
+
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut rope =  Rope::new();
+    for (direction, distance) in read_moves(input)? {
+        rope.move_head_some_distance(direction, distance);
+    }
+    recorder::last_frame(&rope);
+    return Ok(Answer::from(rope.history.len()));
+}
+
+pub fn simulation(input: &str, _params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let moves: Vec<char> = read_moves(input)?.into_iter()
+        .flat_map(|(direction, distance)| std::iter::repeat_n(direction, distance.max(0) as usize))
+        .collect();
+    return Ok(Box::new(RopeSimulation {rope: Rope::new(), moves: moves, num_moves_made: 0}));
+}
+
+
+fn read_moves(input: &str) -> Result<Vec<(char, i32)>, ParseError> {
+    let mut moves: Vec<(char, i32)> = Vec::new();
+    for line in lines(input) {
+        let direction: char = line.char_at(1, "a direction (R, L, U or D)")?;
+        if !"RLUD".contains(direction) {
+            return Err(line.error_at(1, &direction.to_string(), "a direction (R, L, U or D)"));
+        }
+        let distance: i32 = line.parse_word(1, "a number of steps")?;
+        moves.push((direction, distance));
+    }
+    return Ok(moves);
+}
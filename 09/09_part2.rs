@@ -1,11 +1,18 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::geometry::Point2;
+use crate::common::grid::Bounds;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::{lines, ParseError};
+use crate::common::recorder;
+use crate::common::render::{Cell, Colour, Render, ScreenPoint};
+use crate::common::simulation::Simulation;
 use std::collections::HashSet;
 
 type Position = (i32, i32);
 
+pub const KNOTS: Param<usize> = Param::new("knots", "10", "how many knots the rope has, counting the head");
+pub const PARAMS: &[&ParamInfo] = &[&KNOTS.info];
+
 struct Rope {
     length: usize,
     knots: Vec<Position>,
@@ -34,6 +41,7 @@ impl Rope {
         self.knots[0].0 += displacement.0;
         self.knots[0].1 += displacement.1;
         self.update_tail();
+        recorder::frame(self);
     }
 
     fn update_tail(&mut self) {
@@ -66,30 +74,124 @@ impl Rope {
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut rope =  Rope::new(10);
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                let instr_vec: Vec<&str> = val.split(" ").collect();
-                let direction: char = instr_vec[0].chars().nth(0).unwrap();
-                let distance: i32 = instr_vec[1].parse().unwrap();
-                rope.move_head_some_distance(direction, distance);    
+// Up is up on the screen, so y flips
+fn get_screen_point(position: &Position) -> ScreenPoint {
+    return Point2::new(position.0 as i64, -position.1 as i64);
+}
+
+impl Render for Rope {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let mut bounds = Bounds::from_point(&get_screen_point(&(0, 0)));
+        for position in self.history.iter().chain(self.knots.iter()) {
+            bounds.include(&get_screen_point(position));
+        }
+        return Some(bounds);
+    }
+
+    // Knots are drawn like the puzzle text: H for the head, then 1-9, and the front-most knot wins
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let position: Position = (point.x as i32, -point.y as i32);
+        return match self.knots.iter().position(|knot| *knot == position) {
+            Some(0) => Cell::new('H', Colour::Red),
+            Some(i) => Cell::new(char::from_digit(i as u32, 36).unwrap_or('+'), Colour::Yellow),
+            None if position == (0, 0) => Cell::new('s', Colour::Green),
+            None if self.history.contains(&position) => Cell::new('#', Colour::Default),
+            None => Cell::new('.', Colour::Grey),
+        };
+    }
+}
+
+// The head moves one square per step
+struct RopeSimulation {
+    rope: Rope,
+    moves: Vec<char>,
+    num_moves_made: usize,
+}
+
+impl Render for RopeSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.rope.render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return self.rope.cell_at(point);
+    }
+}
+
+impl Simulation for RopeSimulation {
+    fn step(&mut self) -> bool {
+        return match self.moves.get(self.num_moves_made) {
+            Some(direction) => {
+                self.rope.move_head(*direction);
+                self.num_moves_made += 1;
+                true
+            },
+            None => false,
+        };
+    }
+
+    fn reset(&mut self) {
+        self.rope = Rope::new(self.rope.length);
+        self.num_moves_made = 0;
+    }
+
+    fn status(&self) -> String {
+        return format!("Move {} of {}: head at {:?}, tail at {:?}, {} positions visited",
+            self.num_moves_made, self.moves.len(), self.rope.knots[0], self.rope.knots[self.rope.length - 1], self.rope.history.len());
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let position: Position = (point.x as i32, -point.y as i32);
+        let mut things: Vec<String> = Vec::new();
+        for (i, knot) in self.rope.knots.iter().enumerate() {
+            if *knot == position {
+                things.push(if i == 0 {"the head".to_string()} else {format!("knot {}", i)});
             }
         }
+        if position == (0, 0) {things.push("the start".to_string());}
+        if self.rope.history.contains(&position) {things.push("visited by the tail".to_string());}
+        if things.is_empty() {things.push("nothing".to_string());}
+        return format!("{:?}: {}", position, things.join(", "));
     }
-    println!("Rope has been in {} positions.", rope.history.len());
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let mut rope =  Rope::new(get_knots(params)?);
+    for (direction, distance) in read_moves(input)? {
+        rope.move_head_some_distance(direction, distance);
+    }
+    recorder::last_frame(&rope);
+    return Ok(Answer::from(rope.history.len()));
 }
 
+pub fn simulation(input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let knots: usize = get_knots(params)?;
+    let moves: Vec<char> = read_moves(input)?.into_iter()
+        .flat_map(|(direction, distance)| std::iter::repeat_n(direction, distance.max(0) as usize))
+        .collect();
+    return Ok(Box::new(RopeSimulation {rope: Rope::new(knots), moves: moves, num_moves_made: 0}));
+}
+
+// A rope needs at least its head
+fn get_knots(params: &Params) -> Result<usize, SolveError> {
+    let knots: usize = params.value(&KNOTS)?;
+    if knots == 0 {
+        return Err(SolveError::BadParam {name: KNOTS.info.name.to_string(), value: knots.to_string()});
+    }
+    return Ok(knots);
+}
+
+
+fn read_moves(input: &str) -> Result<Vec<(char, i32)>, ParseError> {
+    let mut moves: Vec<(char, i32)> = Vec::new();
+    for line in lines(input) {
+        let direction: char = line.char_at(1, "a direction (R, L, U or D)")?;
+        if !"RLUD".contains(direction) {
+            return Err(line.error_at(1, &direction.to_string(), "a direction (R, L, U or D)"));
+        }
+        let distance: i32 = line.parse_word(1, "a number of steps")?;
+        moves.push((direction, distance));
+    }
+    return Ok(moves);
+}
@@ -0,0 +1,59 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::parse::lines;
+use crate::common::random::Rng;
+use std::collections::HashSet;
+
+// A few dozen moves of up to ten steps each
+pub fn generate(rng: &mut Rng) -> (String, Params) {
+    let num_moves = rng.range(5, 60);
+    let mut input = String::new();
+    for _ in 0..num_moves {
+        input += &format!("{} {}\n", rng.choose(&['R', 'L', 'U', 'D']), rng.range(1, 10));
+    }
+    return (input, Params::new());
+}
+
+// Moves every knot with the rule as the puzzle states it: a knot that isn't touching the one in
+// front takes one step towards it, diagonally if they're not in the same row or column
+pub fn brute_force(part: Part, input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let num_knots = if part == Part::One {2} else {10};
+    let mut knots: Vec<(i32, i32)> = vec![(0, 0); num_knots];
+    let mut visited: HashSet<(i32, i32)> = HashSet::from([(0, 0)]);
+    for line in lines(input) {
+        let direction: char = line.char_at(1, "a direction")?;
+        let distance: i32 = line.parse_word(1, "a number of steps")?;
+        let step = match direction {
+            'R' => (1, 0),
+            'L' => (-1, 0),
+            'U' => (0, 1),
+            'D' => (0, -1),
+            other => return Err(line.error_at(1, &other.to_string(), "R, L, U or D").into()),
+        };
+        for _ in 0..distance {
+            knots[0] = (knots[0].0 + step.0, knots[0].1 + step.1);
+            for i in 1..num_knots {
+                let (dx, dy) = (knots[i - 1].0 - knots[i].0, knots[i - 1].1 - knots[i].1);
+                if (dx.abs() > 1) || (dy.abs() > 1) {
+                    knots[i] = (knots[i].0 + dx.signum(), knots[i].1 + dy.signum());
+                }
+            }
+            visited.insert(knots[num_knots - 1]);
+        }
+    }
+    return Ok(Answer::from(visited.len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for seed in 0..20 {
+            let (input, params) = generate(&mut Rng::new(seed));
+            for part in [Part::One, Part::Two] {
+                assert_eq!(super::super::solve_with_params(part, &input, &params), brute_force(part, &input, &params), "seed {} part {}", seed, part);
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+use crate::common::simulation::Simulation;
+
+#[path = "09_part1.rs"]
+pub mod part1;
+#[path = "09_part2.rs"]
+pub mod part2;
+
+pub mod fuzz;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+pub fn simulation(part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return match part {
+        Part::One => part1::simulation(input, params),
+        Part::Two => part2::simulation(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/09.txt");
+    const LARGER_EXAMPLE: &str = include_str!("../examples/09_large.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(13)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(1)));
+    }
+
+    #[test]
+    fn part2_larger_example() {
+        assert_eq!(solve(Part::Two, LARGER_EXAMPLE), Ok(Answer::from(36)));
+    }
+}
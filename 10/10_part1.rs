@@ -1,7 +1,7 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::log;
+
 
 const CHECK_START_CYCLE: usize = 20;
 const CHECK_CYCLE_FREQUENCY: usize = 40;
@@ -40,48 +40,45 @@ impl Clock {
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let clock = run_program(input)?;
+    log::detail("max_cycle", clock.current_cycle);
+    return Ok(Answer::from(sum_signal_strengths(&clock)));
+}
+
+
+fn run_program(input: &str) -> Result<Clock, ParseError> {
     let mut clock =  Clock::new();
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                if val == "noop".to_string() {
-                    clock.noop();
-                }
-                else if val.starts_with("addx ") {
-                    let instructions: Vec<&str> = val.split(" ").collect();
-                    let to_add: i32  = instructions[1].trim().parse().unwrap();
-                    clock.addx(to_add);
-                }
-            }
+    for line in lines(input) {
+        if line.text == "noop" {
+            clock.noop();
+        }
+        else if line.text.starts_with("addx ") {
+            let to_add: i32  = line.parse_word(1, "a number to add")?;
+            clock.addx(to_add);
+        }
+        else {
+            return Err(line.error(line.text, "'noop' or 'addx <number>'"));
         }
     }
+    return Ok(clock);
+}
+
 
-    println!("Max cycle: {}", clock.current_cycle);
+fn sum_signal_strengths(clock: &Clock) -> i32 {
     let mut output: i32 = 0;
     let mut check_cycle: usize = CHECK_START_CYCLE;
     loop {
+        // A program can stop before the first cycle that's checked
+        if check_cycle as u32 > clock.current_cycle {
+            break;
+        }
         let cycle_register = clock.history[check_cycle].register;
         let signal_strength: i32 = (check_cycle as i32) * cycle_register;
-        println!("{}, {}, {}", check_cycle, cycle_register, signal_strength);
         output += signal_strength;
         check_cycle += CHECK_CYCLE_FREQUENCY;
-        if check_cycle as u32 > clock.current_cycle {
-            break;
-        }
     }
-    println!("Output: {}", output);
+    return output;
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
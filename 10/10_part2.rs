@@ -1,21 +1,61 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::io::stdout;
-use std::io::Write;
+#![allow(clippy::unnecessary_cast)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::geometry::Point2;
+use crate::common::grid::Bounds;
+use crate::common::render::{Cell, Colour, Picture, Render, RenderOptions, ScreenPoint};
 
 type Position = (u32, u32);
 
+const CRT_WIDTH: u32 = 40;
+
+// The pixels drawn so far, row by row
+pub struct Crt {
+    pixels: Vec<Vec<bool>>,
+}
+
+impl Crt {
+    fn new() -> Crt {
+        return Crt {pixels: Vec::new()};
+    }
+
+    fn draw(&mut self, position: Position, lit: bool) {
+        if position.0 == 0 {
+            self.pixels.push(Vec::new());
+        }
+        self.pixels[position.1 as usize].push(lit);
+    }
+}
+
+impl Render for Crt {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        if self.pixels.is_empty() {
+            return None;
+        }
+        return Some(Bounds::new(Point2::new(0, 0), Point2::new(CRT_WIDTH as i64 - 1, self.pixels.len() as i64 - 1)));
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let lit = self.pixels[point.y as usize].get(point.x as usize);
+        return match lit {
+            Some(true) => Cell::new('#', Colour::Green),
+            Some(false) => Cell::new('.', Colour::Grey),
+            None => Cell::plain(' '),
+        };
+    }
+}
+
 struct Clock {
     current_register: i32,
     current_pixel: Position,
     current_cycle: u32,
+    crt: Crt,
 }
 
 impl Clock {
     fn new() -> Clock {
-        return Clock {current_register: 1, current_cycle: 0, current_pixel: (0, 0)};
+        return Clock {current_register: 1, current_cycle: 0, current_pixel: (0, 0), crt: Crt::new()};
     }
 
     fn noop(&mut self) {
@@ -29,50 +69,42 @@ impl Clock {
     }
 
     fn increment_cycle(&mut self) {
-        self.current_pixel.0 = self.current_cycle % 40;
-        self.current_pixel.1 = (self.current_cycle - self.current_pixel.0 as u32)/40;
+        self.current_pixel.0 = self.current_cycle % CRT_WIDTH;
+        self.current_pixel.1 = (self.current_cycle - self.current_pixel.0 as u32)/CRT_WIDTH;
         self.current_cycle += 1;
 
-        if self.current_pixel.0 == 0 {
-            print!("\n");
-            stdout().flush().expect("This should print");
-        }
-        if (self.current_register - self.current_pixel.0 as i32).abs() <= 1 {
-            print!("{}", '#');
-        }
-        else {
-            print!("{}", '.');
-        }
+        let lit = (self.current_register - self.current_pixel.0 as i32).abs() <= 1;
+        self.crt.draw(self.current_pixel, lit);
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let clock = run_program(input)?;
+    let screen = clock.crt.render_to_string(&RenderOptions::default());
+    return Ok(Answer::from(screen.trim_end()));
+}
+
+pub fn picture(input: &str, _params: &Params) -> Result<Picture, SolveError> {
+    let clock = run_program(input)?;
+    return Ok(Picture::capture(&clock.crt));
+}
+
+
+fn run_program(input: &str) -> Result<Clock, ParseError> {
     let mut clock =  Clock::new();
-    if let Ok(lines) = read_lines(file_name) {
-        for line in lines {
-            if let Ok(val) = line {
-                if val == "noop".to_string() {
-                    clock.noop();
-                }
-                else if val.starts_with("addx ") {
-                    let instructions: Vec<&str> = val.split(" ").collect();
-                    let to_add: i32  = instructions[1].trim().parse().unwrap();
-                    clock.addx(to_add);
-                }
-            }
+    for line in lines(input) {
+        if line.text == "noop" {
+            clock.noop();
+        }
+        else if line.text.starts_with("addx ") {
+            let to_add: i32  = line.parse_word(1, "a number to add")?;
+            clock.addx(to_add);
+        }
+        else {
+            return Err(line.error(line.text, "'noop' or 'addx <number>'"));
         }
     }
+    return Ok(clock);
 }
 
-// The output is wrapped in a Result to allow matching on errors
-// Returns an Iterator to the Reader of the lines of the file.
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
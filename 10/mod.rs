@@ -0,0 +1,52 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::render::Picture;
+
+#[path = "10_part1.rs"]
+pub mod part1;
+#[path = "10_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+// Only part two draws anything
+pub fn picture(part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return match part {
+        Part::One => Err(SolveError::NoPicture(10)),
+        Part::Two => part2::picture(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/10.txt");
+    const CRT_PICTURE: &str = include_str!("../examples/10_part2_crt.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(13140)));
+    }
+
+    #[test]
+    fn short_programs() {
+        assert_eq!(solve(Part::One, "noop\n"), Ok(Answer::from(0)));
+        assert_eq!(solve(Part::One, ""), Ok(Answer::from(0)));
+    }
+
+    #[test]
+    fn part2_example() {
+        let answer = solve(Part::Two, EXAMPLE).unwrap();
+        assert_eq!(answer.to_string().trim_end(), CRT_PICTURE.trim_end());
+    }
+}
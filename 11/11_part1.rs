@@ -1,76 +1,82 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::{lines, Line, ParseError};
+
+pub const ROUNDS: Param<u32> = Param::new("rounds", "20", "how many rounds the monkeys play");
+pub const PARAMS: &[&ParamInfo] = &[&ROUNDS.info, &BIGINT.info];
 
 #[derive(Debug)]
-enum FormulaValue {
+enum FormulaValue<T> {
     FormulaVar,
-    Constant(u32),
+    Constant(T),
 }
 
-struct Monkey {
-    items: Vec<u32>,
-    rule_val_a: FormulaValue,
-    rule_val_b: FormulaValue,
+struct Monkey<T> {
+    items: Vec<T>,
+    rule_val_a: FormulaValue<T>,
+    rule_val_b: FormulaValue<T>,
     rule_op: char, 
-    throw_divisibility: u32,
+    throw_divisibility: T,
     true_monkey: usize,
     false_monkey: usize,
-    num_inspections: u32,
+    num_inspections: u64,
 }
 
-impl Monkey {
-    fn add_item(&mut self, item: u32) {
+impl<T: Integer> Monkey<T> {
+    fn add_item(&mut self, item: T) {
         self.items.push(item);
     }
 
-    fn take_turn(&mut self) -> Vec<(usize, u32)> {
-        let mut throw_instructions: Vec<(usize, u32)> = Vec::new();
+    fn take_turn(&mut self) -> Vec<(usize, T)> {
+        let mut throw_instructions: Vec<(usize, T)> = Vec::new();
         for _ in 0..self.items.len() {
             throw_instructions.push(self.inspect_first_item());
         }
         return throw_instructions;
     }
 
-    fn inspect_first_item(&mut self) -> (usize, u32) {
+    fn inspect_first_item(&mut self) -> (usize, T) {
         self.num_inspections += 1;
         let mut this_item = self.items.remove(0);
         this_item = self.change_worry(this_item);
-        let to_monkey = self.get_monkey_to_throw_to(this_item);
+        let to_monkey = self.get_monkey_to_throw_to(&this_item);
         return (to_monkey, this_item);
     }
 
-    fn change_worry(&self, value: u32) -> u32 {
-        let value_a: u32 = match self.rule_val_a {
-            FormulaValue::Constant(number) => number,
-            FormulaValue::FormulaVar => value,
+    fn change_worry(&self, value: T) -> T {
+        let value_a: T = match &self.rule_val_a {
+            FormulaValue::Constant(number) => number.clone(),
+            FormulaValue::FormulaVar => value.clone(),
         };
-        let value_b: u32 = match self.rule_val_b {
-            FormulaValue::Constant(number) => number,
-            FormulaValue::FormulaVar => value,
+        let value_b: T = match &self.rule_val_b {
+            FormulaValue::Constant(number) => number.clone(),
+            FormulaValue::FormulaVar => value.clone(),
         };
         let new_value = match self.rule_op {
             '+' => value_a + value_b,
             '*' => value_a * value_b,
             c => panic!("Unexpected char received for operation: {}", c),
         };
-        return new_value / 3;
+        return new_value / T::from(3);
     }
 
-    fn get_monkey_to_throw_to(&self, value: u32) -> usize {
-        return if value % self.throw_divisibility == 0 {self.true_monkey} else {self.false_monkey};
+    fn get_monkey_to_throw_to(&self, value: &T) -> usize {
+        return if (value.clone() % self.throw_divisibility.clone()).is_zero() {self.true_monkey} else {self.false_monkey};
     }
 }
 
-struct Troupe {
-    monkeys: Vec<Monkey>,
+struct Troupe<T> {
+    monkeys: Vec<Monkey<T>>,
 }
 
-impl Troupe {
-    fn new() -> Troupe {
+impl<T: Integer> Troupe<T> {
+    fn new() -> Troupe<T> {
         return Troupe{monkeys: Vec::new()};
     }
 
-    fn add_monkey(&mut self, monkey: Monkey) {
+    fn add_monkey(&mut self, monkey: Monkey<T>) {
         self.monkeys.push(monkey);
     }
 
@@ -82,108 +88,175 @@ impl Troupe {
 
     fn get_monkey_to_take_turn(&mut self, monkey_ind: usize) {
         let throw_instructions = self.monkeys[monkey_ind].take_turn();
-        for (to_monkey, value) in &throw_instructions {
-            self.throw(*to_monkey, *value);
+        for (to_monkey, value) in throw_instructions {
+            self.throw(to_monkey, value);
         }
     }
 
-    fn throw(&mut self, to_monkey: usize, item: u32) {
+    fn throw(&mut self, to_monkey: usize, item: T) {
         self.monkeys[to_monkey].add_item(item);
     }
 
-    fn get_monkey_business(&self) -> u32 {
-        let mut monkey_activity: Vec<u32> = Vec::new();
+    // Worry levels stay overflowed once they have, and the monkeys never drop an item, so this catches
+    // any overflow along the way
+    fn overflowed(&self) -> bool {
+        return self.monkeys.iter().any(|monkey| monkey.items.iter().any(|item| item.overflowed()));
+    }
+
+    fn get_monkey_business(&self) -> Result<u64, SolveError> {
+        let mut monkey_activity: Vec<u64> = Vec::new();
         for monkey in &self.monkeys {
             monkey_activity.push(monkey.num_inspections);
         }
         monkey_activity.sort_by(|a, b| b.cmp(a));
-        return monkey_activity[0] * monkey_activity[1];
+        return monkey_activity[0].checked_mul(monkey_activity[1]).ok_or(SolveError::Overflow("The monkey business".to_string()));
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    
-    let mut monkeys: Troupe = initialise_monkeys(file_name);
-    for _ in 0..20 {
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input, params),
+        false => solve_with::<Checked>(input, params),
+    };
+}
+
+fn solve_with<T: Integer>(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let monkeys: Troupe<T> = initialise_monkeys(input)?;
+    return Ok(Answer::from(get_monkey_business_after_rounds(monkeys, params.value(&ROUNDS)?)?));
+}
+
+
+fn get_monkey_business_after_rounds<T: Integer>(mut monkeys: Troupe<T>, rounds: u32) -> Result<u64, SolveError> {
+    for _ in 0..rounds {
         monkeys.play_round();
     }
-    let monkey_business = monkeys.get_monkey_business();
-    println!("The level of monkey business is: {}", monkey_business);
+    if monkeys.overflowed() {
+        return Err(SolveError::Overflow("The worry levels".to_string()));
+    }
+    return monkeys.get_monkey_business();
 }
 
 
-fn initialise_monkeys(file_name: &String) -> Troupe {
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+fn initialise_monkeys<T: Integer>(input: &str) -> Result<Troupe<T>, ParseError> {
     let mut monkeys = Troupe::new();
 
-    for monkey_string in input.split("\n\n").collect::<Vec<&str>>() {
-        let new_monkey = initialise_monkey(monkey_string);
+    let input_lines: Vec<Line> = lines(input).collect();
+    for monkey_lines in input_lines.split(|line| line.text.trim().is_empty()) {
+        if monkey_lines.is_empty() {
+            continue;
+        }
+        let new_monkey = initialise_monkey(monkey_lines)?;
         monkeys.add_monkey(new_monkey);
     }
-    return monkeys;
+
+    // The monkey business is the two busiest monkeys' inspections multiplied
+    let num_monkeys = monkeys.monkeys.len();
+    if num_monkeys < 2 {
+        let found = if num_monkeys == 1 {"one monkey".to_string()} else {"no monkeys".to_string()};
+        return Err(ParseError::whole_input(&found, "at least two monkeys"));
+    }
+    for (index, monkey) in monkeys.monkeys.iter().enumerate() {
+        for target in [monkey.true_monkey, monkey.false_monkey] {
+            if target >= num_monkeys {
+                let found = format!("monkey {} throwing to monkey {}", index, target);
+                return Err(ParseError::whole_input(&found, &format!("a monkey from 0 to {}", num_monkeys - 1)));
+            }
+        }
+    }
+    return Ok(monkeys);
 }
 
 
-fn initialise_monkey(input: &str) -> Monkey {
-    let mut items: Option<Vec<u32>> = None;
+fn initialise_monkey<T: Integer>(monkey_lines: &[Line]) -> Result<Monkey<T>, ParseError> {
+    let mut items: Option<Vec<T>> = None;
     let items_start = "Starting items: ";
 
-    let mut rule_val_a: Option<FormulaValue> = None;
-    let mut rule_val_b: Option<FormulaValue> = None;
+    let mut rule_val_a: Option<FormulaValue<T>> = None;
+    let mut rule_val_b: Option<FormulaValue<T>> = None;
     let mut rule_operation: Option<char> = None;
     let operation_start = "Operation: new = ";
 
-    let mut divisible_by: Option<u32> = None;
+    let mut divisible_by: Option<T> = None;
     let divisible_start = "Test: divisible by ";
     let mut true_monkey: Option<usize> = None;
     let true_start = "If true: throw to monkey ";
     let mut false_monkey: Option<usize> = None;
     let false_start = "If false: throw to monkey ";
 
-    for line in input.lines() {
-        if line.starts_with("Monkey") {}
-        else if line.trim().starts_with(items_start) {
-            items = Some(line.trim().replace(items_start, "").split(", ").map(|x| x.parse::<u32>().unwrap()).collect());
+    for line in monkey_lines {
+        let text = line.text.trim();
+        if text.starts_with("Monkey") {}
+        else if text.starts_with(items_start) {
+            let item_list = line.after(items_start)?;
+            items = Some(item_list.split(", ").map(|x| parse_whole_number(line, x, "a worry level")).collect::<Result<Vec<T>, ParseError>>()?);
         }
-        else if line.trim().starts_with(operation_start){
-            let expr: String = line.trim().replace(operation_start, "");
-            let expr_vec: Vec<&str> = expr.split(" ").collect();
-            rule_val_a = match expr_vec[0].parse::<u32>() {
-                Ok(number) => Some(FormulaValue::Constant(number)),
-                Err(_) => Some(FormulaValue::FormulaVar),
+        else if text.starts_with(operation_start){
+            let expr = line.after(operation_start)?;
+            let expr_vec: Vec<&str> = expr.split_whitespace().collect();
+            if expr_vec.len() != 3 {
+                return Err(line.error(expr, "an expression like 'old * 19'"));
+            }
+            rule_val_a = Some(get_formula_value(line, expr_vec[0])?);
+            rule_val_b = Some(get_formula_value(line, expr_vec[2])?);
+            rule_operation = match expr_vec[1] {
+                "+" => Some('+'),
+                "*" => Some('*'),
+                other => return Err(line.error(other, "'+' or '*'")),
             };
-            rule_val_b = match expr_vec[2].parse::<u32>() {
-                Ok(number) => Some(FormulaValue::Constant(number)),
-                Err(_) => Some(FormulaValue::FormulaVar),
-            };
-            rule_operation = Some(expr_vec[1].chars().nth(0).unwrap());
-
         }
-        else if line.trim().starts_with(divisible_start) {
-            divisible_by = Some(line.trim().replace(divisible_start, "").parse().unwrap());
+        else if text.starts_with(divisible_start) {
+            let divisor: T = parse_whole_number(line, line.after(divisible_start)?, "a number to divide by")?;
+            if divisor.is_zero() {
+                return Err(line.error(line.after(divisible_start)?, "a number to divide by other than 0"));
+            }
+            divisible_by = Some(divisor);
         }
-        else if line.trim().starts_with(true_start) {
-            true_monkey = Some(line.trim().replace(true_start, "").parse().unwrap());
+        else if text.starts_with(true_start) {
+            true_monkey = Some(line.parse(line.after(true_start)?, "a monkey number")?);
         }
-        else if line.trim().starts_with(false_start) {
-            false_monkey = Some(line.trim().replace(false_start, "").parse().unwrap());
+        else if text.starts_with(false_start) {
+            false_monkey = Some(line.parse(line.after(false_start)?, "a monkey number")?);
         }
         else {
-            panic!("'{}' was unexpected!", line.trim());
+            return Err(line.error(text, "a line describing the monkey"));
         }
     }
 
-    return Monkey {
-        items: items.expect("items should be initialised"), 
-        rule_val_a: rule_val_a.expect("rule_val_a should be initialised"), 
-        rule_val_b: rule_val_b.expect("rule_val_b should be initialised"),
-        rule_op: rule_operation.expect("rule_op should be initialised"),
-        throw_divisibility: divisible_by.expect("throw_divisibility should be initialised"),
-        true_monkey: true_monkey.expect("true_monkey should be initialised"),
-        false_monkey: false_monkey.expect("false_monkey should be initialised"),
+    return Ok(Monkey {
+        items: items.ok_or(get_missing_line_error(monkey_lines, items_start))?, 
+        rule_val_a: rule_val_a.ok_or(get_missing_line_error(monkey_lines, operation_start))?, 
+        rule_val_b: rule_val_b.ok_or(get_missing_line_error(monkey_lines, operation_start))?,
+        rule_op: rule_operation.ok_or(get_missing_line_error(monkey_lines, operation_start))?,
+        throw_divisibility: divisible_by.ok_or(get_missing_line_error(monkey_lines, divisible_start))?,
+        true_monkey: true_monkey.ok_or(get_missing_line_error(monkey_lines, true_start))?,
+        false_monkey: false_monkey.ok_or(get_missing_line_error(monkey_lines, false_start))?,
         num_inspections: 0,
-    };
+    });
+}
+
+
+fn get_formula_value<T: Integer>(line: &Line, token: &str) -> Result<FormulaValue<T>, ParseError> {
+    if token == "old" {
+        return Ok(FormulaValue::FormulaVar);
+    }
+    return Ok(FormulaValue::Constant(parse_whole_number(line, token, "'old' or a number")?));
+}
+
+
+// Worry levels never go below 0. One too big to hold is left overflowed, to be reported as that
+// once the monkeys are done, and overflowed numbers sort below every other so it can't be compared.
+fn parse_whole_number<T: Integer>(line: &Line, token: &str, expected: &str) -> Result<T, ParseError> {
+    let number: T = line.parse(token, expected)?;
+    if !number.overflowed() && (number < T::from(0)) {
+        return Err(line.error(token.trim(), expected));
+    }
+    return Ok(number);
+}
+
+
+// Points just past the end of the monkey's last line
+fn get_missing_line_error(monkey_lines: &[Line], line_start: &str) -> ParseError {
+    let last_line = monkey_lines[monkey_lines.len() - 1];
+    let column = last_line.text.chars().count() + 1;
+    return last_line.error_at(column, "end of monkey", &format!("a line starting '{}'", line_start.trim()));
 }
@@ -0,0 +1,163 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::random::Rng;
+
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+// Between two and eight monkeys, each testing a different prime, like the real troupes
+pub fn generate(rng: &mut Rng) -> (String, Params) {
+    let num_monkeys = rng.range(2, 8) as usize;
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+    // Squaring makes worry levels grow fastest, so like the real troupes at most one monkey does it.
+    // Nobody throws to it either, or an item squared every round would be too big to work with exactly
+    let squarer = if (num_monkeys > 2) && rng.chance(0.4) {Some(rng.index(num_monkeys))} else {None};
+    let mut input = String::new();
+    for (index, divisor) in primes.iter().enumerate().take(num_monkeys) {
+        let num_items = rng.range(1, 5);
+        let items: Vec<String> = (0..num_items).map(|_| rng.range(1, 99).to_string()).collect();
+        let operation = match rng.below(3) {
+            _ if Some(index) == squarer => "old * old".to_string(),
+            0 => format!("old * {}", rng.range(2, 7)),
+            _ => format!("old + {}", rng.range(1, 9)),
+        };
+        let mut targets = (0..num_monkeys).filter(|&target| (target != index) && (Some(target) != squarer)).collect::<Vec<usize>>();
+        rng.shuffle(&mut targets);
+        let false_target = if targets.len() > 1 {targets[1]} else {targets[0]};
+        if index > 0 {
+            input += "\n";
+        }
+        input += &format!("Monkey {}:\n", index);
+        input += &format!("  Starting items: {}\n", items.join(", "));
+        input += &format!("  Operation: new = {}\n", operation);
+        input += &format!("  Test: divisible by {}\n", divisor);
+        input += &format!("    If true: throw to monkey {}\n", targets[0]);
+        input += &format!("    If false: throw to monkey {}\n", false_target);
+    }
+    // Part 1's worry levels often outgrow i64, so the solver needs big integers to match the brute force
+    let mut params = Params::new();
+    params.insert("bigint", "true");
+    return (input, params);
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: (String, char, String),
+    divisor: u64,
+    targets: (usize, usize),
+}
+
+// Part 1 keeps every worry level exactly, and part 2 keeps each item as its remainders for every
+// monkey's divisor, which is all the throwing depends on
+pub fn brute_force(part: Part, input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut monkeys = read_monkeys(input)?;
+    let mut inspections: Vec<u64> = vec![0; monkeys.len()];
+    let divisors: Vec<u64> = monkeys.iter().map(|monkey| monkey.divisor).collect();
+    if part == Part::One {
+        let mut items: Vec<Vec<BigInt>> = monkeys.iter().map(|monkey| monkey.items.iter().map(|&item| BigInt::from(item as i64)).collect()).collect();
+        let three = BigInt::from(3);
+        for _ in 0..20 {
+            for index in 0..monkeys.len() {
+                for item in std::mem::take(&mut items[index]) {
+                    inspections[index] += 1;
+                    let worry = &apply_exactly(&monkeys[index].operation, &item) / &three;
+                    let (if_true, if_false) = monkeys[index].targets;
+                    let divisor = BigInt::from(monkeys[index].divisor as i64);
+                    items[if (&worry % &divisor).is_zero() {if_true} else {if_false}].push(worry);
+                }
+            }
+        }
+    }
+    else {
+        let mut items: Vec<Vec<Vec<u128>>> = monkeys.iter_mut().map(|monkey| {
+            return monkey.items.iter().map(|&item| divisors.iter().map(|&divisor| (item % divisor) as u128).collect()).collect();
+        }).collect();
+        for _ in 0..10000 {
+            for index in 0..monkeys.len() {
+                for item in std::mem::take(&mut items[index]) {
+                    inspections[index] += 1;
+                    let remainders: Vec<u128> = item.iter().zip(&divisors).map(|(&remainder, &divisor)| {
+                        return apply(&monkeys[index].operation, remainder, divisor as u128);
+                    }).collect();
+                    let (if_true, if_false) = monkeys[index].targets;
+                    items[if remainders[index] == 0 {if_true} else {if_false}].push(remainders);
+                }
+            }
+        }
+    }
+    inspections.sort_by(|a, b| b.cmp(a));
+    return Ok(Answer::from(inspections[0] * inspections[1]));
+}
+
+fn apply(operation: &(String, char, String), old: u128, modulo: u128) -> u128 {
+    let value = |token: &str| -> u128 {
+        return if token == "old" {old} else {token.parse::<u128>().unwrap()};
+    };
+    let (a, b) = (value(&operation.0) % modulo, value(&operation.2) % modulo);
+    return if operation.1 == '*' {(a * b) % modulo} else {(a + b) % modulo};
+}
+
+fn apply_exactly(operation: &(String, char, String), old: &BigInt) -> BigInt {
+    let value = |token: &str| -> BigInt {
+        return if token == "old" {old.clone()} else {token.parse::<BigInt>().unwrap()};
+    };
+    let (a, b) = (value(&operation.0), value(&operation.2));
+    return if operation.1 == '*' {&a * &b} else {&a + &b};
+}
+
+fn read_monkeys(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let bad_input = |text: &str| SolveError::BadInput(format!("'{}' isn't part of a monkey", text));
+    let mut monkeys: Vec<Monkey> = Vec::new();
+    for block in input.split("\n\n").filter(|block| !block.trim().is_empty()) {
+        let lines: Vec<&str> = block.lines().map(|line| line.trim()).collect();
+        if lines.len() != 6 {
+            return Err(bad_input(block));
+        }
+        let after = |line: &str, prefix: &str| -> Result<String, SolveError> {
+            return line.strip_prefix(prefix).map(|rest| rest.to_string()).ok_or(bad_input(line));
+        };
+        let number = |line: &str, prefix: &str| -> Result<u64, SolveError> {
+            return after(line, prefix)?.parse::<u64>().map_err(|_| bad_input(line));
+        };
+        let items = after(lines[1], "Starting items: ")?.split(", ").map(|item| item.parse::<u64>().map_err(|_| bad_input(lines[1]))).collect::<Result<Vec<u64>, SolveError>>()?;
+        let operation: Vec<String> = after(lines[2], "Operation: new = ")?.split_whitespace().map(|token| token.to_string()).collect();
+        let is_value = |token: &str| (token == "old") || token.parse::<u128>().is_ok();
+        if (operation.len() != 3) || !["+", "*"].contains(&operation[1].as_str()) || !is_value(&operation[0]) || !is_value(&operation[2]) {
+            return Err(bad_input(lines[2]));
+        }
+        monkeys.push(Monkey {
+            items: items,
+            operation: (operation[0].clone(), operation[1].chars().next().unwrap(), operation[2].clone()),
+            divisor: number(lines[3], "Test: divisible by ")?,
+            targets: (number(lines[4], "If true: throw to monkey ")? as usize, number(lines[5], "If false: throw to monkey ")? as usize),
+        });
+    }
+    if monkeys.len() < 2 || monkeys.iter().any(|monkey| monkey.divisor == 0 || monkey.targets.0 >= monkeys.len() || monkey.targets.1 >= monkeys.len()) {
+        return Err(SolveError::BadInput("expected at least two monkeys that throw to each other".to_string()));
+    }
+    return Ok(monkeys);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brute_force_example() {
+        let example = include_str!("../examples/11.txt");
+        assert_eq!(brute_force(Part::One, example, &Params::new()), Ok(Answer::from(10605)));
+        assert_eq!(brute_force(Part::Two, example, &Params::new()), Ok(Answer::from(2713310158_i64)));
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for seed in 0..10 {
+            let (input, params) = generate(&mut Rng::new(seed));
+            for part in [Part::One, Part::Two] {
+                let expected = brute_force(part, &input, &params);
+                assert!(expected.is_ok(), "seed {} part {}", seed, part);
+                assert_eq!(super::super::solve_with_params(part, &input, &params), expected, "seed {} part {}", seed, part);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "11_part1.rs"]
+pub mod part1;
+#[path = "11_part2.rs"]
+pub mod part2;
+
+pub mod fuzz;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/11.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(10605)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(2713310158_i64)));
+    }
+
+    #[test]
+    fn lone_monkey() {
+        let input = "Monkey 0:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 2\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n";
+        for part in [Part::One, Part::Two] {
+            assert!(matches!(solve(part, input), Err(SolveError::Parse(error)) if error.expected == "at least two monkeys"));
+        }
+    }
+
+    #[test]
+    fn worry_levels_past_64_bits() {
+        let troupe = |item: &str| format!("Monkey 0:\n  Starting items: {}\n  Operation: new = old * old\n  Test: divisible by 2\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n\nMonkey 1:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 3\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n", item);
+        // Squared, this is just past i64::MAX
+        let input = troupe("3037000500");
+        assert_eq!(solve(Part::One, &input), Err(SolveError::Overflow("The worry levels".to_string())));
+        assert_eq!(solve(Part::Two, &input), Err(SolveError::Overflow("The worry levels".to_string())));
+        // Part 2 only cares about the item modulo 6, which big integers get right
+        let mut params = Params::new();
+        params.insert("bigint", "true");
+        assert_eq!(solve_with_params(Part::Two, &input, &params), solve(Part::Two, &troupe("6")));
+        // A worry level too big to read at all overflows the same way, rather than failing to parse
+        let input = troupe("99999999999999999999");
+        assert_eq!(solve(Part::One, &input), Err(SolveError::Overflow("The worry levels".to_string())));
+        assert_eq!(solve(Part::Two, &input), Err(SolveError::Overflow("The worry levels".to_string())));
+    }
+}
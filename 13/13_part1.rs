@@ -1,27 +1,40 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, Line, ParseError};
+use crate::common::log;
+use crate::common::params::ParamInfo;
 use std::cmp::Ordering;
 
-mod packet_data;
-use packet_data::Data;
+use super::packet_data::Data;
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+pub const PARAMS: &[&ParamInfo] = &[];
 
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let correct_inds = find_correctly_ordered_pairs(input)?;
+    log::detail("correct_indices", correct_inds.clone());
+    return Ok(Answer::from(correct_inds.iter().sum::<u32>()));
+}
+
+
+fn find_correctly_ordered_pairs(input: &str) -> Result<Vec<u32>, ParseError> {
     let mut correct_inds: Vec<u32> = Vec::new();
     let mut ind: u32 = 1;
-    for pair in input.split("\n\n").collect::<Vec<&str>>() {
-        let pair_vec: Vec<&str> = pair.trim().split("\n").collect();
-        let packet_1: Data = Data::from_string(&pair_vec[0].trim().to_string());
-        let packet_2: Data = Data::from_string(&pair_vec[1].trim().to_string());
+    let input_lines: Vec<Line> = lines(input).collect();
+    for pair_vec in input_lines.split(|line| line.text.trim().is_empty()) {
+        if pair_vec.is_empty() {
+            continue;
+        }
+        if pair_vec.len() == 1 {
+            return Err(ParseError::new(pair_vec[0].number + 1, 1, "", "a blank line", "the second packet of the pair"));
+        }
+        if pair_vec.len() > 2 {
+            return Err(pair_vec[2].error(pair_vec[2].text.trim(), "a blank line after each pair"));
+        }
+        let packet_1: Data = Data::from_line(&pair_vec[0])?;
+        let packet_2: Data = Data::from_line(&pair_vec[1])?;
         if packet_1.cmp(&packet_2) == Ordering::Less {
             correct_inds.push(ind);
         }
         ind += 1;
     }
-    println!("Correct inds: {:?}", correct_inds);
-    println!("Sum of correct inds: {}", correct_inds.iter().sum::<u32>());
-} 
+    return Ok(correct_inds);
+}
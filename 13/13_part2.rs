@@ -1,25 +1,62 @@
-use std::env;
-use std::fs;
+use std::str::FromStr;
 
-mod packet_data;
-use packet_data::Data;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::parse::{lines, Line, ParseError};
+use crate::common::log;
 
+use super::packet_data::Data;
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+pub const DIVIDERS: Param<Dividers> = Param::new("dividers", "[[2]] [[6]]", "the divider packets, separated by spaces");
+pub const PARAMS: &[&ParamInfo] = &[&DIVIDERS.info];
 
-    let string_separator_1 = "[[2]]".to_string();
-    let string_separator_2 = "[[6]]".to_string();
-    let mut packets: Vec<Data> = vec![Data::from_string(&string_separator_1.to_string()), Data::from_string(&string_separator_2.to_string())];
-    for str_packet in input.lines().collect::<Vec<&str>>() {
-        if str_packet != "".to_string() {
-            let packet: Data = Data::from_string(&str_packet.trim().to_string());
+// Each one is checked like a line of the input
+pub struct Dividers(Vec<String>);
+
+impl FromStr for Dividers {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut dividers: Vec<String> = Vec::new();
+        for divider in text.split_whitespace() {
+            Data::from_line(&Line {number: 1, text: divider})?;
+            if !dividers.iter().any(|other| other == divider) {
+                dividers.push(divider.to_string());
+            }
+        }
+        if dividers.is_empty() {
+            return Err(ParseError::whole_input("no divider packets", "a packet like [[2]]"));
+        }
+        return Ok(Dividers(dividers));
+    }
+}
+
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let Dividers(dividers) = params.value(&DIVIDERS)?;
+    let packets: Vec<Data> = read_packets(input)?;
+    let separator_inds: Vec<usize> = find_separator_inds(packets, &dividers);
+    log::detail("separator_indices", separator_inds.clone());
+    return Ok(Answer::from(separator_inds.iter().product::<usize>()));
+}
+
+
+fn read_packets(input: &str) -> Result<Vec<Data>, ParseError> {
+    let mut packets: Vec<Data> = Vec::new();
+    for line in lines(input) {
+        if line.text.trim() != "" {
+            let packet: Data = Data::from_line(&line)?;
             packets.push(packet);
         }
     }
+    return Ok(packets);
+}
+
+
+fn find_separator_inds(mut packets: Vec<Data>, separators: &[String]) -> Vec<usize> {
+    for separator in separators {
+        packets.push(Data::from_string(separator));
+    }
     packets.sort();
 
     // We'll look for the separators as strings to avoid the recursion needed for Data comparisons
@@ -27,9 +64,5 @@ fn main() {
     for packet in packets {
         str_packets.push(packet.to_string());
     }
-    let ind_sep_1 = str_packets.iter().position(|r| r == &string_separator_1).unwrap() + 1;
-    let ind_sep_2 = str_packets.iter().position(|r| r == &string_separator_2).unwrap() + 1;
-
-    println!("Separator inds: {}, {}", ind_sep_1, ind_sep_2);
-    println!("Product: {}", ind_sep_1 * ind_sep_2);
-} 
+    return separators.iter().map(|separator| str_packets.iter().position(|r| r == separator).unwrap() + 1).collect();
+}
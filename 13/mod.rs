@@ -0,0 +1,56 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "13_part1.rs"]
+pub mod part1;
+#[path = "13_part2.rs"]
+pub mod part2;
+
+mod packet_data;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/13.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(13)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(140)));
+    }
+
+    #[test]
+    fn other_dividers() {
+        let mut params = Params::new();
+        params.insert("dividers", "[[2]]");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(10)));
+        params.insert("dividers", "[[2]] [[6]] [[2]] []");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(11 * 15)));
+        params.insert("dividers", "[[2]] [6");
+        assert!(matches!(solve_with_params(Part::Two, EXAMPLE, &params), Err(SolveError::BadParam {..})));
+    }
+}
@@ -1,5 +1,9 @@
+#![allow(clippy::comparison_to_empty, clippy::inherent_to_string, clippy::unnecessary_to_owned)]
+
 use std::cmp::Ordering;
 
+use crate::common::parse::{Line, ParseError};
+
 
 #[derive(Debug)]
 pub enum Data {
@@ -21,6 +25,17 @@ impl Data {
         }
     }
 
+    // Checks the whole packet up front, since the comparisons only parse it lazily
+    pub fn from_line(line: &Line) -> Result<Data, ParseError> {
+        let chars: Vec<char> = line.text.chars().collect();
+        let start = chars.iter().take_while(|c| c.is_whitespace()).count();
+        let end = check_list(line, &chars, start)?;
+        if chars[end..].iter().any(|c| !c.is_whitespace()) {
+            return Err(line.error_at(end + 1, &chars[end].to_string(), "the end of the packet"));
+        }
+        return Ok(Data::from_string(&line.text.trim().to_string()));
+    }
+
     pub fn to_string(&self) -> String {
         return match &self {
             Data::Scalar(data) => data.to_string(),
@@ -120,3 +135,79 @@ impl Ord for Data {
         };
     }
 }
+
+
+// Returns the index just after the list starting at `start`
+fn check_list(line: &Line, chars: &Vec<char>, start: usize) -> Result<usize, ParseError> {
+    let found = |index: usize| {
+        return chars.get(index).map(|c| c.to_string()).unwrap_or("end of line".to_string());
+    };
+    if chars.get(start) != Some(&'[') {
+        return Err(line.error_at(start + 1, &found(start), "'['"));
+    }
+    let mut index = start + 1;
+    if chars.get(index) == Some(&']') {
+        return Ok(index + 1);
+    }
+    loop {
+        match chars.get(index) {
+            Some('[') => index = check_list(line, chars, index)?,
+            Some(c) if c.is_ascii_digit() => {
+                let number_start = index;
+                while chars.get(index).is_some_and(|c| c.is_ascii_digit()) {
+                    index += 1;
+                }
+                let number: String = chars[number_start..index].iter().collect();
+                if number.parse::<u32>().is_err() {
+                    return Err(line.error_at(number_start + 1, &number, "a number that fits in 32 bits"));
+                }
+            },
+            _ => return Err(line.error_at(index + 1, &found(index), "a number or '['")),
+        };
+        match chars.get(index) {
+            Some(',') => index += 1,
+            Some(']') => return Ok(index + 1),
+            _ => return Err(line.error_at(index + 1, &found(index), "',' or ']'")),
+        };
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::parse::lines;
+
+    fn packet(text: &str) -> Data {
+        return Data::from_string(&text.to_string());
+    }
+
+    #[test]
+    fn example_pairs() {
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[[4,4],4,4]") < packet("[[4,4],4,4,4]"));
+        assert!(packet("[7,7,7,7]") > packet("[7,7,7]"));
+        assert!(packet("[]") < packet("[3]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert!(packet("[1,[2,[3,[4,[5,6,7]]]],8,9]") > packet("[1,[2,[3,[4,[5,6,0]]]],8,9]"));
+    }
+
+    #[test]
+    fn a_scalar_equals_a_list_of_just_that_scalar() {
+        assert_eq!(packet("[[2]]"), packet("[2]"));
+        assert_eq!(packet("10"), packet("[10]"));
+        assert!(packet("[10]") > packet("[9,9]"));
+    }
+
+    #[test]
+    fn from_line_points_at_the_problem() {
+        let line = lines("[1,[2,x]]").next().unwrap();
+        let error = Data::from_line(&line).unwrap_err();
+        assert_eq!((error.line, error.column, error.found.as_str()), (1, 7, "x"));
+
+        let line = lines("[1,2").next().unwrap();
+        assert_eq!(Data::from_line(&line).unwrap_err().found, "end of line");
+    }
+}
@@ -1,10 +1,21 @@
-use std::env;
-use std::fs;
-use std::collections::HashMap;
+#![allow(clippy::needless_late_init, clippy::single_match)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::geometry::Point2;
+use crate::common::grid::{Bounds, Grid, SparseGrid};
+use crate::common::log::{self, Level};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::recorder;
+use crate::common::render::{stderr_supports_colour, Cell, Colour, Picture, Render, RenderOptions, ScreenPoint};
+use crate::common::simulation::Simulation;
+use crate::log_trace;
 use std::cmp::Ordering;
-use std::io::stdout;
-use std::io::Write;
-use std::ops::Add;
+
+type Point = Point2<i32>;
+
+pub const SOURCE: Param<Point> = Param::new("source", "500,0", "where the sand comes in, as x,y");
+pub const PARAMS: &[&ParamInfo] = &[&SOURCE.info];
 
 
 #[allow(dead_code)]
@@ -23,64 +34,21 @@ enum DropStatus {
     Void,
 }
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Point {
-        return Point {x: x, y: y};
-    }
-
-    fn from_string(string: &String) -> Point {
-        let points: Vec<&str> = string.trim().split(",").collect();
-        return Point {x: points[0].parse::<i32>().unwrap(), y: points[1].parse::<i32>().unwrap()};
-    }   
-
-    fn scalar_mult(&self, scalar: i32) -> Point {
-        return Self::new(scalar * self.x, scalar * self.y);
-    }
-}
-
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y)
-    }
-}
-
-
+#[derive(Clone)]
 struct Tiles {
-    tiles: HashMap<Point, Tile>,
+    tiles: SparseGrid<i32, Tile>,
     source: Point,
-    max_x: i32,
-    min_x: i32,
-    max_y: i32,
-    min_y: i32,
 }
 
 impl Tiles {
     fn new(source: Point) -> Tiles {
-        let mut hashmap = HashMap::new();
-        hashmap.insert(source, Tile::Source);
-        return Tiles {
-            tiles: hashmap, 
-            source: source, 
-            max_x: source.x,
-            min_x: source.x,
-            max_y: source.y,
-            min_y: source.y,
-        };
+        let mut tiles = SparseGrid::new();
+        tiles.set(source, Tile::Source);
+        return Tiles {tiles: tiles, source: source};
     }
 
     fn add_point(&mut self, point: Point, tile: Tile) {
-        if point.x > self.max_x {self.max_x = point.x;}
-        if point.x < self.min_x {self.min_x = point.x;}
-        if point.y > self.max_y {self.max_y = point.y;}
-        if point.y > self.max_y {self.min_y = point.y;}
-        self.tiles.insert(point, tile);
+        self.tiles.set(point, tile);
     }
 
     fn draw_line(&mut self, start: Point, end: Point, tile: Tile) {
@@ -162,48 +130,135 @@ impl Tiles {
     }
 
     fn within_bounds(&self, point: Point) -> bool {
-        return (point.x <= self.max_x) && (point.x >= self.min_x) && (point.y <= self.max_y) && (point.y >= self.min_y);
+        return self.tiles.in_bounds(&point);
     }
 
     fn tile_occupied(&self, point: Point) -> bool {
-        return self.tiles.contains_key(&point);
+        return self.tiles.contains(&point);
     }
 
     #[allow(dead_code)]
-    fn render_to_stdout(&self) {
-        let width: i32 = self.max_x - self.min_x + 1;
-        let height: i32 = self.max_y - self.min_y + 1;
-        for i in 0..height {
-            for j in 0..width {
-                let tile_char = match self.tile_at_point(Point::new(self.min_x + j, self.min_y + i)) {
-                    Tile::Air => '.',
-                    Tile::Stone => '#',
-                    Tile::Sand => 'o',
-                    Tile::Source => '+',
-                };
-                print!("{}", tile_char);
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
+    fn render_to_log(&self) {
+        if !log::is_enabled(Level::Trace) {
+            return;
         }
+        let options = RenderOptions::default().with_colour(stderr_supports_colour());
+        log_trace!("\n{}", self.render_to_string(&options));
+    }
+}
+
+impl Render for Tiles {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.tiles.bounds().map(|bounds| bounds.to_i64());
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return match self.tile_at_point(Point::from_i64(point)) {
+            Tile::Air => Cell::new('.', Colour::Grey),
+            Tile::Stone => Cell::new('#', Colour::White),
+            Tile::Sand => Cell::new('o', Colour::Yellow),
+            Tile::Source => Cell::new('+', Colour::Red),
+        };
     }
 }
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("file name is '{}'", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+// One step is one unit of sand, dropped from the source until it comes to rest
+struct SandSimulation {
+    initial_tiles: Tiles,
+    tiles: Tiles,
+    sand_count: u32,
+    finished: bool,
+}
+
+impl Render for SandSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.tiles.render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return self.tiles.cell_at(point);
+    }
+}
 
-    let mut tiles: Tiles = Tiles::new(Point::new(500, 0));
-    for wall in input.trim().lines().collect::<Vec<&str>>() {
-        let vertices: Vec<&str> = wall.trim().split(" -> ").collect();
+impl Simulation for SandSimulation {
+    fn step(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        return match self.tiles.drop() {
+            DropStatus::Success => {
+                self.sand_count += 1;
+                true
+            },
+            DropStatus::SourceBlocked => {
+                self.sand_count += 1;
+                self.finished = true;
+                true
+            },
+            DropStatus::Void => {
+                self.finished = true;
+                false
+            },
+        };
+    }
+
+    fn reset(&mut self) {
+        self.tiles = self.initial_tiles.clone();
+        self.sand_count = 0;
+        self.finished = false;
+    }
+
+    fn status(&self) -> String {
+        let ending = if self.finished {"the source is blocked"} else {"more is falling"};
+        return format!("{} units of sand at rest, and {}", self.sand_count, ending);
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let point = Point::from_i64(point);
+        return format!("{}: {:?}", point, self.tiles.tile_at_point(point));
+    }
+}
+
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let mut tiles: Tiles = read_tiles(input, params.value(&SOURCE)?)?;
+    add_floor(&mut tiles);
+    return Ok(Answer::from(pour_sand(&mut tiles)));
+}
+
+// The pile once it's blocked the source
+pub fn picture(input: &str, params: &Params) -> Result<Picture, SolveError> {
+    let mut tiles: Tiles = read_tiles(input, params.value(&SOURCE)?)?;
+    add_floor(&mut tiles);
+    pour_sand(&mut tiles);
+    return Ok(Picture::capture(&tiles));
+}
+
+
+pub fn simulation(input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let mut tiles: Tiles = read_tiles(input, params.value(&SOURCE)?)?;
+    add_floor(&mut tiles);
+    return Ok(Box::new(SandSimulation {initial_tiles: tiles.clone(), tiles: tiles, sand_count: 0, finished: false}));
+}
+
+fn read_tiles(input: &str, source: Point) -> Result<Tiles, ParseError> {
+    let mut tiles: Tiles = Tiles::new(source);
+    for wall in lines(input) {
+        if wall.text.trim().is_empty() {
+            continue;
+        }
+        let vertices: Vec<&str> = wall.text.trim().split(" -> ").collect();
         let mut current: Option<Point> = None;
         let mut prev: Option<Point>;
         for point in vertices {
             prev = current;
-            let this_point = Point::from_string(&point.to_string());
+            let this_point = wall.parse::<Point>(point, "a point like '498,4'")?;
+            if let Some(prev_point) = prev {
+                if (prev_point.x != this_point.x) && (prev_point.y != this_point.y) {
+                    return Err(wall.error(point, "a point in a straight line from the last one"));
+                }
+            }
             current = Some(this_point);
             match (prev, current) {
                 (Some(point1), Some(point2)) => tiles.draw_line(point1, point2, Tile::Stone),
@@ -211,18 +266,33 @@ fn main() {
             };
         }
     }
+    // The sand source is always there, so it takes a stone to have drawn any rock
+    if !tiles.tiles.iter().any(|(_, tile)| matches!(tile, Tile::Stone)) {
+        return Err(ParseError::whole_input("no rock", "at least one path of rock"));
+    }
+    return Ok(tiles);
+}
+
 
-    let floor_height = tiles.max_y + 2;
-    let max_left = tiles.source.x - floor_height;
-    let max_right = tiles.source.x + floor_height;
+fn add_floor(tiles: &mut Tiles) {
+    let floor_height = tiles.tiles.bounds().unwrap().max.y + 2;
+    // Wide enough for the whole pile, which spreads one either way for each row it falls
+    let half_width = floor_height - tiles.source.y;
+    let max_left = tiles.source.x - half_width;
+    let max_right = tiles.source.x + half_width;
     tiles.draw_line(Point::new(max_left, floor_height), Point::new(max_right, floor_height), Tile::Stone);
+}
+
 
+fn pour_sand(tiles: &mut Tiles) -> u32 {
     let mut sand_count: u32 = 0;
     let mut flow: bool = true;
     while flow {
         let status = tiles.drop();
         flow = status == DropStatus::Success;
         sand_count += (status != DropStatus::Void) as u32;
+        recorder::frame(tiles);
     }
-    println!("Sand dropped before stopping: {}", sand_count);
-} 
+    recorder::last_frame(tiles);
+    return sand_count;
+}
@@ -0,0 +1,91 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+
+#[path = "14_part1.rs"]
+pub mod part1;
+#[path = "14_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+pub fn picture(part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return match part {
+        Part::One => part1::picture(input, params),
+        Part::Two => part2::picture(input, params),
+    };
+}
+
+pub fn simulation(part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return match part {
+        Part::One => part1::simulation(input, params),
+        Part::Two => part2::simulation(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::common::ParseError;
+    use crate::common::render::{Render, RenderOptions};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/14.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(24)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(93)));
+    }
+
+    #[test]
+    fn no_rock() {
+        for input in ["", "\n", "498,4\n"] {
+            for part in [Part::One, Part::Two] {
+                let error = ParseError::whole_input("no rock", "at least one path of rock");
+                assert_eq!(solve(part, input), Err(SolveError::Parse(error)), "{:?} part {}", input, part);
+            }
+        }
+    }
+
+    #[test]
+    fn part1_picture() {
+        let picture = picture(Part::One, EXAMPLE, &Params::new()).unwrap();
+        // The picture in the puzzle text once the 24 units have come to rest
+        let expected = "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+";
+        assert_eq!(picture.render_to_string(&RenderOptions::default()), expected);
+    }
+}
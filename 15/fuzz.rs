@@ -0,0 +1,138 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::random::Rng;
+
+use super::{part1, part2};
+
+type Point = (i64, i64);
+
+const MAX_ATTEMPTS: usize = 1000;
+
+// Covers a small square with sensors everywhere except one gap. Each sensor goes on the far side
+// of a point that isn't covered yet, so its diamond reaches that point without reaching the gap.
+pub fn generate(rng: &mut Rng) -> (String, Params) {
+    loop {
+        if let Some((input, params)) = try_generate(rng) {
+            return (input, params);
+        }
+    }
+}
+
+fn try_generate(rng: &mut Rng) -> Option<(String, Params)> {
+    let max_coord = rng.range(10, 30);
+    let gap: Point = (rng.range(0, max_coord), rng.range(0, max_coord));
+    let mut readings: Vec<(Point, Point)> = Vec::new();
+    loop {
+        let uncovered: Vec<Point> = get_square(max_coord).filter(|&point| (point != gap) && !is_covered(&readings, point)).collect();
+        if uncovered.is_empty() {
+            break;
+        }
+        let point = *rng.choose(&uncovered);
+        let reading = (0..MAX_ATTEMPTS).map(|_| get_reading(rng, point, gap, max_coord)).find(|&reading| is_consistent(&readings, reading))?;
+        readings.push(reading);
+    }
+    rng.shuffle(&mut readings);
+
+    let mut input = String::new();
+    for (sensor, beacon) in readings {
+        input += &format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", sensor.0, sensor.1, beacon.0, beacon.1);
+    }
+    let mut params = Params::new();
+    params.insert("row", &rng.range(-3, max_coord + 3).to_string());
+    params.insert("max_coord", &max_coord.to_string());
+    return Some((input, params));
+}
+
+// Moving away from the gap keeps the sensor's distance to the gap the sum of its distance to the
+// point and the point's distance to the gap, so any radius in between covers one and not the other
+fn get_reading(rng: &mut Rng, point: Point, gap: Point, max_coord: i64) -> (Point, Point) {
+    let away = |from: i64, to: i64, rng: &mut Rng| if from == to {*rng.choose(&[-1, 1])} else {(to - from).signum()};
+    let steps = rng.range(1, max_coord / 2);
+    let steps_x = rng.range(0, steps);
+    let sensor = (point.0 + away(gap.0, point.0, rng) * steps_x, point.1 + away(gap.1, point.1, rng) * (steps - steps_x));
+    let radius = rng.range(get_distance(sensor, point), get_distance(sensor, gap) - 1);
+    let beacon_x = rng.range(-radius, radius);
+    let beacon_y = *rng.choose(&[-1, 1]) * (radius - beacon_x.abs());
+    return (sensor, (sensor.0 + beacon_x, sensor.1 + beacon_y));
+}
+
+// Every sensor's beacon has to be the one closest to it, with no ties
+fn is_consistent(readings: &[(Point, Point)], new: (Point, Point)) -> bool {
+    let radius = get_distance(new.0, new.1);
+    return readings.iter().all(|&(sensor, beacon)| {
+        return (beacon == new.1) || ((get_distance(new.0, beacon) > radius) && (get_distance(sensor, new.1) > get_distance(sensor, beacon)));
+    });
+}
+
+fn get_square(max_coord: i64) -> impl Iterator<Item = Point> {
+    return (0..=max_coord).flat_map(move |y| (0..=max_coord).map(move |x| (x, y)));
+}
+
+fn get_distance(a: Point, b: Point) -> i64 {
+    return (a.0 - b.0).abs() + (a.1 - b.1).abs();
+}
+
+fn is_covered(readings: &[(Point, Point)], point: Point) -> bool {
+    return readings.iter().any(|&(sensor, beacon)| get_distance(sensor, point) <= get_distance(sensor, beacon));
+}
+
+// Checks every position in the row, or every position in the square, one at a time
+pub fn brute_force(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let readings = read_readings(input)?;
+    if part == Part::One {
+        let row: i64 = params.value(&part1::ROW)? as i64;
+        let reach = readings.iter().map(|&(sensor, beacon)| get_distance(sensor, beacon)).max().unwrap();
+        let min_x = readings.iter().map(|&(sensor, _)| sensor.0).min().unwrap() - reach;
+        let max_x = readings.iter().map(|&(sensor, _)| sensor.0).max().unwrap() + reach;
+        let count = (min_x..=max_x).filter(|&x| {
+            return is_covered(&readings, (x, row)) && readings.iter().all(|&(_, beacon)| beacon != (x, row));
+        }).count();
+        return Ok(Answer::from(count));
+    }
+    let max_coord: i64 = params.value(&part2::MAX_COORD)? as i64;
+    let gaps: Vec<Point> = get_square(max_coord).filter(|&point| !is_covered(&readings, point)).collect();
+    return match gaps[..] {
+        [(x, y)] => Ok(Answer::from(4000000 * x + y)),
+        [] => Err(SolveError::NoSolution("No potential positions for the beacon found".to_string())),
+        _ => Err(SolveError::NoSolution("Too many possibilities for the beacon".to_string())),
+    };
+}
+
+fn read_readings(input: &str) -> Result<Vec<(Point, Point)>, SolveError> {
+    let mut readings: Vec<(Point, Point)> = Vec::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let numbers: Vec<i64> = line.split(|c: char| !(c.is_ascii_digit() || c == '-')).filter_map(|word| word.parse::<i64>().ok()).collect();
+        if numbers.len() != 4 {
+            return Err(SolveError::BadInput(format!("'{}' should have a sensor and a beacon position", line)));
+        }
+        readings.push(((numbers[0], numbers[1]), (numbers[2], numbers[3])));
+    }
+    if readings.is_empty() {
+        return Err(SolveError::BadInput("there are no sensors".to_string()));
+    }
+    return Ok(readings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brute_force_example() {
+        let example = include_str!("../examples/15.txt");
+        let mut params = Params::new();
+        params.insert("row", "10");
+        params.insert("max_coord", "20");
+        assert_eq!(brute_force(Part::One, example, &params), Ok(Answer::from(26)));
+        assert_eq!(brute_force(Part::Two, example, &params), Ok(Answer::from(56000011)));
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for seed in 0..10 {
+            let (input, params) = generate(&mut Rng::new(seed));
+            for part in [Part::One, Part::Two] {
+                assert_eq!(super::super::solve_with_params(part, &input, &params), brute_force(part, &input, &params), "seed {} part {}", seed, part);
+            }
+        }
+    }
+}
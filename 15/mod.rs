@@ -0,0 +1,49 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "15_part1.rs"]
+pub mod part1;
+#[path = "15_part2.rs"]
+pub mod part2;
+
+pub mod fuzz;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/15.txt");
+
+    #[test]
+    fn part1_example() {
+        let mut params = Params::new();
+        params.insert("row", "10");
+        assert_eq!(solve_with_params(Part::One, EXAMPLE, &params), Ok(Answer::from(26)));
+    }
+
+    #[test]
+    fn part2_example() {
+        let mut params = Params::new();
+        params.insert("max_coord", "20");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(56000011)));
+    }
+}
@@ -1,9 +1,20 @@
-use std::env;
-use std::fs;
-use std::collections::{HashMap, VecDeque, HashSet};
+#![allow(clippy::needless_borrow, clippy::ptr_arg)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::search::bfs;
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::progress;
+use crate::log_debug;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
-const MAX_TIME: u32 = 30;
+pub const MAX_TIME: Param<u32> = Param::new("max_time", "30", "how many minutes there are before the volcano erupts");
+pub const PARAMS: &[&ParamInfo] = &[&MAX_TIME.info];
+
+// The tunnels out of each valve, and the flow rate of every valve worth opening
+type Valves = (HashMap<String, Vec<String>>, HashMap<String, u32>);
 
 #[derive(Debug)]
 struct Path {
@@ -12,16 +23,18 @@ struct Path {
     current_score: u32,
     pressure: u32,
     path: Vec<String>,
+    max_time: u32,
 }
 
 impl Path {
-    fn new(node: &String, time_elapsed: u32, current_score: u32, pressure: u32, path: &Vec<String>) -> Path {
+    fn new(node: &String, time_elapsed: u32, current_score: u32, pressure: u32, path: &Vec<String>, max_time: u32) -> Path {
         return Path {
             current_node: node.to_string(),
             time_elapsed: time_elapsed,
             current_score: current_score,
             pressure: pressure,
             path: path.iter().map(|x| x.to_string()).collect(),
+            max_time: max_time,
         }
     }
     
@@ -31,7 +44,7 @@ impl Path {
 
     fn get_copy(&self) -> Path {
         let path_copy: Vec<String> = self.path.iter().map(|x| x.to_string()).collect();
-        return Path::new(&self.current_node, self.time_elapsed, self.current_score, self.pressure, &path_copy);
+        return Path::new(&self.current_node, self.time_elapsed, self.current_score, self.pressure, &path_copy, self.max_time);
     }
     
     fn get_updated_path(&self, new_node: &String, valve_pressure: u32, distance: u32) -> Path {
@@ -43,6 +56,7 @@ impl Path {
             self.current_score + (distance * self.pressure), 
             self.pressure + valve_pressure,
             &new_path,
+            self.max_time,
         )
     }
 
@@ -51,73 +65,73 @@ impl Path {
     }
 
     fn score_at_time(&self) -> u32 {
-        let time_left: u32 = MAX_TIME - self.time_elapsed;
+        let time_left: u32 = self.max_time - self.time_elapsed;
         return self.current_score + (time_left * self.pressure);
     }
 }
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-
-    let (vertices, valves) = read_input_to_hashmaps(input);
-    println!("Input read in:");
-    println!("Vertices: {:?}", vertices);
-    println!("Valves: {:?}\n", valves);
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let max_time: u32 = params.value(&MAX_TIME)?;
+    let (vertices, valves) = read_input_to_hashmaps(input)?;
+    log_debug!("Vertices: {:?}", vertices);
+    log_debug!("Valves: {:?}", valves);
 
-    let best_path: Path = get_best_score(vertices, valves);
-    println!("Winning path: {:?}", best_path);
-    println!("Most pressure released: {}", best_path.score_at_time());
+    let best_path: Path = get_best_score(vertices, valves, max_time);
+    log::detail("winning_path", best_path.path.clone());
+    if progress::is_cancelled() {
+        return Err(SolveError::Interrupted {best: Some(Answer::from(best_path.score_at_time()))});
+    }
+    return Ok(Answer::from(best_path.score_at_time()));
 } 
 
-fn read_input_to_hashmaps(input: String) -> (HashMap<String, Vec<String>>, HashMap<String, u32>) {
+fn read_input_to_hashmaps(input: &str) -> Result<Valves, ParseError> {
     let mut vertices: HashMap<String, Vec<String>> = HashMap::new();
     let mut valves: HashMap<String, u32> = HashMap::new();
-    for line in input.trim().lines().collect::<Vec<&str>>() {
-        let this_node: String = line.split(" ").nth(1).unwrap().trim().to_string();
-        let flow_rate: u32 = {
-            line.split(";").nth(0).unwrap()
-            .split(" ").nth(4).unwrap().trim().to_string()
-            .split("=").nth(1).unwrap().to_string()
-            .parse::<u32>().unwrap()
-        };
-        let edges_part: String = line.split("; ").nth(1).unwrap().to_string();
-        let edge_nodes: Vec<String>;
-        if edges_part.contains(",") {
-            edge_nodes = {
-                edges_part.split(" valves ").nth(1).unwrap()
-                .split(", ").map(|x| x.to_string()).collect()
-            };
-        }
-        else {
-            edge_nodes = {
-                edges_part.split(" valve ").nth(1).unwrap()
-                .split(", ").map(|x| x.to_string()).collect()
-            };
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            continue;
         }
+        line.after("Valve ")?;
+        let this_node: String = line.word(1, "a valve name")?.to_string();
+        let (valve_part, edges_part) = line.split_once("; ")?;
+        let (_, rate_str) = valve_part.split_once("rate=")
+            .ok_or(line.error(valve_part.trim(), "'Valve XX has flow rate=N'"))?;
+        let flow_rate: u32 = line.parse(rate_str, "a flow rate")?;
+        let (_, edges_str) = edges_part.split_once(" valves ").or(edges_part.split_once(" valve "))
+            .ok_or(line.error(edges_part, "'tunnels lead to valves XX, YY'"))?;
+        let edge_nodes: Vec<String> = edges_str.split(", ").map(|x| x.trim().to_string()).collect();
 
         vertices.insert(this_node.to_string(), edge_nodes);
         if flow_rate > 0 {
             valves.insert(this_node, flow_rate);
         }
     }
-    return (vertices, valves);
+    if !vertices.contains_key("AA") {
+        return Err(ParseError::whole_input("no valve AA", "the starting valve AA"));
+    }
+    for (node, edge_nodes) in &vertices {
+        for edge_node in edge_nodes {
+            if !vertices.contains_key(edge_node) {
+                let found = format!("a tunnel from {} to {}", node, edge_node);
+                return Err(ParseError::whole_input(&found, "tunnels only to valves in the input"));
+            }
+        }
+    }
+    return Ok((vertices, valves));
 }
 
-fn get_best_score(vertices: HashMap<String, Vec<String>>, valves: HashMap<String, u32>) -> Path {
+fn get_best_score(vertices: HashMap<String, Vec<String>>, valves: HashMap<String, u32>, max_time: u32) -> Path {
     let all_distances = get_distances_between_nodes(vertices);
     let new_distances = reduce_graph("AA".to_string(), all_distances, valves.keys().cloned().collect());
     
-    return dfs_for_best(new_distances, valves);
+    return dfs_for_best(new_distances, valves, max_time);
 }
 
 fn get_distances_between_nodes(vertices: HashMap<String, Vec<String>>) -> HashMap<(String, String), u32> {
     let mut shortest_paths: HashMap<(String, String), u32> = HashMap::new();
     for source in vertices.keys() {
-        let distances_from_node = dijkstra(source.to_string(), &vertices);
+        let distances_from_node = get_distances_from_node(source.to_string(), &vertices);
         for (target, distance) in distances_from_node {
             shortest_paths.insert((source.to_string(), target), distance);
         }
@@ -125,38 +139,10 @@ fn get_distances_between_nodes(vertices: HashMap<String, Vec<String>>) -> HashMa
     return shortest_paths;
 }
 
-fn dijkstra(source: String, vertices: &HashMap<String, Vec<String>>) -> HashMap<String, u32> {
-    let mut queue: VecDeque<String> = VecDeque::new();
-    let mut queued: HashSet<String> = HashSet::new();
-    let mut explored: HashSet<String> = HashSet::new();
-
-    let mut previous_node: HashMap<String, Option<String>> = HashMap::new();
-    let mut distances: HashMap<String, u32> = HashMap::new();
-    let mut current_node = source;
-    previous_node.insert((&current_node).to_string(), None);
-    loop {
-        let distance = match previous_node.get(&current_node).unwrap() {
-            Some(prev_node) => distances.get(&prev_node.to_string()).unwrap() + 1,
-            None => 0,
-        };
-        distances.insert((&current_node).to_string(), distance);
-
-        let next_nodes: Vec<String> = vertices.get(&current_node).unwrap().to_vec();
-        for node in next_nodes {
-            if !queued.contains(&node) && !explored.contains(&node) {
-                previous_node.insert((&node).to_string(), Some((&current_node).to_string()));
-                queued.insert((&node).to_string());
-                queue.push_back(node);
-            }
-        }
-        explored.insert(current_node);
-
-        if explored.len() == vertices.keys().len() {
-            break;
-        }
-        current_node = queue.pop_front().unwrap();
-    }
-    return distances;
+// Every tunnel takes a minute, so a breadth first search gives the distances
+fn get_distances_from_node(source: String, vertices: &HashMap<String, Vec<String>>) -> HashMap<String, u32> {
+    let result = bfs([source], |node| vertices.get(node).unwrap().to_vec(), |_| false);
+    return result.distances.into_iter().map(|(node, distance)| (node, distance as u32)).collect();
 }
 
 fn reduce_graph(source: String, distances: HashMap<(String, String), u32>, mut valves: Vec<String>) -> HashMap<(String, String), u32> {
@@ -165,26 +151,30 @@ fn reduce_graph(source: String, distances: HashMap<(String, String), u32>, mut v
     for node1 in &valves {
         for node2 in &valves {
             if node1 == node2 {continue;}
-            // Adding one because this is the "distance" to turn on a valve
-            edge_distances.insert((node1.to_string(), node2.to_string()), distances.get(&(node1.to_string(), node2.to_string())).unwrap() + 1);
+            // Adding one because this is the "distance" to turn on a valve, and one that can't be
+            // reached is too far away to ever get to in time
+            let distance = distances.get(&(node1.to_string(), node2.to_string())).map(|distance| distance + 1);
+            edge_distances.insert((node1.to_string(), node2.to_string()), distance.unwrap_or(u32::MAX));
         }
     }
     return edge_distances;
 }
 
-fn dfs_for_best(edge_weights: HashMap<(String, String), u32>, valves: HashMap<String, u32>) -> Path {
+fn dfs_for_best(edge_weights: HashMap<(String, String), u32>, valves: HashMap<String, u32>, max_time: u32) -> Path {
     let start = "AA".to_string();
     let mut visited = HashSet::new();
     visited.insert((&start).to_string());
-    let path = Path::new(&start, 0, 0, 0, &vec!["AA".to_string()]);
+    let path = Path::new(&start, 0, 0, 0, &vec!["AA".to_string()], max_time);
     return dfs(path, &edge_weights, &valves);
 }
 
 fn dfs(current_path: Path, edge_weights: &HashMap<(String, String), u32>, valves: &HashMap<String, u32>) -> Path {
     let current_node = current_path.get_current_node();
     let mut best_path: Path = current_path.get_copy();
-    let time_left: u32 = MAX_TIME - best_path.time_elapsed;
+    let time_left: u32 = best_path.max_time - best_path.time_elapsed;
     for node in valves.keys() {
+        // Stopping early still leaves the best path found so far
+        if progress::is_cancelled() {break;}
         if current_path.nodes_visited().contains(node) {continue;}
         let distance = *edge_weights.get(&(current_node.to_string(), node.to_string())).unwrap();
         if distance > time_left {continue;}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::random::Rng;
+
+use super::{part1, part2};
+
+// A connected cave of up to nine valves. AA is always there with no flow, like in the real inputs,
+// and there's at least one valve worth opening.
+pub fn generate(rng: &mut Rng) -> (String, Params) {
+    let num_valves = rng.range(4, 9) as usize;
+    let mut names: Vec<String> = vec!["AA".to_string()];
+    while names.len() < num_valves {
+        let name: String = (0..2).map(|_| (b'A' + rng.below(26) as u8) as char).collect();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let rates: Vec<u32> = (0..num_valves).map(|index| {
+        return if (index == 0) || ((index > 1) && rng.chance(0.3)) {0} else {rng.range(1, 25) as u32};
+    }).collect();
+
+    // A random tree keeps everything connected, then a few more tunnels make loops
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); num_valves];
+    let mut connect = |a: usize, b: usize| {
+        if (a != b) && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for index in 1..num_valves {
+        connect(index, rng.index(index));
+    }
+    for _ in 0..rng.range(0, num_valves as i64 / 2) {
+        connect(rng.index(num_valves), rng.index(num_valves));
+    }
+
+    let mut order: Vec<usize> = (0..num_valves).collect();
+    rng.shuffle(&mut order);
+    let mut input = String::new();
+    for index in order {
+        let mut neighbours: Vec<&str> = tunnels[index].iter().map(|&neighbour| names[neighbour].as_str()).collect();
+        rng.shuffle(&mut neighbours);
+        let description = if neighbours.len() == 1 {"tunnel leads to valve"} else {"tunnels lead to valves"};
+        input += &format!("Valve {} has flow rate={}; {} {}\n", names[index], rates[index], description, neighbours.join(", "));
+    }
+    return (input, Params::new());
+}
+
+struct Cave {
+    rates: Vec<u32>,
+    tunnels: Vec<Vec<usize>>,
+    start: usize,
+}
+
+impl Cave {
+    fn get_flow(&self, open: u32) -> u32 {
+        return (0..self.rates.len()).filter(|&index| open & (1 << index) != 0).map(|index| self.rates[index]).sum();
+    }
+
+    // Everything one person can do in a minute: open the valve they're at, or walk down a tunnel
+    fn get_moves(&self, position: usize, open: u32) -> Vec<(usize, u32)> {
+        let mut moves: Vec<(usize, u32)> = self.tunnels[position].iter().map(|&next| (next, 0)).collect();
+        if (self.rates[position] > 0) && (open & (1 << position) == 0) {
+            moves.push((position, 1 << position));
+        }
+        return moves;
+    }
+}
+
+// A minute-by-minute search over where everyone is and which valves are open, remembering the
+// best pressure from each state
+pub fn brute_force(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let cave = read_cave(input)?;
+    let mut memo: HashMap<(u32, usize, usize, u32), u32> = HashMap::new();
+    let best = match part {
+        Part::One => get_best_alone(&cave, params.value(&part1::MAX_TIME)?, cave.start, 0, &mut memo),
+        Part::Two => get_best_together(&cave, params.value(&part2::MAX_TIME)?, cave.start, cave.start, 0, &mut memo),
+    };
+    return Ok(Answer::from(best));
+}
+
+fn get_best_alone(cave: &Cave, minutes: u32, position: usize, open: u32, memo: &mut HashMap<(u32, usize, usize, u32), u32>) -> u32 {
+    if minutes == 0 {
+        return 0;
+    }
+    if let Some(&best) = memo.get(&(minutes, position, 0, open)) {
+        return best;
+    }
+    let flow = cave.get_flow(open);
+    let best = cave.get_moves(position, open).into_iter().map(|(next, opened)| {
+        return flow + get_best_alone(cave, minutes - 1, next, open | opened, memo);
+    }).max().unwrap_or(flow * minutes);
+    memo.insert((minutes, position, 0, open), best);
+    return best;
+}
+
+fn get_best_together(cave: &Cave, minutes: u32, first: usize, second: usize, open: u32, memo: &mut HashMap<(u32, usize, usize, u32), u32>) -> u32 {
+    if minutes == 0 {
+        return 0;
+    }
+    let (first, second) = (first.min(second), first.max(second));
+    if let Some(&best) = memo.get(&(minutes, first, second, open)) {
+        return best;
+    }
+    let flow = cave.get_flow(open);
+    let mut best = 0;
+    for (first_next, first_opened) in cave.get_moves(first, open) {
+        for (second_next, second_opened) in cave.get_moves(second, open) {
+            if (first_opened != 0) && (first_opened == second_opened) {
+                continue;
+            }
+            best = best.max(flow + get_best_together(cave, minutes - 1, first_next, second_next, open | first_opened | second_opened, memo));
+        }
+    }
+    memo.insert((minutes, first, second, open), best);
+    return best;
+}
+
+fn read_cave(input: &str) -> Result<Cave, SolveError> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rates: Vec<u32> = Vec::new();
+    let mut neighbour_names: Vec<Vec<String>> = Vec::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let bad_line = || SolveError::BadInput(format!("'{}' should describe a valve", line));
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if (words.len() < 10) || (words[0] != "Valve") {
+            return Err(bad_line());
+        }
+        names.push(words[1].to_string());
+        rates.push(words[4].trim_start_matches("rate=").trim_end_matches(';').parse::<u32>().map_err(|_| bad_line())?);
+        neighbour_names.push(words[9..].iter().map(|word| word.trim_end_matches(',').to_string()).collect());
+    }
+    if names.len() > 16 {
+        return Err(SolveError::BadInput("too many valves to try every way of opening them".to_string()));
+    }
+    let mut tunnels: Vec<Vec<usize>> = Vec::new();
+    for neighbours in neighbour_names {
+        let indices = neighbours.iter().map(|name| names.iter().position(|other| other == name)).collect::<Option<Vec<usize>>>();
+        tunnels.push(indices.ok_or(SolveError::BadInput("a tunnel leads to a valve that isn't in the input".to_string()))?);
+    }
+    let start = names.iter().position(|name| name == "AA").ok_or(SolveError::BadInput("there's no valve AA".to_string()))?;
+    return Ok(Cave {rates: rates, tunnels: tunnels, start: start});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brute_force_example() {
+        let example = include_str!("../examples/16.txt");
+        assert_eq!(brute_force(Part::One, example, &Params::new()), Ok(Answer::from(1651)));
+        assert_eq!(brute_force(Part::Two, example, &Params::new()), Ok(Answer::from(1707)));
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for seed in 0..10 {
+            let (input, params) = generate(&mut Rng::new(seed));
+            for part in [Part::One, Part::Two] {
+                assert_eq!(super::super::solve_with_params(part, &input, &params), brute_force(part, &input, &params), "seed {} part {}", seed, part);
+            }
+        }
+    }
+}
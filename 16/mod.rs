@@ -0,0 +1,70 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "16_part1.rs"]
+pub mod part1;
+#[path = "16_part2.rs"]
+pub mod part2;
+
+pub mod fuzz;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::common::progress::{self, CancelToken};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/16.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(1651)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(1707)));
+    }
+
+    #[test]
+    fn unreachable_valves_are_never_opened() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves CC\n\
+                     Valve BB has flow rate=10; tunnel leads to valve AA\n\
+                     Valve CC has flow rate=5; tunnel leads to valve AA\n";
+        assert_eq!(solve(Part::One, input), Ok(Answer::from(140)));
+        assert_eq!(solve(Part::Two, input), Ok(Answer::from(120)));
+    }
+
+    #[test]
+    fn cancelled_search_gives_best_so_far() {
+        // Cancelled before it starts, the only plan it has is to stay put
+        let token = CancelToken::new();
+        token.cancel();
+        let result = thread::spawn(move || {
+            progress::set_cancel_token(token);
+            return solve(Part::Two, EXAMPLE);
+        }).join().unwrap();
+        assert_eq!(result, Err(SolveError::Interrupted {best: Some(Answer::from(0))}));
+    }
+}
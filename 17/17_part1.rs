@@ -1,47 +1,28 @@
-use std::env;
-use std::fs;
-use std::collections::HashSet;
-use std::ops::Add;
-use std::io::stdout;
-use std::io::Write;
-
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Point {
-        return Point {x: x, y: y};
-    }
+#![allow(clippy::needless_borrow)]
 
-    #[allow(dead_code)]
-    fn scalar_mult(&self, scalar: i32) -> Point {
-        return Self::new(scalar * self.x, scalar * self.y);
-    }
-
-    #[allow(dead_code)]
-    fn distance(&self, other: &Point) -> u32 {
-        return ((self.x - other.x).abs() + (self.y - other.y).abs()) as u32;
-    }
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, ParseError};
+use crate::common::geometry::Point2;
+use crate::common::grid::{Bounds, Grid, SparseGrid};
+use crate::common::log::{self, Level};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::recorder;
+use crate::common::render::{stderr_supports_colour, Cell, Colour, Picture, Render, RenderOptions, ScreenPoint};
+use crate::common::simulation::Simulation;
+use crate::log_trace;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
-    #[allow(dead_code)]
-    fn vector_prod(&self, other: &Point) -> Point {
-        return Point::new(self.x * other.x, self.y * other.y);
-    }
-}
+type Point = Point2<i32>;
 
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y)
-    }
-}
+pub const NUM_ROCKS: Param<u64> = Param::new("num_rocks", "2022", "how many rocks fall");
+pub const WIDTH: Param<i32> = Param::new("width", "7", "how wide the chamber is");
+pub const PARAMS: &[&ParamInfo] = &[&NUM_ROCKS.info, &WIDTH.info];
 
+const RECORDING_ROWS: i32 = 40;
 
 struct ChamberRock {
-    resting_rocks: HashSet<Point>,
+    resting_rocks: SparseGrid<i32, ()>,
     highest_rock: i32,
     left_wall: i32,
     right_wall: i32,
@@ -51,12 +32,12 @@ struct ChamberRock {
 }
 
 impl ChamberRock {
-    fn new(jets: Vec<Point>) -> ChamberRock {
+    fn new(jets: Vec<Point>, width: i32) -> ChamberRock {
         return ChamberRock {
-            resting_rocks: HashSet::new(),
+            resting_rocks: SparseGrid::new(),
             highest_rock: 0,
             left_wall: 0,
-            right_wall: 8,
+            right_wall: width + 1,
             floor: 0,
             jets: jets,
             current_jet: 0,
@@ -116,7 +97,7 @@ impl ChamberRock {
 
     fn check_collision_with_rocks(&self, point: &Point, rock_shape: &HashSet<Point>) -> bool {
         let current_rock: HashSet<Point> = self.get_current_rock(point, rock_shape);
-        return current_rock.intersection(&self.resting_rocks).count() > 0;
+        return current_rock.iter().any(|point| self.resting_rocks.contains(point));
     }
 
     fn check_collision_with_chamber(&self, point: &Point, rock_shape: &HashSet<Point>) -> bool {
@@ -130,9 +111,9 @@ impl ChamberRock {
     fn set_rock(&mut self, point: &Point, rock_shape: &HashSet<Point>) {
         let current_rock: HashSet<Point> = self.get_current_rock(point, rock_shape);
         for rock in current_rock {
-            self.resting_rocks.insert(rock);
+            self.resting_rocks.set(rock, ());
         }
-        self.highest_rock = self.resting_rocks.iter().map(|rock| rock.y).max().unwrap();
+        self.highest_rock = self.resting_rocks.bounds().unwrap().max.y;
     }
 
     fn get_current_rock(&self, point: &Point, rock_shape: &HashSet<Point>) -> HashSet<Point> {
@@ -145,52 +126,191 @@ impl ChamberRock {
 
     #[allow(dead_code)]
     fn render_chamber(&self) {
-        let render_height = self.highest_rock + 5;
-        for i in 0..=render_height {
-            for j in self.left_wall..=self.right_wall {
-                if (i == render_height) & ((j == self.left_wall) | (j == self.right_wall)) {print!("{}", '+');}
-                else if i == render_height {print!("{}", '-');}
-                else if (j == self.left_wall) | (j == self.right_wall) {print!("{}", '|');}
-                else if self.resting_rocks.contains(&Point::new(j, render_height - i)) {print!("{}", '#');}
-                else {print!("{}", '.');}
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
+        if !log::is_enabled(Level::Trace) {
+            return;
+        }
+        let options = RenderOptions::default().with_colour(stderr_supports_colour());
+        log_trace!("\n{}", self.render_to_string(&options));
+    }
+}
+
+// The chamber grows upwards, so screen rows count down from a few rows above the highest rock to the floor
+impl Render for ChamberRock {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let top = Point2::new(self.left_wall as i64, -(self.highest_rock as i64 + 5));
+        let bottom = Point2::new(self.right_wall as i64, self.floor as i64);
+        return Some(Bounds::new(top, bottom));
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let (x, height) = (point.x as i32, -point.y as i32);
+        let is_wall = (x == self.left_wall) || (x == self.right_wall);
+        return match (height == self.floor, is_wall) {
+            (true, true) => Cell::new('+', Colour::Grey),
+            (true, false) => Cell::new('-', Colour::Grey),
+            (false, true) => Cell::new('|', Colour::Grey),
+            (false, false) if self.resting_rocks.contains(&Point::new(x, height)) => Cell::new('#', Colour::Cyan),
+            (false, false) => Cell::new('.', Colour::Grey),
+        };
+    }
+}
+
+
+// Recordings follow the top of the tower, so every frame is the same size however tall it gets
+struct TopOfChamber<'a> {
+    chamber: &'a ChamberRock,
+}
+
+impl Render for TopOfChamber<'_> {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let top_left = Point2::new(self.chamber.left_wall as i64, 0);
+        let bottom_right = Point2::new(self.chamber.right_wall as i64, RECORDING_ROWS as i64 - 1);
+        return Some(Bounds::new(top_left, bottom_right));
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let height = self.get_height(point);
+        if height < self.chamber.floor {
+            return Cell::plain(' ');
+        }
+        return self.chamber.cell_at(&Point2::new(point.x, -height as i64));
+    }
+}
+
+impl TopOfChamber<'_> {
+    fn get_height(&self, point: &ScreenPoint) -> i32 {
+        let top = (self.chamber.highest_rock + 4).max(self.chamber.floor + RECORDING_ROWS - 1);
+        return top - point.y as i32;
+    }
+}
+
+// One step is one rock falling until it comes to rest, with the top of the tower in view
+struct RockSimulation {
+    jets: Vec<Point>,
+    width: i32,
+    rocks: Vec<HashSet<Point>>,
+    chamber: ChamberRock,
+    num_rocks: u64,
+    num_dropped: u64,
+}
+
+impl Render for RockSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return TopOfChamber {chamber: &self.chamber}.render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return TopOfChamber {chamber: &self.chamber}.cell_at(point);
+    }
+}
+
+impl Simulation for RockSimulation {
+    fn step(&mut self) -> bool {
+        if self.num_dropped >= self.num_rocks {
+            return false;
         }
-        println!("");
+        self.chamber.rock_fall(&self.rocks[(self.num_dropped % 5) as usize]);
+        self.num_dropped += 1;
+        return true;
+    }
+
+    fn reset(&mut self) {
+        self.chamber = ChamberRock::new(self.jets.clone(), self.width);
+        self.num_dropped = 0;
+    }
+
+    fn status(&self) -> String {
+        return format!("Rock {} of {}: the tower is {} high and the next jet is number {}",
+            self.num_dropped, self.num_rocks, self.chamber.highest_rock, self.chamber.current_jet);
     }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let height = TopOfChamber {chamber: &self.chamber}.get_height(point);
+        let x = point.x as i32;
+        let is_wall = (x == self.chamber.left_wall) || (x == self.chamber.right_wall);
+        let what = match (height.cmp(&self.chamber.floor), is_wall) {
+            (Ordering::Less, _) => "below the floor",
+            (Ordering::Equal, _) => "the floor",
+            (Ordering::Greater, true) => "a wall",
+            (Ordering::Greater, false) if self.chamber.resting_rocks.contains(&Point::new(x, height)) => "rock",
+            (Ordering::Greater, false) => "air",
+        };
+        return format!("x {}, height {}: {}", x, height, what);
+    }
+}
+
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let num_rocks: u64 = params.value(&NUM_ROCKS)?;
+    let jets: Vec<Point> = read_jets(input)?;
+    let chamber: ChamberRock = drop_rocks(jets, get_width(params)?, num_rocks);
+    return Ok(Answer::from(chamber.highest_rock));
+}
+
+// The tower after `num_rocks` rocks, 2022 unless told otherwise
+pub fn picture(input: &str, params: &Params) -> Result<Picture, SolveError> {
+    let num_rocks: u64 = params.value(&NUM_ROCKS)?;
+    let jets: Vec<Point> = read_jets(input)?;
+    let chamber: ChamberRock = drop_rocks(jets, get_width(params)?, num_rocks);
+    return Ok(Picture::capture(&chamber));
 }
 
+// Steps through the same rocks as the picture
+pub fn simulation(input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let num_rocks: u64 = params.value(&NUM_ROCKS)?;
+    let width: i32 = get_width(params)?;
+    let jets: Vec<Point> = read_jets(input)?;
+    let chamber = ChamberRock::new(jets.clone(), width);
+    return Ok(Box::new(RockSimulation {jets: jets, width: width, rocks: get_rocks(), chamber: chamber, num_rocks: num_rocks, num_dropped: 0}));
+}
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    let jets: Vec<Point> = input.chars().map(|x| char_to_direction(x)).collect();
 
-    let rocks: Vec<HashSet<Point>> = vec![
+fn get_rocks() -> Vec<HashSet<Point>> {
+    return vec![
         HashSet::from([Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]),
         HashSet::from([Point::new(0, 1), Point::new(1, 1), Point::new(2, 1), Point::new(1, 0), Point::new(1, 2)]),
         HashSet::from([Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(2, 1), Point::new(2, 2)]),
         HashSet::from([Point::new(0, 0), Point::new(0, 1), Point::new(0, 2), Point::new(0, 3)]),
         HashSet::from([Point::new(0, 0), Point::new(0, 1), Point::new(1, 0), Point::new(1, 1)]),
     ];
+}
+
 
-    let mut chamber: ChamberRock = ChamberRock::new(jets);
-    for i in 0..2022 {
+fn drop_rocks(jets: Vec<Point>, width: i32, num_rocks: u64) -> ChamberRock {
+    let rocks: Vec<HashSet<Point>> = get_rocks();
+    let mut chamber: ChamberRock = ChamberRock::new(jets, width);
+    for i in 0..num_rocks {
         let this_rock_index = (i % 5) as usize;
         let this_rock = &rocks[this_rock_index];
         chamber.rock_fall(&this_rock);
+        recorder::frame(&TopOfChamber {chamber: &chamber});
+    }
+    recorder::last_frame(&TopOfChamber {chamber: &chamber});
+    return chamber;
+}
+
+// Rocks start two units from the left wall, so the widest one needs at least six
+fn get_width(params: &Params) -> Result<i32, SolveError> {
+    let width: i32 = params.value(&WIDTH)?;
+    if width < 6 {
+        return Err(SolveError::BadParam {name: WIDTH.info.name.to_string(), value: width.to_string()});
     }
+    return Ok(width);
+}
 
-    println!("Highest rock: {}", chamber.highest_rock);
-} 
+fn read_jets(input: &str) -> Result<Vec<Point>, ParseError> {
+    check_chars(input.trim(), |c| (c == '<') || (c == '>'), "a jet direction ('<' or '>')")?;
+    let jets: Vec<Point> = input.trim().chars().filter_map(char_to_direction).collect();
+    if jets.is_empty() {
+        return Err(ParseError::whole_input("no jets", "a line of '<' and '>'"));
+    }
+    return Ok(jets);
+}
 
-fn char_to_direction(direction_char: char) -> Point {
+fn char_to_direction(direction_char: char) -> Option<Point> {
     return match direction_char {
-        '<' => Point::new(-1, 0),
-        '>' => Point::new(1, 0),
-        c => panic!("Invalid character found for direction: '{}'", c),
+        '<' => Some(Point::new(-1, 0)),
+        '>' => Some(Point::new(1, 0)),
+        _ => None,
     };
 }
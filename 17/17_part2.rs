@@ -1,46 +1,25 @@
-use std::env;
-use std::fs;
+#![allow(clippy::needless_borrow, clippy::needless_late_init)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, ParseError};
+use crate::common::geometry::Point2;
+use crate::common::grid::{Bounds, Grid, SparseGrid};
+use crate::common::log::{self, Level};
+use crate::common::params::{Param, ParamInfo};
+use crate::common::recorder;
+use crate::common::render::{stderr_supports_colour, Cell, Colour, Render, RenderOptions, ScreenPoint};
+use crate::log_trace;
 use std::collections::{HashSet, HashMap};
-use std::ops::Add;
-use std::io::stdout;
-use std::io::Write;
-
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
 
-impl Point {
-    fn new(x: i32, y: i32) -> Point {
-        return Point {x: x, y: y};
-    }
+type Point = Point2<i32>;
 
-    #[allow(dead_code)]
-    fn scalar_mult(&self, scalar: i32) -> Point {
-        return Self::new(scalar * self.x, scalar * self.y);
-    }
-
-    #[allow(dead_code)]
-    fn distance(&self, other: &Point) -> u32 {
-        return ((self.x - other.x).abs() + (self.y - other.y).abs()) as u32;
-    }
-
-    #[allow(dead_code)]
-    fn vector_prod(&self, other: &Point) -> Point {
-        return Point::new(self.x * other.x, self.y * other.y);
-    }
-}
-
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y)
-    }
-}
+pub const NUM_ROCKS: Param<u64> = Param::new("num_rocks", "1000000000000", "how many rocks fall");
+pub const WIDTH: Param<i32> = Param::new("width", "7", "how wide the chamber is");
+pub const PARAMS: &[&ParamInfo] = &[&NUM_ROCKS.info, &WIDTH.info];
+const RECORDING_ROWS: i32 = 40;
 
 struct ChamberRock {
-    resting_rocks: HashSet<Point>,
+    resting_rocks: SparseGrid<i32, ()>,
     highest_rock: i32,
     left_wall: i32,
     right_wall: i32,
@@ -50,12 +29,12 @@ struct ChamberRock {
 }
 
 impl ChamberRock {
-    fn new(jets: Vec<Point>) -> ChamberRock {
+    fn new(jets: Vec<Point>, width: i32) -> ChamberRock {
         return ChamberRock {
-            resting_rocks: HashSet::new(),
+            resting_rocks: SparseGrid::new(),
             highest_rock: 0,
             left_wall: 0,
-            right_wall: 8,
+            right_wall: width + 1,
             floor: 0,
             jets: jets,
             current_jet: 0,
@@ -115,7 +94,7 @@ impl ChamberRock {
 
     fn check_collision_with_rocks(&self, point: &Point, rock_shape: &HashSet<Point>) -> bool {
         let current_rock: HashSet<Point> = self.get_current_rock(point, rock_shape);
-        return current_rock.intersection(&self.resting_rocks).count() > 0;
+        return current_rock.iter().any(|point| self.resting_rocks.contains(point));
     }
 
     fn check_collision_with_chamber(&self, point: &Point, rock_shape: &HashSet<Point>) -> bool {
@@ -129,9 +108,9 @@ impl ChamberRock {
     fn set_rock(&mut self, point: &Point, rock_shape: &HashSet<Point>) {
         let current_rock: HashSet<Point> = self.get_current_rock(point, rock_shape);
         for rock in current_rock {
-            self.resting_rocks.insert(rock);
+            self.resting_rocks.set(rock, ());
         }
-        self.highest_rock = self.resting_rocks.iter().map(|rock| rock.y).max().unwrap();
+        self.highest_rock = self.resting_rocks.bounds().unwrap().max.y;
     }
 
     fn get_current_rock(&self, point: &Point, rock_shape: &HashSet<Point>) -> HashSet<Point> {
@@ -144,25 +123,16 @@ impl ChamberRock {
 
     #[allow(dead_code)]
     fn render_chamber(&self) {
-        let render_height = self.highest_rock + 5;
-        for i in 0..=render_height {
-            for j in self.left_wall..=self.right_wall {
-                if (i == render_height) & ((j == self.left_wall) | (j == self.right_wall)) {print!("{}", '+');}
-                else if i == render_height {print!("{}", '-');}
-                else if (j == self.left_wall) | (j == self.right_wall) {print!("{}", '|');}
-                else if self.resting_rocks.contains(&Point::new(j, render_height - i)) {print!("{}", '#');}
-                else {print!("{}", '.');}
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-            if i > 600 {break;}
+        if !log::is_enabled(Level::Trace) {
+            return;
         }
-        println!("");
+        let options = RenderOptions::default().with_colour(stderr_supports_colour());
+        log_trace!("\n{}", self.render_to_string(&options));
     }
 
     fn get_top_row_of_rock(&self) -> String {
         let mut row: String = "".to_string();
-        for i in 0..7 {
+        for i in 0..(self.right_wall - self.left_wall - 1) {
             row.push(
                 match self.resting_rocks.contains(&Point::new(i+1, self.highest_rock)) {
                     true => '1',
@@ -174,24 +144,74 @@ impl ChamberRock {
     }
 }
 
+// The chamber grows upwards, so screen rows count down from a few rows above the highest rock to the floor
+impl Render for ChamberRock {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let top = Point2::new(self.left_wall as i64, -(self.highest_rock as i64 + 5));
+        let bottom = Point2::new(self.right_wall as i64, self.floor as i64);
+        return Some(Bounds::new(top, bottom));
+    }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let num_rocks: u64 = env_args[2].parse().expect("Should have been able to parse to int");
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    let jets: Vec<Point> = input.chars().map(|x| char_to_direction(x)).collect();
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let (x, height) = (point.x as i32, -point.y as i32);
+        let is_wall = (x == self.left_wall) || (x == self.right_wall);
+        return match (height == self.floor, is_wall) {
+            (true, true) => Cell::new('+', Colour::Grey),
+            (true, false) => Cell::new('-', Colour::Grey),
+            (false, true) => Cell::new('|', Colour::Grey),
+            (false, false) if self.resting_rocks.contains(&Point::new(x, height)) => Cell::new('#', Colour::Cyan),
+            (false, false) => Cell::new('.', Colour::Grey),
+        };
+    }
+}
 
-    let rocks: Vec<HashSet<Point>> = vec![
+
+// Recordings follow the top of the tower, so every frame is the same size however tall it gets
+struct TopOfChamber<'a> {
+    chamber: &'a ChamberRock,
+}
+
+impl Render for TopOfChamber<'_> {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let top_left = Point2::new(self.chamber.left_wall as i64, 0);
+        let bottom_right = Point2::new(self.chamber.right_wall as i64, RECORDING_ROWS as i64 - 1);
+        return Some(Bounds::new(top_left, bottom_right));
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let top = (self.chamber.highest_rock + 4).max(self.chamber.floor + RECORDING_ROWS - 1);
+        let height = top - point.y as i32;
+        if height < self.chamber.floor {
+            return Cell::plain(' ');
+        }
+        return self.chamber.cell_at(&Point2::new(point.x, -height as i64));
+    }
+}
+
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let num_rocks: u64 = params.value(&NUM_ROCKS)?;
+    let width: i32 = get_width(params)?;
+    let jets: Vec<Point> = read_jets(input)?;
+    return Ok(Answer::from(get_height_after_rocks(jets, width, num_rocks)));
+}
+
+
+fn get_rocks() -> Vec<HashSet<Point>> {
+    return vec![
         HashSet::from([Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]),
         HashSet::from([Point::new(0, 1), Point::new(1, 1), Point::new(2, 1), Point::new(1, 0), Point::new(1, 2)]),
         HashSet::from([Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(2, 1), Point::new(2, 2)]),
         HashSet::from([Point::new(0, 0), Point::new(0, 1), Point::new(0, 2), Point::new(0, 3)]),
         HashSet::from([Point::new(0, 0), Point::new(0, 1), Point::new(1, 0), Point::new(1, 1)]),
     ];
+}
+
 
-    let mut chamber: ChamberRock = ChamberRock::new(jets);
+// Drops rocks until the chamber state repeats, then extrapolates the height from the cycle
+fn get_height_after_rocks(jets: Vec<Point>, width: i32, num_rocks: u64) -> u64 {
+    let rocks: Vec<HashSet<Point>> = get_rocks();
+    let mut chamber: ChamberRock = ChamberRock::new(jets, width);
 
     let mut states: HashMap<(usize, usize, String), u64> = HashMap::new();
     let mut heights: HashMap<u64, u64> = HashMap::new();
@@ -214,9 +234,11 @@ fn main() {
             states.insert(this_state, i);
         }
         chamber.rock_fall(&this_rock);
+        recorder::frame(&TopOfChamber {chamber: &chamber});
     }
+    recorder::last_frame(&TopOfChamber {chamber: &chamber});
     if !cyclic_flag {
-        println!("Highest rock: {}", chamber.highest_rock);
+        return chamber.highest_rock as u64;
     }
     else {
         let original_index: u64 = *states.get(&repeat_state).unwrap();
@@ -236,15 +258,34 @@ fn main() {
             answer = last_non_cyclic_height + num_times*cycle_height_increase + modulo_height_increase;
         }
 
-        println!("{}, {}, {:?}", original_index, first_repeat, repeat_state);
-        println!("Highest rock: {}", answer);
+        log::detail("cycle_start", original_index);
+        log::detail("cycle_length", cycle_length);
+        return answer;
+    }
+}
+
+// Rocks start two units from the left wall, so the widest one needs at least six
+fn get_width(params: &Params) -> Result<i32, SolveError> {
+    let width: i32 = params.value(&WIDTH)?;
+    if width < 6 {
+        return Err(SolveError::BadParam {name: WIDTH.info.name.to_string(), value: width.to_string()});
     }
-} 
+    return Ok(width);
+}
+
+fn read_jets(input: &str) -> Result<Vec<Point>, ParseError> {
+    check_chars(input.trim(), |c| (c == '<') || (c == '>'), "a jet direction ('<' or '>')")?;
+    let jets: Vec<Point> = input.trim().chars().filter_map(char_to_direction).collect();
+    if jets.is_empty() {
+        return Err(ParseError::whole_input("no jets", "a line of '<' and '>'"));
+    }
+    return Ok(jets);
+}
 
-fn char_to_direction(direction_char: char) -> Point {
+fn char_to_direction(direction_char: char) -> Option<Point> {
     return match direction_char {
-        '<' => Point::new(-1, 0),
-        '>' => Point::new(1, 0),
-        c => panic!("Invalid character found for direction: '{}'", c),
+        '<' => Some(Point::new(-1, 0)),
+        '>' => Some(Point::new(1, 0)),
+        _ => None,
     };
 }
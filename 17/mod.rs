@@ -0,0 +1,54 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+
+#[path = "17_part1.rs"]
+pub mod part1;
+#[path = "17_part2.rs"]
+pub mod part2;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+// Part two's trillion rocks won't fit in a picture, so both parts draw the part one tower
+pub fn picture(_part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return part1::picture(input, params);
+}
+
+pub fn simulation(_part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return part1::simulation(input, params);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/17.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(3068)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(1514285714288_i64)));
+    }
+}
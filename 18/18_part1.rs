@@ -1,64 +1,25 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::geometry::Point3;
 use std::collections::HashSet;
-use std::ops::Add;
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32, z: i32) -> Point {
-        return Point {x: x, y: y, z:z};
-    }
-
-    #[allow(dead_code)]
-    fn scalar_mult(&self, scalar: i32) -> Point {
-        return Self::new(scalar * self.x, scalar * self.y, scalar * self.z);
-    }
-
-    #[allow(dead_code)]
-    fn distance(&self, other: &Point) -> u32 {
-        return ((self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()) as u32;
-    }
+type Point = Point3<i32>;
 
-    #[allow(dead_code)]
-    fn vector_prod(&self, other: &Point) -> Point {
-        return Point::new(self.x * other.x, self.y * other.y, self.z * other.z);
-    }
-}
-
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
-    }
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    return Ok(Answer::from(get_surface_area(input)?));
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-
-    let directions: Vec<Point> = vec![
-        Point::new(0, 0, 1),
-        Point::new(0, 0, -1),
-        Point::new(0, 1, 0),
-        Point::new(0, -1, 0),
-        Point::new(1, 0, 0),
-        Point::new(-1, 0, 0),
-    ];
 
+fn get_surface_area(input: &str) -> Result<i32, ParseError> {
+    let directions: Vec<Point> = Point::unit_vectors().to_vec();
     let mut cubes: HashSet<Point> = HashSet::new();
     let mut num_cubes: i32 = 0;
     let mut overlapping_surfaces: i32 = 0;
-    for line in input.trim().lines().collect::<Vec<&str>>() {
-        let coords: Vec<i32> = line.trim().split(",").map(|x| x.parse::<i32>().unwrap()).collect();
-        let this_cube: Point = Point::new(coords[0], coords[1], coords[2]);
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let this_cube: Point = line.parse::<Point>(line.text, "a cube like '2,2,2'")?;
 
         for direction in &directions {
             let potential_cube = this_cube + *direction;
@@ -67,5 +28,5 @@ fn main() {
         cubes.insert(this_cube);
         num_cubes += 1;
     }
-    println!("Surface area: {}", (6 * num_cubes) - overlapping_surfaces);
-} 
+    return Ok((6 * num_cubes) - overlapping_surfaces);
+}
@@ -1,43 +1,15 @@
-use std::env;
-use std::fs;
-use std::collections::{VecDeque, HashSet};
-use std::ops::Add;
+#![allow(clippy::ptr_arg)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::search::flood_fill;
+use crate::common::geometry::Point3;
+use crate::common::log;
+use crate::common::progress;
+use std::collections::HashSet;
 use std::cmp::{min,max};
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32, z: i32) -> Point {
-        return Point {x: x, y: y, z:z};
-    }
-
-    #[allow(dead_code)]
-    fn scalar_mult(&self, scalar: i32) -> Point {
-        return Self::new(scalar * self.x, scalar * self.y, scalar * self.z);
-    }
-
-    #[allow(dead_code)]
-    fn distance(&self, other: &Point) -> u32 {
-        return ((self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()) as u32;
-    }
-
-    #[allow(dead_code)]
-    fn vector_prod(&self, other: &Point) -> Point {
-        return Point::new(self.x * other.x, self.y * other.y, self.z * other.z);
-    }
-}
-
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
-    }
-}
+type Point = Point3<i32>;
 
 #[derive(Debug)]
 struct BoundingBox {
@@ -175,30 +147,35 @@ impl BoundingBox {
     }
 }
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-
-    let directions: Vec<Point> = vec![
-        Point::new(0, 0, 1),
-        Point::new(0, 0, -1),
-        Point::new(0, 1, 0),
-        Point::new(0, -1, 0),
-        Point::new(1, 0, 0),
-        Point::new(-1, 0, 0),
-    ];
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let directions: Vec<Point> = Point::unit_vectors().to_vec();
+    let (cubes, mut bounding_box, lava_surface) = read_cubes(input, &directions)?;
+    log::detail("lava_surface", lava_surface);
+
+    bounding_box.expand();
+    let air_surface = get_air_surface_area(&cubes, &bounding_box, &directions);
+    // A flood fill that was cancelled part way only found some of the air
+    if progress::is_cancelled() {
+        return Err(SolveError::Interrupted {best: None});
+    }
+    log::detail("air_surface", air_surface);
+    log::detail("exterior_air_surface", bounding_box.exterior_surface());
+    return Ok(Answer::from(air_surface - bounding_box.exterior_surface()));
+}
+
 
+fn read_cubes(input: &str, directions: &Vec<Point>) -> Result<(HashSet<Point>, BoundingBox, i32), ParseError> {
     let mut cubes: HashSet<Point> = HashSet::new();
     let mut num_cubes: i32 = 0;
     let mut overlapping_surfaces: i32 = 0;
     let mut bounding_box: BoundingBox = BoundingBox::new();
-    for line in input.trim().lines().collect::<Vec<&str>>() {
-        let coords: Vec<i32> = line.trim().split(",").map(|x| x.parse::<i32>().unwrap()).collect();
-        let this_cube: Point = Point::new(coords[0], coords[1], coords[2]);
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let this_cube: Point = line.parse::<Point>(line.text, "a cube like '2,2,2'")?;
 
-        for direction in &directions {
+        for direction in directions {
             let potential_cube = this_cube + *direction;
             overlapping_surfaces += 2 * (cubes.contains(&potential_cube) as i32);
         }
@@ -206,41 +183,31 @@ fn main() {
         num_cubes += 1;
         bounding_box.update_bounds(&this_cube);
     }
-    println!("Full lava surface area: {}", (6 * num_cubes) - overlapping_surfaces);
+    if cubes.is_empty() {
+        return Err(ParseError::whole_input("no cubes", "at least one cube"));
+    }
+    return Ok((cubes, bounding_box, (6 * num_cubes) - overlapping_surfaces));
+}
 
-    let mut air: HashSet<Point> = HashSet::new();
-    bounding_box.expand();
-    let mut this_air: Point = Point::new(
+
+// Flood fills the air in the (expanded) bounding box, so the surface found is the air's surface
+fn get_air_surface_area(cubes: &HashSet<Point>, bounding_box: &BoundingBox, directions: &Vec<Point>) -> i32 {
+    let start: Point = Point::new(
         bounding_box.min_x.unwrap(), 
         bounding_box.min_y.unwrap(), 
         bounding_box.min_z.unwrap()
     );
-    let mut air_surface: i32 = 0;
-
-    let mut queue: VecDeque<Point> = VecDeque::new();
-    let mut queued: HashSet<Point> = HashSet::new();
-
-    loop {
-        let mut neighbouring_air: i32 = 0;
-        for direction in &directions {
-            let potential_air: Point = this_air + *direction;
-            if air.contains(&potential_air) {
-                neighbouring_air += 1;
-            }
-            else if !queued.contains(&potential_air) && bounding_box.in_bounds(&potential_air) && !cubes.contains(&potential_air) {
-                queued.insert(potential_air);
-                queue.push_back(potential_air);
-            }
-        }
-        air.insert(this_air);
-        air_surface += 6 - (2 * neighbouring_air);
+    let air: HashSet<Point> = flood_fill(start, |this_air| {
+        return directions.iter()
+            .map(|direction| *this_air + *direction)
+            .filter(|potential_air| bounding_box.in_bounds(potential_air) && !cubes.contains(potential_air))
+            .collect();
+    });
 
-        match queue.pop_front() {
-            Some(neighbour) => this_air = neighbour,
-            None => break,
-        };
+    let mut air_surface: i32 = 0;
+    for this_air in &air {
+        let neighbouring_air = directions.iter().filter(|direction| air.contains(&(*this_air + **direction))).count() as i32;
+        air_surface += 6 - neighbouring_air;
     }
-    println!("Full air surface area: {}", air_surface);
-    println!("Exterior air surface area: {}", bounding_box.exterior_surface());
-    println!("Interior air/exterior lava surface area: {}", air_surface - bounding_box.exterior_surface());
-} 
+    return air_surface;
+}
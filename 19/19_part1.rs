@@ -1,31 +1,37 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::progress::{self, Progress};
+use crate::log_debug;
 
-mod structs_and_enums;
-use structs_and_enums::Blueprint;
-mod blueprints_from_input;
-use blueprints_from_input::get_blueprints_from_input;
-mod get_best_value_from_blueprint;
-use get_best_value_from_blueprint::get_best_value_from_blueprint;
+use super::structs_and_enums::Blueprint;
+use super::blueprints_from_input::get_blueprints_from_input;
+use super::get_best_value_from_blueprint::get_best_value_from_blueprint;
 
+pub const TIME_LEFT: Param<u32> = Param::new("time_left", "24", "how many minutes there are to crack geodes");
+pub const PARAMS: &[&ParamInfo] = &[&TIME_LEFT.info];
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let time_left: u32 = env_args[2].parse().unwrap();
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    let blueprints: Vec<Blueprint> = get_blueprints_from_input(input);
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let time_left: u32 = params.value(&TIME_LEFT)?;
+    let blueprints: Vec<Blueprint> = get_blueprints_from_input(input)?;
 
     let mut total_quality: u32 = 0;
+    let mut qualities: Vec<u32> = Vec::new();
+    let mut bar = Progress::new("Blueprints", blueprints.len());
     for blueprint in blueprints {
         let bid: u32 = blueprint.get_index();
-        println!("Looking at blueprint number {}", bid);
         let best_for_blueprint = get_best_value_from_blueprint(blueprint, time_left);
-        println!("Best for blueprint {}: {}", bid, best_for_blueprint);
+        log_debug!("Best for blueprint {}: {}", bid, best_for_blueprint);
         let current_quality: u32 = bid * best_for_blueprint;
         total_quality += current_quality;
-        println!("Quality of blueprint {}: {}", bid, current_quality);
+        qualities.push(current_quality);
+        // The blueprints not looked at yet count for nothing, so the total so far is still a lower bound
+        if progress::is_cancelled() {
+            return Err(SolveError::Interrupted {best: Some(Answer::from(total_quality))});
+        }
+        bar.tick();
     }
-    println!("Total quality: {}", total_quality);
+    log::detail("quality_levels", qualities);
+    return Ok(Answer::from(total_quality));
 } 
@@ -1,32 +1,36 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::progress::{self, Progress};
 
-mod structs_and_enums;
-use structs_and_enums::Blueprint;
-mod blueprints_from_input;
-use blueprints_from_input::get_blueprints_from_input;
-mod get_best_value_from_blueprint;
-use get_best_value_from_blueprint::get_best_value_from_blueprint;
+use super::structs_and_enums::Blueprint;
+use super::blueprints_from_input::get_blueprints_from_input;
+use super::get_best_value_from_blueprint::get_best_value_from_blueprint;
 
+pub const TIME_LEFT: Param<u32> = Param::new("time_left", "32", "how many minutes there are to crack geodes");
+pub const BLUEPRINTS: Param<usize> = Param::new("blueprints", "3", "how many blueprints are left after the elephants ate the rest");
+pub const PARAMS: &[&ParamInfo] = &[&TIME_LEFT.info, &BLUEPRINTS.info];
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let time_left: u32 = env_args[2].parse().unwrap();
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    let blueprints: Vec<Blueprint> = get_blueprints_from_input(input);
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let time_left: u32 = params.value(&TIME_LEFT)?;
+    let blueprints: Vec<Blueprint> = get_blueprints_from_input(input)?;
 
     let mut product: u32 = 1;
-    for blueprint in blueprints {
-        let bid: u32 = blueprint.get_index();
-        println!("Looking at blueprint number {}", bid);
+    let mut best_values: Vec<u32> = Vec::new();
+    let num_blueprints = blueprints.len().min(params.value(&BLUEPRINTS)?);
+    let mut bar = Progress::new("Blueprints", num_blueprints);
+    for blueprint in blueprints.into_iter().take(num_blueprints) {
         let best_for_blueprint = get_best_value_from_blueprint(blueprint, time_left);
-        println!("Best for blueprint {}: {}", bid, best_for_blueprint);
         product *= best_for_blueprint;
-        if bid >= 3 {
-            break;
+        best_values.push(best_for_blueprint);
+        // A blueprint that hasn't been looked at could make anything, so there's only a best so far once they all have
+        if progress::is_cancelled() {
+            let best = if best_values.len() == num_blueprints {Some(Answer::from(product))} else {None};
+            return Err(SolveError::Interrupted {best: best});
         }
+        bar.tick();
     }
-    println!("Product of geode counts: {}", product);
+    log::detail("max_geodes", best_values);
+    return Ok(Answer::from(product));
 } 
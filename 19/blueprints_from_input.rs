@@ -1,33 +1,64 @@
 use std::collections::HashMap;
 
-use structs_and_enums::{Blueprint, ResourceType, ResourceTally};
+use crate::common::parse::{lines, Line, ParseError};
 
-pub fn get_blueprints_from_input(input: String) -> Vec<Blueprint> {
+use super::structs_and_enums::{Blueprint, ResourceType, ResourceTally};
+
+pub fn get_blueprints_from_input(input: &str) -> Result<Vec<Blueprint>, ParseError> {
     let mut blueprints: Vec<Blueprint> = Vec::new();
-    for line in input.trim().lines().collect::<Vec<&str>>() {
-        let parts: Vec<String> = line.trim().split(": ").map(|x| x.to_string()).collect();
-        let blueprint_index: u32 = parts[0].replace("Blueprint ", "").parse().unwrap();
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let (index_part, recipes_part) = line.split_once(": ")?;
+        let index_str = index_part.trim().strip_prefix("Blueprint ")
+            .ok_or(line.error(index_part.trim(), "'Blueprint N'"))?;
+        let blueprint_index: u32 = line.parse(index_str, "a blueprint number")?;
 
-        let robot_recipes_in_words: Vec<String> = parts[1].split(". ").map(|x| x.replace(".", "").to_string()).collect();
         let mut blueprint_hashmap: HashMap<ResourceType, ResourceTally> = HashMap::new();
-        for robot_recipe in &robot_recipes_in_words {
+        for robot_recipe in recipes_part.split('.').map(|x| x.trim()).filter(|x| !x.is_empty()) {
             let mut resource_tally: ResourceTally = ResourceTally::new();
-            let words: Vec<String> = robot_recipe.split_whitespace().map(|x| x.to_string()).collect();
-            let robot_type: ResourceType = ResourceType::from_string(&words[1]).unwrap();
-            let resource_amount: u32 = words[4].parse().unwrap();
-            let resource_type: ResourceType = ResourceType::from_string(&words[5]).unwrap();
-            
-            resource_tally.update_resource(resource_type, resource_amount);
-            if words.len() > 6 {
-                let resource_amount: u32 = words[7].parse().unwrap();
-                let resource_type: ResourceType = ResourceType::from_string(&words[8]).unwrap();
-                
+            let words: Vec<&str> = robot_recipe.split_whitespace().collect();
+            let robot_type: ResourceType = get_resource_type(&line, &words, 1, robot_recipe)?;
+
+            // Costs come as "costs 4 ore" optionally followed by "and 14 clay"
+            let mut word_index: usize = 4;
+            loop {
+                let amount_word = get_word(&line, &words, word_index, robot_recipe, "an amount")?;
+                let resource_amount: u32 = line.parse(amount_word, "an amount")?;
+                let resource_type: ResourceType = get_resource_type(&line, &words, word_index + 1, robot_recipe)?;
                 resource_tally.update_resource(resource_type, resource_amount);
+                if words.get(word_index + 2) != Some(&"and") {
+                    break;
+                }
+                word_index += 3;
             }
             blueprint_hashmap.insert(robot_type, resource_tally);
         }
+
+        for robot_type in ResourceType::resource_types() {
+            if !blueprint_hashmap.contains_key(&robot_type) {
+                let expected = format!("a recipe for the {} robot", robot_type.to_string());
+                return Err(line.error_at(line.text.chars().count() + 1, "end of line", &expected));
+            }
+        }
         let blueprint = Blueprint::new(blueprint_index, blueprint_hashmap);
         blueprints.push(blueprint);
     }
-    return blueprints;
-}
\ No newline at end of file
+    return Ok(blueprints);
+}
+
+
+fn get_word<'a>(line: &Line, words: &[&'a str], index: usize, recipe: &str, expected: &str) -> Result<&'a str, ParseError> {
+    return match words.get(index) {
+        Some(word) => Ok(word),
+        None => Err(line.error(recipe, &format!("{} in the recipe", expected))),
+    };
+}
+
+
+fn get_resource_type(line: &Line, words: &[&str], index: usize, recipe: &str) -> Result<ResourceType, ParseError> {
+    let expected = "a resource (ore, clay, obsidian or geode)";
+    let word = get_word(line, words, index, recipe, expected)?;
+    return ResourceType::from_string(&word.to_string()).map_err(|_| line.error(word, expected));
+}
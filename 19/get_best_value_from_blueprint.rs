@@ -1,8 +1,12 @@
+#![allow(clippy::needless_borrow)]
+
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use structs_and_enums::{Blueprint, ResourceType, ResourceTally, State};
+use crate::common::progress;
+
+use super::structs_and_enums::{Blueprint, ResourceType, ResourceTally, State};
 
 
 pub fn get_best_value_from_blueprint(blueprint: Blueprint, time_left: u32) -> u32 {
@@ -14,7 +18,11 @@ pub fn get_best_value_from_blueprint(blueprint: Blueprint, time_left: u32) -> u3
     return shareable_blueprint.as_ref().borrow().get_best_value_from_blueprint();
 }
 
+// Stops early if cancelled, leaving the best found so far in the blueprint
 fn dfs(state: State, blueprint: &mut Blueprint, previous_states: &mut HashMap<State, Option<State>>) {
+    if progress::is_cancelled() {
+        return;
+    }
     let new_states: Vec<State> = get_potential_states(&state, &blueprint);
     let shareable_blueprint: Rc<RefCell<&mut Blueprint>> = Rc::new(RefCell::new(blueprint));
     let current_best_geodes: u32 = shareable_blueprint.as_ref().borrow().get_best_value_from_blueprint();
@@ -1,3 +1,11 @@
+#![allow(
+    clippy::derived_hash_with_manual_eq,
+    clippy::for_kv_map,
+    clippy::inherent_to_string,
+    clippy::needless_borrow,
+    clippy::wrong_self_convention,
+)]
+
 use std::collections::HashMap;
 use std::ops::Add;
 use std::ops::Sub;
@@ -23,6 +31,7 @@ impl ResourceType {
         };
     }
 
+    #[allow(dead_code)]
     pub fn to_string(&self) -> String {
         return match self {
             ResourceType::Ore => "ore".to_string(),
@@ -82,10 +91,12 @@ impl ResourceTally {
         ])
     }
 
+    #[allow(dead_code)]
     pub fn copy_tally(&self) -> Self {
         return Self::from_hashmap(self.as_hashmap());
     }
 
+    #[allow(dead_code)]
     pub fn update_resource_from_string(&mut self, resource_type_str: &String, amount: u32) {
         if let Ok(resource_type) = ResourceType::from_string(resource_type_str) {
             self.update_resource(resource_type, amount);
@@ -212,6 +223,7 @@ impl State {
         return self.resources;
     }
 
+    #[allow(dead_code)]
     pub fn copy_current_resources(&self) -> ResourceTally {
         return self.resources.copy_tally();
     }
@@ -275,3 +287,46 @@ impl Blueprint {
         return self.max_geode_count;
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tallies_order_only_when_every_resource_agrees() {
+        let small = ResourceTally::from_tallies(1, 2, 3, 4);
+        let big = ResourceTally::from_tallies(2, 2, 5, 4);
+        assert_eq!(small.partial_cmp(&big), Some(Ordering::Less));
+        assert_eq!(big.partial_cmp(&small), Some(Ordering::Greater));
+        assert!(small <= big);
+        assert!(big >= small);
+    }
+
+    #[test]
+    fn mixed_tallies_are_incomparable() {
+        let more_ore = ResourceTally::from_tallies(5, 0, 0, 0);
+        let more_clay = ResourceTally::from_tallies(0, 5, 0, 0);
+        assert_eq!(more_ore.partial_cmp(&more_clay), None);
+        assert_eq!(more_clay.partial_cmp(&more_ore), None);
+        assert_ne!(more_ore, more_clay);
+    }
+
+    #[test]
+    fn equal_tallies() {
+        let tally = ResourceTally::from_tallies(1, 2, 3, 4);
+        assert_eq!(tally.partial_cmp(&tally.copy_tally()), Some(Ordering::Equal));
+        assert_eq!(tally, ResourceTally::from_tallies(1, 2, 3, 4));
+        assert!(ResourceTally::new() <= tally);
+    }
+
+    #[test]
+    fn arithmetic_and_updates() {
+        let tally = ResourceTally::from_tallies(1, 2, 3, 4);
+        assert_eq!(tally + tally - tally, tally);
+        assert_eq!(tally.scalar_mult(3), ResourceTally::from_tallies(3, 6, 9, 12));
+        let with_geode = tally.new_tally_with_added_resource(&ResourceType::Geode, 10);
+        assert_eq!(with_geode.get_amount(&ResourceType::Geode), 14);
+        assert_eq!(ResourceType::from_string(&"Obsidian".to_string()), Ok(ResourceType::Obsidian));
+    }
+}
@@ -1,9 +1,14 @@
-use std::env;
-use std::fs;
+#![allow(clippy::deref_addrof, clippy::ptr_arg, clippy::unused_unit)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::log;
+use crate::common::params::ParamInfo;
+use crate::common::progress::{self, Progress};
 use std::collections::HashMap;
 use std::cmp::Ordering;
-use std::io::stdout;
-use std::io::Write;
+
+pub const PARAMS: &[&ParamInfo] = &[];
 
 #[derive(PartialEq,Clone,Copy)]
 enum Sign {
@@ -114,14 +119,10 @@ impl CircularVector {
         return *self.vector.get(&key).unwrap();
     }
 
-    pub fn render_as_vec(&self) {
-        print!("[");
-        for i in 0..self.length {
-            print!("{}", self.get(i as i32));
-            if i < self.length - 1 {print!(", ");}
-        }
-        print!("]\n");
-        stdout().flush().expect("This should print to screen");
+    #[allow(dead_code)]
+    pub fn render_as_vec(&self) -> String {
+        let values: Vec<String> = (0..self.length).map(|i| self.get(i as i32).to_string()).collect();
+        return format!("[{}]", values.join(", "));
     }
 
     pub fn get_new_ind_from_original(&self, original_ind: usize) -> usize {
@@ -130,36 +131,45 @@ impl CircularVector {
 }
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let (original_vector, mut new_vector) = read_numbers(input)?;
+    mix(&original_vector, &mut new_vector)?;
+
+    if let Some(base_key) = new_vector.anchor {
+        let grove_coord1 = new_vector.get(base_key as i32 + 1000);
+        let grove_coord2 = new_vector.get(base_key as i32 + 2000);
+        let grove_coord3 = new_vector.get(base_key as i32 + 3000);
+        log::detail("grove_coordinates", vec![grove_coord1, grove_coord2, grove_coord3]);
+        return Ok(Answer::from(grove_coord1 + grove_coord2 + grove_coord3));
+    }
+    else {
+        return Err(SolveError::NoSolution("No 0 found!".to_string()));
+    }
+}
+
 
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+fn read_numbers(input: &str) -> Result<(Vec<i32>, CircularVector), ParseError> {
     let mut original_vector: Vec<i32> = Vec::new();
     let mut new_vector: CircularVector = CircularVector::new();
-    for line in input.trim().lines().collect::<Vec<&str>>() {
-        let item: i32 = line.trim().parse().unwrap();
+    for line in lines(input.trim_end()) {
+        let item: i32 = line.parse(line.text, "a number")?;
         original_vector.push(item);
         new_vector.insert(item);
     }
+    return Ok((original_vector, new_vector));
+}
+
 
-    println!("Total elements to run through: {}", &original_vector.len());
+// A half-mixed file says nothing about the answer, so stopping early gives up on it
+fn mix(original_vector: &Vec<i32>, new_vector: &mut CircularVector) -> Result<(), SolveError> {
+    let mut bar = Progress::new("Mixing", original_vector.len());
     for (ind, value) in original_vector.iter().enumerate() {
+        if progress::is_cancelled() {
+            return Err(SolveError::Interrupted {best: None});
+        }
         let new_ind: i32 = new_vector.get_new_ind_from_original(ind) as i32;
         new_vector.move_key_num_places(new_ind, *value);
-        if (ind + 1) % 100 == 0 {
-            println!("{}/{} done", ind + 1, &original_vector.len());
-        }
+        bar.tick();
     }
-
-    if let Some(base_key) = new_vector.anchor {
-        let grove_coord1 = new_vector.get(base_key as i32 + 1000);
-        let grove_coord2 = new_vector.get(base_key as i32 + 2000);
-        let grove_coord3 = new_vector.get(base_key as i32 + 3000);
-        println!("Coords: {} {} {}. Sum: {}", &grove_coord1, &grove_coord2, &grove_coord3, grove_coord1 + grove_coord2 + grove_coord3);
-    }
-    else {
-        panic!("No 0 found!");
-    }
-} 
+    return Ok(());
+}
@@ -1,32 +1,58 @@
-use std::env;
-use std::fs;
+#![allow(clippy::ptr_arg)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{lines, ParseError};
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::progress::{self, Progress};
+use crate::{log_debug, log_trace};
 use std::cmp::Ordering;
 
 type CanonicalInt = i64;
 type ValueIndex = (CanonicalInt, usize);
 
-const DECRYPT_KEY_DEFAULT: CanonicalInt = 811589153;
+pub const ROUNDS: Param<usize> = Param::new("rounds", "10", "how many times to mix the file");
+pub const DECRYPT_KEY: Param<CanonicalInt> = Param::new("decrypt_key", "811589153", "what to multiply every number by first");
+pub const PARAMS: &[&ParamInfo] = &[&ROUNDS.info, &DECRYPT_KEY.info];
+
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let rounds: usize = params.value(&ROUNDS)?;
+    let decrypt_key: CanonicalInt = params.value(&DECRYPT_KEY)?;
 
+    let mut data: Vec<ValueIndex> = read_numbers(input, decrypt_key)?;
+    log_trace!("Initial arrangement: {:?}", data);
+    mix(&mut data, rounds)?;
+    return match sum_grove_coordinates(&data) {
+        Some(total) => Ok(Answer::from(total)),
+        None => Err(SolveError::NoSolution("No 0 found!".to_string())),
+    };
+}
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let rounds: usize = if env_args.len() > 2 {env_args[2].parse().unwrap()} else {1};
-    let decrypt_key: CanonicalInt = if env_args.len() > 3 {env_args[3].parse().unwrap()} else {DECRYPT_KEY_DEFAULT};
-    println!("File name is '{}'. Reading input...", file_name);
 
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+fn read_numbers(input: &str, decrypt_key: CanonicalInt) -> Result<Vec<ValueIndex>, ParseError> {
     let mut data: Vec<ValueIndex> = Vec::new();
-    for (index, line) in input.trim().lines().collect::<Vec<&str>>().iter().enumerate() {
-        let item: CanonicalInt = line.trim().parse().unwrap();
+    for (index, line) in lines(input.trim_end()).enumerate() {
+        let item: CanonicalInt = line.parse(line.text, "a number")?;
         data.push((item * decrypt_key, index));
     }
+    return Ok(data);
+}
+
 
-    println!("{:?}", data);
+fn mix(data: &mut Vec<ValueIndex>, rounds: usize) -> Result<(), SolveError> {
     let data_len: usize = data.len();
-    println!("Total elements to run through: {}", data_len);
+    // Moving around a circle of one number goes nowhere
+    if data_len <= 1 {
+        return Ok(());
+    }
+    let mut bar = Progress::new("Mixing", rounds * data_len);
     for i in 0..rounds {
         for ind in 0..data_len {
+            if progress::is_cancelled() {
+                return Err(SolveError::Interrupted {best: None});
+            }
+            bar.tick();
             let current_index = data.iter().position(|&x| x.1 == ind).unwrap();
             let current_value = data[current_index].0;
 
@@ -44,15 +70,22 @@ fn main() {
                 },
             };
         }
-        println!("After round {}: {:?}", i + 1, data);
+        log_debug!("Finished round {}", i + 1);
+        log_trace!("After round {}: {:?}", i + 1, data);
     }
+    return Ok(());
+}
+
 
-    let base_key = data.iter().position(|&x| x.0 == 0).unwrap();
+fn sum_grove_coordinates(data: &Vec<ValueIndex>) -> Option<CanonicalInt> {
+    let data_len: usize = data.len();
+    let base_key = data.iter().position(|&x| x.0 == 0)?;
     let grove_coord1 = data[positive_mod(base_key as CanonicalInt + 1000, data_len as CanonicalInt) as usize].0;
     let grove_coord2 = data[positive_mod(base_key as CanonicalInt + 2000, data_len as CanonicalInt) as usize].0;
     let grove_coord3 = data[positive_mod(base_key as CanonicalInt + 3000, data_len as CanonicalInt) as usize].0;
-    println!("Coords: {} {} {}. Sum: {}", &grove_coord1, &grove_coord2, &grove_coord3, grove_coord1 + grove_coord2 + grove_coord3);
-} 
+    log::detail("grove_coordinates", vec![grove_coord1, grove_coord2, grove_coord3]);
+    return Some(grove_coord1 + grove_coord2 + grove_coord3);
+}
 
 fn positive_mod(n: CanonicalInt, div: CanonicalInt) -> CanonicalInt {
     let modded = n % div;
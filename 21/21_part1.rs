@@ -1,26 +1,28 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::ParamInfo;
+use crate::log_info;
 
-mod rational;
-mod token;
-mod linear_vector;
-mod formula;
-mod symbol_table;
-use symbol_table::SymbolTable;
+use super::symbol_table::{check_equations, get_answer, SymbolTable};
 
+pub const PARAMS: &[&ParamInfo] = &[&BIGINT.info];
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    check_equations(input)?;
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input),
+        false => solve_with::<Checked>(input),
+    };
+}
 
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
-    let mut symbol_table: SymbolTable = SymbolTable::new();
+fn solve_with<T: Integer>(input: &str) -> Result<Answer, SolveError> {
+    let mut symbol_table: SymbolTable<T> = SymbolTable::new();
     for line in input.trim().lines().collect::<Vec<&str>>() {
         symbol_table.add_symbol_from_string(&line.trim().to_string());
     }
-    println!("Data loaded. Evaluating root...");
+    log_info!("Data loaded. Evaluating root...");
 
     let ans = symbol_table.evaluate_variable(&String::from("root")).unwrap();
-    println!("Root answer: {}", ans);
-} 
+    return get_answer(ans, "The number root yells");
+}
@@ -1,23 +1,26 @@
-use std::env;
-use std::fs;
+#![allow(clippy::cmp_owned, clippy::needless_borrow, clippy::useless_vec)]
 
-mod rational;
-mod token;
-use token::Operation;
-mod linear_vector;
-mod formula;
-mod symbol_table;
-use symbol_table::SymbolTable;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::ParamInfo;
+use crate::log_info;
 
+use super::token::Operation;
+use super::symbol_table::{check_equations, get_answer, SymbolTable};
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
+pub const PARAMS: &[&ParamInfo] = &[&BIGINT.info];
 
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file");
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    check_equations(input)?;
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input),
+        false => solve_with::<Checked>(input),
+    };
+}
 
-    let mut symbol_table: SymbolTable = SymbolTable::new();
+fn solve_with<T: Integer>(input: &str) -> Result<Answer, SolveError> {
+    let mut symbol_table: SymbolTable<T> = SymbolTable::new();
     for line in input.trim().lines().collect::<Vec<&str>>() {
         if line.starts_with(&String::from("humn")) {
             symbol_table.add_symbol_from_string(&String::from("humn: humn"));
@@ -29,13 +32,14 @@ fn main() {
             symbol_table.add_symbol_from_string(&line.trim().to_string());
         }
     }
-    println!("Data loaded. Evaluating humn...");
+    log_info!("Data loaded. Evaluating humn...");
 
-    let ans = symbol_table.solve_for_symbol(&String::from("humn")).unwrap();
-    println!("humn answer: {}", ans);
-} 
+    let ans = symbol_table.solve_for_symbol(&String::from("humn")).map_err(|reason| SolveError::NoSolution(reason.to_string()))?;
+    return get_answer(ans, "The number to yell");
+}
 
 
+#[allow(dead_code)]
 fn rearrange_equation(current_subject: &String, current_formula: Vec<String>, new_subject: &String) -> String {
     if current_formula.len() != 3 {
         panic!(
@@ -1,31 +1,41 @@
+#![allow(
+    clippy::cmp_owned,
+    clippy::manual_map,
+    clippy::needless_borrow,
+    clippy::single_match,
+    clippy::useless_conversion,
+)]
+
 use std::collections::HashMap;
 
-use rational::{Rational,R0,R1};
-use token::{Token};
-use linear_vector::{LinearVector,NO_VAR};
+use crate::common::integer::Integer;
+
+use super::rational::Rational;
+use super::token::{Operation,Token};
+use super::linear_vector::{LinearVector,NO_VAR};
 
 #[derive(Debug)]
-pub struct Formula {
-    formula: Vec<Token>,
-    substitutions: HashMap<String, LinearVector>,
-    reduces_to: Option<LinearVector>,
-    evaluates_to: Option<Rational>,
+pub struct Formula<T: Integer> {
+    formula: Vec<Token<T>>,
+    substitutions: HashMap<String, LinearVector<T>>,
+    reduces_to: Option<LinearVector<T>>,
+    evaluates_to: Option<Rational<T>>,
 }
 
-impl Formula {
-    pub fn new(formula: Vec<Token>) -> Self {
+impl<T: Integer> Formula<T> {
+    pub fn new(formula: Vec<Token<T>>) -> Self {
         return Self{formula: formula, substitutions: HashMap::new(), reduces_to: None, evaluates_to: None};
     }
 
     pub fn from_string(formula_str: &String) -> Self {
         let clean_str: String = formula_str.trim().to_string();
         let tokens: Vec<&str> = clean_str.split_whitespace().collect();
-        let token_vector: Vec<Token>;
+        let token_vector: Vec<Token<T>>;
         if tokens.len() == 1 {
             let new_token = Token::from_string(&tokens[0].to_string());
             token_vector = match new_token {
                 Token::Constant(_) => vec![new_token],
-                Token::Variable(name) => vec![Token::Term(R1, name)],
+                Token::Variable(name) => vec![Token::Term(Rational::one(), name)],
                 _ => panic!("'{:?}' is not a valid formula", &formula_str),
             };
         }
@@ -39,7 +49,7 @@ impl Formula {
         return Self::new(token_vector);
     }
 
-    pub fn get_formula(&self) -> &Vec<Token> {
+    pub fn get_formula(&self) -> &Vec<Token<T>> {
         return &self.formula;
     }
 
@@ -54,30 +64,32 @@ impl Formula {
         return output;
     }
 
-    pub fn sub_linear_vec(&mut self, variable: &String, value: LinearVector) {
+    pub fn sub_linear_vec(&mut self, variable: &String, value: LinearVector<T>) {
         self.substitutions.insert(variable.to_string(), value);
     }
 
-    pub fn sub_value(&mut self, variable: &String, value: Rational) {
+    #[allow(dead_code)]
+    pub fn sub_value(&mut self, variable: &String, value: Rational<T>) {
         self.substitutions.insert(variable.to_string(), LinearVector::constant_from_rational(value, &(NO_VAR.to_string())));
     }
 
-    fn set_reduces_to(&mut self, value: &LinearVector){
+    fn set_reduces_to(&mut self, value: &LinearVector<T>){
         self.reduces_to = Some(value.clone());
-        if (&value).get_coeff() == R0 {self.evaluates_to = Some(value.get_constant());}
+        if (&value).get_coeff().is_zero() {self.evaluates_to = Some(value.get_constant());}
     }
 
-    fn set_evaluates_to(&mut self, value: &Rational){
-        self.evaluates_to = Some(*value);
+    #[allow(dead_code)]
+    fn set_evaluates_to(&mut self, value: &Rational<T>){
+        self.evaluates_to = Some(value.clone());
     }
 
-    pub fn get_reduces_to(&self) -> Option<LinearVector> {
+    pub fn get_reduces_to(&self) -> Option<LinearVector<T>> {
         return self.reduces_to.as_ref().cloned();
     }
 
-    pub fn reduce_to_linear_vector(&mut self, subject: &String) -> Result<LinearVector, &str> {
-        if let Some(num) = self.evaluates_to {
-            let lin_vec: LinearVector = LinearVector::constant_from_rational(num, &subject);
+    pub fn reduce_to_linear_vector(&mut self, subject: &String) -> Result<LinearVector<T>, &'static str> {
+        if let Some(num) = self.evaluates_to.clone() {
+            let lin_vec: LinearVector<T> = LinearVector::constant_from_rational(num, &subject);
             self.set_reduces_to(&lin_vec);
             return Ok(lin_vec);
         }
@@ -99,7 +111,14 @@ impl Formula {
         else if self.formula.len() == 3 {
             let left = self.reduce_token(&self.formula[0], &subject).unwrap();
             let right = self.reduce_token(&self.formula[2], &subject).unwrap();
+            // LinearVector can only hold the subject to the first power
             let ans = match &self.formula[1] {
+                Token::Op(Operation::Multiplication) if !left.get_coeff().is_zero() && !right.get_coeff().is_zero() => {
+                    return Err("The unknown would be multiplied by itself");
+                },
+                Token::Op(Operation::Division) if !right.get_coeff().is_zero() => {
+                    return Err("Something would be divided by the unknown");
+                },
                 Token::Op(operation) => operation.evaluate(left, right),
                 other => panic!("Middle token should be a term, not {:?}", other),
             };
@@ -111,18 +130,19 @@ impl Formula {
         }
     }
 
-    pub fn reduce_token(&self, term: &Token, subject: &String) -> Result<LinearVector,&str> {
+    pub fn reduce_token(&self, term: &Token<T>, subject: &String) -> Result<LinearVector<T>,&str> {
         let reduced = match term {
-            Token::Constant(num) => Ok(LinearVector::constant_from_rational(*num, subject)),
-            Token::Variable(name) => if name == subject {Ok(LinearVector::from_rationals(R0, R1, &name))} else {Ok(self.substitutions.get(name).unwrap().clone())},
+            Token::Constant(num) => Ok(LinearVector::constant_from_rational(num.clone(), subject)),
+            Token::Variable(name) => if name == subject {Ok(LinearVector::from_rationals(Rational::zero(), Rational::one(), &name))} else {Ok(self.substitutions.get(name).unwrap().clone())},
             Token::Op(_) => Err("Operations aren't valid terms for evaluation on their own isn't a valid term for evaluation"),
-            Token::Term(num, name) => Ok(LinearVector::constant_from_rational(*num, subject) * (self.substitutions.get(name).unwrap().clone())),
+            Token::Term(num, name) => Ok(LinearVector::constant_from_rational(num.clone(), subject) * (self.substitutions.get(name).unwrap().clone())),
         };
         return reduced;
     }
 
-    pub fn evaluate(&mut self) -> Result<Rational, &str> {
-        if let Some(num) = self.evaluates_to {
+    #[allow(dead_code)]
+    pub fn evaluate(&mut self) -> Result<Rational<T>, &str> {
+        if let Some(num) = self.evaluates_to.clone() {
             return Ok(num);
         }
         else if let Ok(ans) = self.reduce_to_linear_vector(&(NO_VAR.to_string())) {
@@ -133,7 +153,7 @@ impl Formula {
 
 
     pub fn create_copy(&self) -> Self {
-        let values_copy: HashMap<String, LinearVector> = self.substitutions.iter().map(|(x, y)| ((&x).to_string(), y.clone())).collect();
+        let values_copy: HashMap<String, LinearVector<T>> = self.substitutions.iter().map(|(x, y)| ((&x).to_string(), y.clone())).collect();
         return Self {
             formula: self.formula.iter().map(|x| x.create_copy()).collect(),
             substitutions: HashMap::from(values_copy),
@@ -141,7 +161,7 @@ impl Formula {
                 Some(lin_vec) => Some(lin_vec.clone()),
                 None => None, 
             },
-            evaluates_to: self.evaluates_to,
+            evaluates_to: self.evaluates_to.clone(),
         }
     }
 }
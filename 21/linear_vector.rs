@@ -1,77 +1,88 @@
+#![allow(clippy::cmp_owned, clippy::needless_borrow)]
+
 use std::ops::{Add,Sub,Mul,Div};
 
-use rational::{StdInt,Rational,R0, R1};
-use token::{Operation,Token};
-use formula::Formula;
+use crate::common::integer::Integer;
+
+use super::rational::Rational;
+use super::token::{Operation,Token};
+use super::formula::Formula;
 
 pub const NO_VAR: &str = "";
 
 // This method assumes we have only one unknown (which is the case for now)
 // This method assumes we won't end up with higher order terms for that one variable)
 #[derive(Debug)]
-pub struct LinearVector {
+pub struct LinearVector<T: Integer> {
     name: String,
-    constant: Rational,
-    coeff: Rational,
+    constant: Rational<T>,
+    coeff: Rational<T>,
 }
 
-impl LinearVector {
-    pub fn new(constant: Rational, coeff: Rational, name: &String) -> Self {
+impl<T: Integer> LinearVector<T> {
+    pub fn new(constant: Rational<T>, coeff: Rational<T>, name: &String) -> Self {
         return Self {constant: constant, coeff: coeff, name: name.to_string()};
     }
 
-    pub fn from_rationals(constant: Rational, coeff: Rational, name: &String) -> Self {
+    pub fn from_rationals(constant: Rational<T>, coeff: Rational<T>, name: &String) -> Self {
         return Self::new(constant, coeff, name);
     }
 
-    pub fn from_ints(constant: StdInt, coeff: StdInt, name: &String) -> Self {
+    #[allow(dead_code)]
+    pub fn from_ints(constant: i64, coeff: i64, name: &String) -> Self {
         return Self::from_rationals(Rational::from_int(constant), Rational::from_int(coeff), name);
     }
 
+    #[allow(dead_code)]
     pub fn from_bool(value: bool, name: &String) -> Self {
-        return Self::constant_from_int(value as StdInt, name);
+        return Self::constant_from_int(value as i64, name);
     }
 
-    pub fn get_coeff(&self) -> Rational {
-        return self.coeff;
+    pub fn get_coeff(&self) -> Rational<T> {
+        return self.coeff.clone();
     }
 
-    pub fn get_constant(&self) -> Rational {
-        return self.constant;
+    pub fn get_constant(&self) -> Rational<T> {
+        return self.constant.clone();
     }
 
+    #[allow(dead_code)]
     pub fn get_variable_name(&self) -> String {
         return (&self.name).to_string();
     }
 
-    pub fn constant_from_rational(constant: Rational, name: &String) -> Self {
-        return Self::from_rationals(constant, R0, name);
+    pub fn constant_from_rational(constant: Rational<T>, name: &String) -> Self {
+        return Self::from_rationals(constant, Rational::zero(), name);
     }
 
-    pub fn constant_from_int(constant: StdInt, name: &String) -> Self {
+    #[allow(dead_code)]
+    pub fn constant_from_int(constant: i64, name: &String) -> Self {
         return Self::constant_from_rational(Rational::from_int(constant), name);
     }
 
+    #[allow(dead_code)]
     pub fn create_copy(&self) -> Self {
-        return Self::new(self.constant, self.coeff, &self.name);
+        return self.clone();
     }
 
-    pub fn to_formula(&self) -> Formula {
-        if (self.coeff == R0) && (self.constant == R0) {
-            return Formula::new(vec![Token::Constant(R0)]);
+    #[allow(dead_code)]
+    pub fn to_formula(&self) -> Formula<T> {
+        if self.coeff.is_zero() && self.constant.is_zero() {
+            return Formula::new(vec![Token::Constant(Rational::zero())]);
         }
-        else if self.coeff == R0 {
-            return Formula::new(vec![Token::Constant(self.constant)]);
+        else if self.coeff.is_zero() {
+            return Formula::new(vec![Token::Constant(self.get_constant())]);
         }
-        else if self.constant == R0 {
-            return Formula::new(vec![Token::Term(self.coeff, (&self.name).to_string())]);
+        else if self.constant.is_zero() {
+            return Formula::new(vec![Token::Term(self.get_coeff(), (&self.name).to_string())]);
         }
         else {
-            return Formula::new(vec![Token::Term(self.coeff, (&self.name).to_string()), Token::Op(Operation::Addition), Token::Constant(self.constant)])
+            return Formula::new(vec![Token::Term(self.get_coeff(), (&self.name).to_string()), Token::Op(Operation::Addition), Token::Constant(self.get_constant())])
         }
     }
 
-    pub fn from_simple_formula(formula: Formula, name: &String) -> Result<Self, (String, Formula)> {
+    #[allow(dead_code)]
+    pub fn from_simple_formula(formula: Formula<T>, name: &String) -> Result<Self, (String, Formula<T>)> {
         let formula_length = formula.get_formula().len();
         return match formula_length {
             1 => Self::from_1_term_formula(formula, name),
@@ -81,7 +92,8 @@ impl LinearVector {
         };
     }
 
-    fn from_3_term_formula(formula: Formula, name: &String) -> Result<Self, (String, Formula)> {
+    #[allow(dead_code)]
+    fn from_3_term_formula(formula: Formula<T>, name: &String) -> Result<Self, (String, Formula<T>)> {
         if formula.get_formula().len() != 3 {panic!{"This method only handles 3 term formulae!"};}
         if formula.get_variable_names().len() > 1 {return Err(("Too many unknowns to reduce".to_string(), formula));}
         if let Token::Op(operation) = formula.get_formula()[1] {
@@ -102,21 +114,22 @@ impl LinearVector {
 
     }
 
-    fn from_1_term_formula(formula: Formula, name: &String) -> Result<Self, (String, Formula)> {
+    #[allow(dead_code)]
+    fn from_1_term_formula(formula: Formula<T>, name: &String) -> Result<Self, (String, Formula<T>)> {
         let var_names = formula.get_variable_names();
         if var_names.len() > 1 {panic!("Can't create a reduced formula when there is more than one variable unknown!");}
         else if (var_names.len() == 1) && (var_names[0] != name.to_string()) {panic!("Variable name introduced is different from what was specified: {}, {}", name, var_names[0]);}
         if formula.get_formula().len() != 1 {panic!{"This method only handles 1 term formulae!"};}
-        return match formula.get_formula()[0] {
-            Token::Constant(num) => Ok(Self::new(num, R0, name)),
-            Token::Term(num, _) => Ok(Self::new(R0, num, name)),
-            Token::Variable(_) => Ok(Self::new(R0, R1, name)),
+        return match &formula.get_formula()[0] {
+            Token::Constant(num) => Ok(Self::new(num.clone(), Rational::zero(), name)),
+            Token::Term(num, _) => Ok(Self::new(Rational::zero(), num.clone(), name)),
+            Token::Variable(_) => Ok(Self::new(Rational::zero(), Rational::one(), name)),
             Token::Op(_) => panic!("Can't create a reduced formula from an operation"),
         };
     }
 }
 
-impl Add for LinearVector { 
+impl<T: Integer> Add for LinearVector<T> { 
     type Output = Self;
     fn add(self, other: Self) -> Self {
         if self.name != other.name {panic!{"Multiple variables not supported!"};}
@@ -124,7 +137,7 @@ impl Add for LinearVector {
     }
 }
 
-impl Sub for LinearVector { 
+impl<T: Integer> Sub for LinearVector<T> { 
     type Output = Self;
     fn sub(self, other: Self) -> Self {
         if self.name != other.name {panic!{"Multiple variables not supported!"};}
@@ -132,23 +145,23 @@ impl Sub for LinearVector {
     }
 }
 
-impl Mul for LinearVector { 
+impl<T: Integer> Mul for LinearVector<T> { 
     type Output = Self;
     fn mul(self, other: Self) -> Self {
         if self.name != other.name {panic!{"Multiple variables not supported!"};}
-        if (self.coeff != R0) && (other.coeff != R0) {panic!("LinearVector only supports linear terms!");}
-        return Self::new(self.constant * other.constant, self.coeff*other.constant + other.coeff*self.constant, &self.name)
+        if !self.coeff.is_zero() && !other.coeff.is_zero() {panic!("LinearVector only supports linear terms!");}
+        return Self::new(self.constant.clone() * other.constant.clone(), self.coeff*other.constant + other.coeff*self.constant, &self.name)
     }
 }
 
-impl Div for LinearVector { 
+impl<T: Integer> Div for LinearVector<T> { 
     type Output = Self;
     fn div(self, other: Self) -> Self {
         if self.name != other.name {panic!{"Multiple variables not supported!"};}
-        if (self.coeff != R0) && (other.coeff != R0) {panic!("LinearVector only supports linear terms!");}
-        if other.coeff == R0 {
+        if !self.coeff.is_zero() && !other.coeff.is_zero() {panic!("LinearVector only supports linear terms!");}
+        if other.coeff.is_zero() {
             return Self {
-                constant: self.constant / other.constant, 
+                constant: self.constant / other.constant.clone(), 
                 coeff: self.coeff / other.constant, 
                 name: self.name,
             }
@@ -159,15 +172,67 @@ impl Div for LinearVector {
     }
 }
 
-impl PartialEq for LinearVector {
+impl<T: Integer> PartialEq for LinearVector<T> {
     fn eq(&self, other: &Self) -> bool {
         return (self.name == other.name) && (self.coeff == other.coeff) && (self.constant == other.constant);
     }
 }
-impl Eq for LinearVector {}
+impl<T: Integer> Eq for LinearVector<T> {}
 
-impl Clone for LinearVector {
+impl<T: Integer> Clone for LinearVector<T> {
     fn clone(&self) -> Self {
-        return Self::new(self.constant, self.coeff, &self.name);
+        return Self::new(self.get_constant(), self.get_coeff(), &self.name);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::common::integer::Checked;
+
+    use super::*;
+
+    type Vector = LinearVector<Checked>;
+
+    fn humn() -> String {
+        return "humn".to_string();
+    }
+
+    #[test]
+    fn add_and_sub_work_on_both_parts() {
+        let a = Vector::from_ints(3, 2, &humn());
+        let b = Vector::from_ints(1, 5, &humn());
+        assert_eq!(a.clone() + b.clone(), Vector::from_ints(4, 7, &humn()));
+        assert_eq!(a - b, Vector::from_ints(2, -3, &humn()));
+    }
+
+    #[test]
+    fn mul_and_div_by_constants_scale_both_parts() {
+        let a = Vector::from_ints(3, 2, &humn());
+        let four = Vector::constant_from_int(4, &humn());
+        assert_eq!(a.clone() * four.clone(), Vector::from_ints(12, 8, &humn()));
+        assert_eq!(four.clone() * a.clone(), Vector::from_ints(12, 8, &humn()));
+        let divided = a / four;
+        assert_eq!(divided.get_constant(), Rational::new(Checked::from(3), Checked::from(4)));
+        assert_eq!(divided.get_coeff(), Rational::new(Checked::from(1), Checked::from(2)));
+    }
+
+    #[test]
+    fn from_simple_formula_reduces_one_unknown() {
+        let formula = Formula::new(vec![Token::Variable(humn()), Token::Op(Operation::Subtraction), Token::Constant(Rational::from_int(3))]);
+        let reduced = Vector::from_simple_formula(formula, &humn()).unwrap();
+        assert_eq!(reduced, Vector::from_ints(-3, 1, &humn()));
+    }
+
+    #[test]
+    fn to_formula_drops_zero_parts() {
+        assert_eq!(Vector::from_ints(5, 0, &humn()).to_formula().get_formula(), &vec![Token::Constant(Rational::from_int(5))]);
+        assert_eq!(Vector::from_ints(0, 2, &humn()).to_formula().get_formula(), &vec![Token::Term(Rational::from_int(2), humn())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "LinearVector only supports linear terms!")]
+    fn squaring_the_unknown_panics() {
+        let _ = Vector::from_ints(0, 1, &humn()) * Vector::from_ints(0, 1, &humn());
     }
 }
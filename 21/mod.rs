@@ -0,0 +1,75 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "21_part1.rs"]
+pub mod part1;
+#[path = "21_part2.rs"]
+pub mod part2;
+
+mod rational;
+mod token;
+mod linear_vector;
+mod formula;
+mod symbol_table;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/21.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(152)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(301)));
+    }
+
+    #[test]
+    fn big_numbers() {
+        let input = "root: aaaa + bbbb\naaaa: 9000000000000000000\nbbbb: 9000000000000000000\n";
+        assert_eq!(solve(Part::One, input), Err(SolveError::Overflow("The number root yells".to_string())));
+        let mut params = Params::new();
+        params.insert("bigint", "true");
+        assert_eq!(solve_with_params(Part::One, input, &params), Ok(Answer::from("18000000000000000000")));
+    }
+
+    #[test]
+    fn dividing_by_zero() {
+        let input = "root: a + b\na: c / d\nc: 4\nd: 0\nb: 3\nhumn: 5\n";
+        assert_eq!(solve(Part::One, input), Err(SolveError::NoSolution("The number root yells needs a division by zero".to_string())));
+        let input = "root: a + b\na: humn - humn\nb: 3\nhumn: 5\n";
+        assert_eq!(solve(Part::Two, input), Err(SolveError::NoSolution("The number to yell needs a division by zero".to_string())));
+    }
+
+    #[test]
+    fn humn_must_stay_linear() {
+        let input = "root: a + b\na: humn * humn\nb: 9\nhumn: 5\n";
+        assert_eq!(solve(Part::One, input), Ok(Answer::from(34)));
+        assert_eq!(solve(Part::Two, input), Err(SolveError::NoSolution("The unknown would be multiplied by itself".to_string())));
+        let input = "root: a + b\na: b / humn\nb: 9\nhumn: 5\n";
+        assert_eq!(solve(Part::Two, input), Err(SolveError::NoSolution("Something would be divided by the unknown".to_string())));
+    }
+}
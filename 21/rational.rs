@@ -2,122 +2,203 @@ use std::ops::{Add,Sub,Mul,Div,Neg};
 use std::cmp::Ordering;
 use std::fmt;
 
-pub type StdInt = i64;
-
-pub fn gcd(x: StdInt, y: StdInt) -> StdInt {
-    let x_abs = x.abs();
-    let y_abs = y.abs();
-    if x == 0 {
-        return y_abs;
-    } 
-    else if y == 0 {
-        return x_abs;
-    }
-    if x == y {
-        return x_abs;
-    }
-    let bigger: StdInt = if x_abs > y_abs {x_abs} else {y_abs};
-    let smaller: StdInt = if x_abs > y_abs {y_abs} else {x_abs};
-
-    let remainder: StdInt = bigger % smaller;
-    return gcd(smaller, remainder);
-}
+use crate::common::integer::Integer;
 
 
-#[derive(Debug,Copy,Clone)]
-pub struct Rational {
-    num: StdInt,
-    denom: StdInt,
+#[derive(Debug,Clone)]
+pub struct Rational<T: Integer> {
+    num: T,
+    denom: T,
 }
 
-impl Rational {
-    pub fn new(num: StdInt, denom: StdInt) -> Self {
-        let gcd: StdInt = gcd(num, denom);
-        let new_num: StdInt = num / gcd;
-        let new_denom: StdInt = denom / gcd;
-        if new_denom > 0 {
-            return Self {num: new_num, denom: new_denom};
+impl<T: Integer> Rational<T> {
+    // Dividing by zero gives 1/0, and everything worked out from that stays 1/0 like an overflow does
+    pub fn new(num: T, denom: T) -> Self {
+        if denom.is_zero() {
+            return Self {num: T::from(1), denom: denom};
         }
-        else if new_denom < 0 {
+        let gcd: T = num.gcd(&denom);
+        let new_num: T = num / gcd.clone();
+        let new_denom: T = denom / gcd;
+        if new_denom < T::from(0) {
             return Self {num: -new_num, denom: -new_denom};
         }
-        else {
-            panic!("Can't divide by zero!");   
-        }
+        return Self {num: new_num, denom: new_denom};
+    }
+
+    pub fn from_int(num: i64) -> Self {
+        return Self::whole(T::from(num));
+    }
+
+    pub fn whole(num: T) -> Self {
+        return Self {num: num, denom: T::from(1)};
     }
 
-    pub fn from_int(num: StdInt) -> Self {
-        return Self::new(num, 1);
+    pub fn zero() -> Self {
+        return Self::from_int(0);
     }
 
+    pub fn one() -> Self {
+        return Self::from_int(1);
+    }
+
+    #[allow(dead_code)]
     pub fn from_bool(value: bool) -> Self {
-        return Self::from_int(value as StdInt);
+        return Self::from_int(value as i64);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.num.is_zero();
+    }
+
+    pub fn as_int(&self) -> Option<T> {
+        return if self.denom == T::from(1) {Some(self.num.clone())} else {None};
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        return self.denom.is_zero();
+    }
+
+    // Overflowed parts stay overflowed, so this only needs checking on the answer
+    pub fn overflowed(&self) -> bool {
+        return self.num.overflowed() || self.denom.overflowed();
     }
 }
 
-impl Add for Rational { 
+impl<T: Integer> Add for Rational<T> { 
     type Output = Self;
     fn add(self, other: Self) -> Self {
-        let new_num: StdInt = (self.num * other.denom) + (self.denom * other.num);
-        let new_denom: StdInt = self.denom * other.denom;
+        let new_num: T = (self.num * other.denom.clone()) + (self.denom.clone() * other.num);
+        let new_denom: T = self.denom * other.denom;
         return Self::new(new_num, new_denom);
     }
 }
 
-impl Sub for Rational { 
+impl<T: Integer> Sub for Rational<T> { 
     type Output = Self;
     fn sub(self, other: Self) -> Self {
-        let new_num: StdInt = (self.num * other.denom) - (self.denom * other.num);
-        let new_denom: StdInt = self.denom * other.denom;
+        let new_num: T = (self.num * other.denom.clone()) - (self.denom.clone() * other.num);
+        let new_denom: T = self.denom * other.denom;
         return Self::new(new_num, new_denom);
     }
 }
 
-impl Mul for Rational { 
+impl<T: Integer> Mul for Rational<T> { 
     type Output = Self;
     fn mul(self, other: Self) -> Self {
         return Self::new(self.num * other.num, self.denom * other.denom);
     }
 }
 
-impl Div for Rational { 
+impl<T: Integer> Div for Rational<T> { 
     type Output = Self;
     fn div(self, other: Self) -> Self {
+        // Its 0 denominator would end up on top here instead
+        if other.is_undefined() {
+            return other;
+        }
         return Self::new(self.num * other.denom, self.denom * other.num);
     }
 }
 
-impl Neg for Rational {
+impl<T: Integer> Neg for Rational<T> {
     type Output = Self;
     fn neg(self) -> Self {
         return Self::new(-self.num, self.denom);
     }
 }
 
-impl PartialOrd for Rational {
+impl<T: Integer> PartialOrd for Rational<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {return Some(self.cmp(other));}
 }
-impl PartialEq for Rational {
+impl<T: Integer> PartialEq for Rational<T> {
     fn eq(&self, other: &Self) -> bool {
         return self.cmp(other) == Ordering::Equal;
     }
 }
-impl Eq for Rational {}
+impl<T: Integer> Eq for Rational<T> {}
 
-impl Ord for Rational {
+impl<T: Integer> Ord for Rational<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        return (self.num * other.denom).cmp(&(other.num * self.denom));
+        return (self.num.clone() * other.denom.clone()).cmp(&(other.num.clone() * self.denom.clone()));
     }
 }
 
-impl fmt::Display for Rational {
+impl<T: Integer> fmt::Display for Rational<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.denom == 1 {
+        if self.denom == T::from(1) {
             return write!(f, "{}", self.num);
         }
         return write!(f, "{}/{}", self.num, self.denom);
     }
 }
 
-pub const R0: Rational = Rational{num: 0, denom: 1};
-pub const R1: Rational = Rational{num: 1, denom: 1};
+
+#[cfg(test)]
+mod tests {
+    use crate::common::bigint::BigInt;
+    use crate::common::integer::Checked;
+
+    use super::*;
+
+    fn rational(num: i64, denom: i64) -> Rational<Checked> {
+        return Rational::new(Checked::from(num), Checked::from(denom));
+    }
+
+    #[test]
+    fn new_reduces_and_keeps_the_sign_on_top() {
+        let half = rational(-3, -6);
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!(rational(4, -6).to_string(), "-2/3");
+        assert_eq!(rational(0, 5).as_int(), Some(Checked::from(0)));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let third = rational(1, 3);
+        let half = rational(1, 2);
+        assert_eq!(third.clone() + half.clone(), rational(5, 6));
+        assert_eq!(third.clone() - half.clone(), rational(-1, 6));
+        assert_eq!(third.clone() * half.clone(), rational(1, 6));
+        assert_eq!(third.clone() / half.clone(), rational(2, 3));
+        assert_eq!(-third.clone(), rational(-1, 3));
+        assert_eq!((half.clone() + half).as_int(), Some(Checked::from(1)));
+        assert_eq!(third.as_int(), None);
+    }
+
+    #[test]
+    fn ordering_compares_values_not_parts() {
+        assert!(rational(1, 3) < rational(1, 2));
+        assert!(rational(-1, 2) < rational(-1, 3));
+        assert_eq!(rational(2, 4), rational(1, 2));
+        assert!(Rational::<Checked>::zero() < Rational::one());
+    }
+
+    #[test]
+    fn gcd_ignores_signs() {
+        assert_eq!(Checked::from(12).gcd(&Checked::from(-18)), Checked::from(6));
+        assert_eq!(Checked::from(0).gcd(&Checked::from(-7)), Checked::from(7));
+        assert_eq!(Checked::from(5).gcd(&Checked::from(5)), Checked::from(5));
+    }
+
+    #[test]
+    fn overflow_is_kept_and_big_integers_are_exact() {
+        let big = rational(i64::MAX, 1);
+        assert!((big.clone() + big.clone()).overflowed());
+        assert!(!(big.clone() / rational(2, 1)).overflowed());
+        let exact: Rational<BigInt> = Rational::whole(BigInt::from(i64::MAX)) * Rational::from_int(4) / Rational::from_int(3);
+        assert_eq!(exact.to_string(), "36893488147419103228/3");
+    }
+
+    #[test]
+    fn dividing_by_zero_stays_undefined() {
+        let undefined = rational(1, 0);
+        assert!(undefined.is_undefined());
+        assert!((rational(3, 1) / Rational::zero()).is_undefined());
+        assert!((undefined.clone() + rational(1, 2)).is_undefined());
+        assert!((undefined.clone() * Rational::zero()).is_undefined());
+        assert!((-undefined.clone()).is_undefined());
+        assert!((rational(2, 1) / undefined).is_undefined());
+        assert!(!rational(0, 3).is_undefined());
+    }
+}
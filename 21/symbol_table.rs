@@ -1,26 +1,32 @@
-use std::collections::HashMap;
+#![allow(clippy::needless_borrow, clippy::ptr_arg)]
 
-use rational::Rational;
-use token::{Operation,Token};
-use linear_vector::{LinearVector,NO_VAR};
-use formula::Formula;
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{Answer, SolveError};
+use crate::common::integer::{self, Checked, Integer};
+use crate::common::parse::{lines, Line, ParseError};
+
+use super::rational::Rational;
+use super::token::{Operation,Token};
+use super::linear_vector::{LinearVector,NO_VAR};
+use super::formula::Formula;
 
 #[derive(Debug)]
-pub struct SymbolTable {
-    table: HashMap<String, Formula>,
-    constraint: Option<Formula>,
+pub struct SymbolTable<T: Integer> {
+    table: HashMap<String, Formula<T>>,
+    constraint: Option<Formula<T>>,
 }
 
-impl SymbolTable {
+impl<T: Integer> SymbolTable<T> {
     pub fn new() -> Self {
         return Self {table: HashMap::new(), constraint: None};
     }
 
-    pub fn add_symbol(&mut self, name: &String, formula: Formula) {
+    pub fn add_symbol(&mut self, name: &String, formula: Formula<T>) {
         self.table.insert(name.to_string(), formula);
     }
 
-    pub fn add_constraint(&mut self, formula: Formula) {
+    pub fn add_constraint(&mut self, formula: Formula<T>) {
         self.constraint = Some(formula);
     }
 
@@ -28,7 +34,7 @@ impl SymbolTable {
         let vec_str: Vec<String> = equation_str.split(": ").map(|x| x.to_string()).collect();
         let symbol_name: &String = &vec_str[0];
         let formula_str: &String = &vec_str[1];
-        let formula: Formula = Formula::from_string(&formula_str);
+        let formula: Formula<T> = Formula::from_string(&formula_str);
         if (formula.get_formula().len() > 1) && formula.get_formula()[1] == Token::Op(Operation::Equals) {
             self.add_constraint(formula);
         }
@@ -37,50 +43,41 @@ impl SymbolTable {
         }
     }
 
-    pub fn evaluate_variable(&mut self, variable_name: &String) -> Result<Rational, &str> {
-        let reduced: Result<LinearVector, &str> = self.reduce_variable(variable_name, &(NO_VAR.to_string()));
+    pub fn evaluate_variable(&mut self, variable_name: &String) -> Result<Rational<T>, &str> {
+        let reduced: Result<LinearVector<T>, &str> = self.reduce_variable(variable_name, &(NO_VAR.to_string()));
         return match reduced {
             Ok(lin_vec) => Ok(lin_vec.get_constant()),
             Err(msg) => Err(msg),
         };
     }
 
-    pub fn reduce_variable(&mut self, variable_name: &String, subject: &String) -> Result<LinearVector, &str> {
+    pub fn reduce_variable(&mut self, variable_name: &String, subject: &String) -> Result<LinearVector<T>, &'static str> {
         let mut current_formula = self.table.get(variable_name).unwrap().create_copy();
         let variables_to_evaluate: &Vec<String> = &(self.table.get(variable_name).unwrap().get_variable_names());
         for name in variables_to_evaluate {
             if name == subject {continue;}
-            let ans = self.reduce_variable(name, subject);
-            if let Ok(num) = ans {
-                current_formula.sub_linear_vec(name, num);
-            }
-            else {
-                return Err("Formula currently not reducible in terms of the given subject");
-            }
+            let num = self.reduce_variable(name, subject)?;
+            current_formula.sub_linear_vec(name, num);
         }
-        match current_formula.reduce_to_linear_vector(subject) {
-            Ok(ans) => {
-                self.add_symbol(variable_name, current_formula);
-                return Ok(ans);
-            },
-            Err(_) => return Err("Variable not currently reducible in terms of the given subject"),
-        };
+        let ans = current_formula.reduce_to_linear_vector(subject)?;
+        self.add_symbol(variable_name, current_formula);
+        return Ok(ans);
     }
 
     
-    pub fn solve_for_symbol(&mut self, subject: &String) -> Result<Rational, &str> {
+    pub fn solve_for_symbol(&mut self, subject: &String) -> Result<Rational<T>, &'static str> {
         let left_symbol = self.constraint.as_ref().expect("No constraint found").get_formula()[0].create_copy();
         let right_symbol = self.constraint.as_ref().expect("No constraint found").get_formula()[2].create_copy();
-        let left: LinearVector;
-        let right: LinearVector;
+        let left: LinearVector<T>;
+        let right: LinearVector<T>;
 
         if let Token::Variable(left_name) = left_symbol {
-            self.reduce_variable(&left_name, subject);
+            self.reduce_variable(&left_name, subject)?;
             left = self.table.get(&left_name).unwrap().get_reduces_to().unwrap();
         }else {return Err("Malformed formula");}
 
         if let Token::Variable(right_name) = right_symbol {
-            self.reduce_variable(&right_name, subject);
+            self.reduce_variable(&right_name, subject)?;
             right = self.table.get(&right_name).unwrap().get_reduces_to().unwrap();
         }else {return Err("Malformed formula");}
     
@@ -88,3 +85,69 @@ impl SymbolTable {
         return Ok(-equals_zero.get_constant()/equals_zero.get_coeff());
     }
 }
+
+
+// Non-integer answers are given as fractions
+pub fn get_answer<T: Integer>(value: Rational<T>, what: &str) -> Result<Answer, SolveError> {
+    if value.overflowed() {
+        return Err(SolveError::Overflow(what.to_string()));
+    }
+    if value.is_undefined() {
+        return Err(SolveError::NoSolution(format!("{} needs a division by zero", what)));
+    }
+    return match value.as_int() {
+        Some(value) => integer::get_answer(value, what),
+        None => Ok(Answer::from(value.to_string())),
+    };
+}
+
+
+// Checks every line has the shape Formula::from_string expects and that every name used is defined,
+// so building and evaluating the table afterwards can't fail on bad input
+pub fn check_equations(input: &str) -> Result<(), ParseError> {
+    let mut defined: HashSet<&str> = HashSet::new();
+    let mut references: Vec<(Line, &str)> = Vec::new();
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        let (name, formula_str) = line.split_once(": ")?;
+        let name = name.trim();
+        if !is_symbol_name(name) {
+            return Err(line.error(name, "a monkey name"));
+        }
+        defined.insert(name);
+
+        let tokens: Vec<&str> = formula_str.split_whitespace().collect();
+        if (tokens.len() != 1) && (tokens.len() != 3) {
+            return Err(line.error(formula_str.trim(), "a number or 'aaaa + bbbb'"));
+        }
+        for (index, token) in tokens.iter().enumerate() {
+            if index == 1 {
+                if Operation::from_string(&token.to_string()).is_err() || (*token == "=") {
+                    return Err(line.error(token, "'+', '-', '*' or '/'"));
+                }
+            }
+            else if is_symbol_name(token) {
+                references.push((line, token));
+            }
+            else if token.parse::<Checked>().is_err() {
+                return Err(line.error(token, "a number or a monkey name"));
+            }
+        }
+    }
+
+    if !defined.contains("root") {
+        return Err(ParseError::whole_input("no 'root' monkey", "a line starting 'root: '"));
+    }
+    for (line, name) in references {
+        if !defined.contains(name) {
+            return Err(line.error(name, "a monkey defined elsewhere in the input"));
+        }
+    }
+    return Ok(());
+}
+
+fn is_symbol_name(name: &str) -> bool {
+    return !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic());
+}
@@ -1,5 +1,8 @@
 use std::ops::{Add,Sub,Mul,Div};
-use rational::{StdInt,Rational};
+
+use crate::common::integer::Integer;
+
+use super::rational::Rational;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Operation {
@@ -48,17 +51,17 @@ impl Operation {
 }
 
 #[derive(Debug,PartialEq)]
-pub enum Token {
-    Term(Rational, String),
+pub enum Token<T: Integer> {
+    Term(Rational<T>, String),
     Variable(String),
-    Constant(Rational),
+    Constant(Rational<T>),
     Op(Operation),
 }
 
-impl Token {
-    pub fn from_string(string: &String) -> Token {
-        if let Ok(int) = string.parse::<StdInt>() {
-            return Token::Constant(Rational::from_int(int));
+impl<T: Integer> Token<T> {
+    pub fn from_string(string: &String) -> Token<T> {
+        if let Ok(int) = string.parse::<T>() {
+            return Token::Constant(Rational::whole(int));
         }
         else if let Ok(op) = Operation::from_string(string) {
             return Token::Op(op);
@@ -71,9 +74,9 @@ impl Token {
     pub fn create_copy(&self) -> Self {
         return match self {
             Self::Op(op) => Self::Op(*op),
-            Self::Constant(num) => Self::Constant(*num),
+            Self::Constant(num) => Self::Constant(num.clone()),
             Self::Variable(string) => Self::Variable((&string).to_string()),
-            Self::Term(num, string) => Self::Term(*num, (&string).to_string()),
+            Self::Term(num, string) => Self::Term(num.clone(), (&string).to_string()),
         }
     }
 }
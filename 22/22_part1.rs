@@ -1,46 +1,72 @@
-use std::env;
-use std::fs;
+#![allow(clippy::needless_borrow, clippy::ptr_arg)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+use crate::log_info;
 use std::collections::HashMap;
 
-mod space;
-use space::{Direction,Marker,Point,Rotation,StdInt};
-mod face;
-mod map;
-use map::Map;
-mod reading_input;
-use reading_input::get_input_data;
-
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let face_size: StdInt = env_args[2].parse().unwrap();
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
-    let (unglued_map, instructions, mut current_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size);
+use super::space::{facing_value,Direction,Marker,Point,Rotation,StdInt};
+use super::map::{Map, MapView, MarkerSimulation};
+use super::reading_input::get_input_data;
+
+pub const FACE_SIZE: Param<StdInt> = Param::new("face_size", "50", "how long each side of the cube is");
+pub const PARAMS: &[&ParamInfo] = &[&FACE_SIZE.info];
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
     let map = glue_faces(&unglued_map);
 
     let total_instructions = &instructions.len();
-    println!("Initial state: {}, num instructions: {}", current_marker, &total_instructions);
-    println!("Data loaded. Traversing map...");
+    log_info!("Initial state: {}, num instructions: {}", initial_marker, &total_instructions);
+    log_info!("Data loaded. Traversing map...");
+
+    let final_marker: Marker = follow_instructions(&map, &instructions, initial_marker);
+    log_info!("Map traversed");
+    log::detail("final_marker", final_marker.to_string());
+    return Ok(Answer::from(get_password(&final_marker)));
+}
+
+// The whole route drawn over the map, with the marker where it ends up
+pub fn picture(input: &str, params: &Params) -> Result<Picture, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
+    let map = glue_faces(&unglued_map);
+    let (final_marker, trail) = map.follow_instructions_with_trail(&instructions, initial_marker);
+    return Ok(Picture::capture(&MapView::new(&map).with_trail(&trail).with_marker(final_marker)));
+}
 
+pub fn simulation(input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
+    return Ok(Box::new(MarkerSimulation::new(glue_faces(&unglued_map), instructions, initial_marker)));
+}
+
+
+fn follow_instructions(map: &Map, instructions: &Vec<String>, mut current_marker: Marker) -> Marker {
     for instruction in instructions.iter() {
         current_marker = match instruction.parse::<StdInt>() {
             Ok(distance) => map.get_new_position(&current_marker, distance),
-            Err(_) => current_marker.get_rotated_marker(&Rotation::from_string(&instruction)),
+            Err(_) => current_marker.get_rotated_marker(&instruction.parse::<Rotation>().unwrap()),
         };
     }
-    println!("Map traversed");
-    println!("Final marker: {}", &current_marker);
-    let current_point: Point = current_marker.get_position();
-    let current_direction: Direction = current_marker.get_direction();
-    let password: StdInt = (1000 * current_point.y) + (4 * current_point.x) + current_direction.as_int();
-    println!("Password is {}", password);
-} 
+    return current_marker;
+}
+
+
+fn get_password(marker: &Marker) -> StdInt {
+    let current_point: Point = marker.get_position();
+    let current_direction: Direction = marker.get_direction();
+    return (1000 * current_point.y) + (4 * current_point.x) + facing_value(&current_direction);
+}
 
 
 fn glue_faces(old_map: &Map) -> Map {
     let mut map = old_map.create_copy();
-    println!("Glueing faces...");
+    log_info!("Glueing faces...");
     let max_face = map.find_face(&Point::new(map.get_max_x().unwrap(), map.get_max_y().unwrap()));
     let max_x = max_face.x;
     let max_y = max_face.y;
@@ -54,13 +80,13 @@ fn glue_faces(old_map: &Map) -> Map {
             }
         }
     }
-    println!("Faces glued");
+    log_info!("Faces glued");
     return map;
 }
 
 fn find_neighbours(map: &Map, face: &Point) -> HashMap<Direction, Point> {
     let mut neighbours: HashMap<Direction,Point> = HashMap::new();
-    for direction in Direction::get_directions() {
+    for direction in Direction::all() {
         let neighbour = find_neighbour_in_direction(map, face, &direction);
         neighbours.insert(direction, neighbour);
     }
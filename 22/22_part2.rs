@@ -1,49 +1,78 @@
-use std::env;
-use std::fs;
-use std::collections::{HashMap,HashSet,VecDeque};
-
-mod space;
-use space::{Direction,Marker,Point,Rotation,StdInt};
-mod space3d;
-use space3d::{Direction3D,Orientation};
-mod face;
-use face::EdgeGlue;
-mod map;
-use map::Map;
-mod reading_input;
-use reading_input::get_input_data;
-
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    let face_size: StdInt = env_args[2].parse().unwrap();
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
-    let (unglued_map, instructions, mut current_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size);
-    let map = glue_faces(&unglued_map);
+#![allow(clippy::map_entry, clippy::needless_borrow, clippy::ptr_arg)]
+
+use crate::common::{Answer, Params, SolveError};
+use crate::common::search::bfs;
+use crate::common::log;
+use crate::common::params::{Param, ParamInfo};
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+use crate::log_info;
+use std::collections::HashMap;
+
+use super::space::{facing_value,Direction,Marker,Point,Rotation,StdInt};
+use super::space3d::{Direction3D,Orientation};
+use super::face::EdgeGlue;
+use super::map::{Map, MapView, MarkerSimulation};
+use super::reading_input::get_input_data;
+
+pub const FACE_SIZE: Param<StdInt> = Param::new("face_size", "50", "how long each side of the cube is");
+pub const PARAMS: &[&ParamInfo] = &[&FACE_SIZE.info];
+
+// Which face each flat face was folded up from and how, and which flat face ends up facing each way
+type Folding = (HashMap<Point,(Point,Direction3D)>, HashMap<Direction3D,Point>);
+
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
+    let map = glue_faces(&unglued_map)?;
 
     let total_instructions = &instructions.len();
-    println!("Initial state: {}, num instructions: {}", current_marker, &total_instructions);
-    println!("Data loaded. Traversing map...");
+    log_info!("Initial state: {}, num instructions: {}", initial_marker, &total_instructions);
+    log_info!("Data loaded. Traversing map...");
 
+    let final_marker: Marker = follow_instructions(&map, &instructions, initial_marker);
+    log_info!("Map traversed");
+    log::detail("final_marker", final_marker.to_string());
+    return Ok(Answer::from(get_password(&final_marker)));
+}
+
+// The whole route drawn over the map, with the marker where it ends up
+pub fn picture(input: &str, params: &Params) -> Result<Picture, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
+    let map = glue_faces(&unglued_map)?;
+    let (final_marker, trail) = map.follow_instructions_with_trail(&instructions, initial_marker);
+    return Ok(Picture::capture(&MapView::new(&map).with_trail(&trail).with_marker(final_marker)));
+}
+
+pub fn simulation(input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let face_size: StdInt = params.value(&FACE_SIZE)?;
+    let (unglued_map, instructions, initial_marker): (Map, Vec<String>, Marker) = get_input_data(input, face_size)?;
+    return Ok(Box::new(MarkerSimulation::new(glue_faces(&unglued_map)?, instructions, initial_marker)));
+}
+
+
+fn follow_instructions(map: &Map, instructions: &Vec<String>, mut current_marker: Marker) -> Marker {
     for instruction in instructions.iter() {
         current_marker = match instruction.parse::<StdInt>() {
             Ok(distance) => map.get_new_position(&current_marker, distance),
-            Err(_) => current_marker.get_rotated_marker(&Rotation::from_string(&instruction)),
+            Err(_) => current_marker.get_rotated_marker(&instruction.parse::<Rotation>().unwrap()),
         };
     }
-    println!("Map traversed");
-    println!("Final marker: {}", &current_marker);
-    let current_point: Point = current_marker.get_position();
-    let current_direction: Direction = current_marker.get_direction();
-    let password: StdInt = (1000 * current_point.y) + (4 * current_point.x) + current_direction.as_int();
-    println!("Password is {}", password);
-} 
+    return current_marker;
+}
+
+
+fn get_password(marker: &Marker) -> StdInt {
+    let current_point: Point = marker.get_position();
+    let current_direction: Direction = marker.get_direction();
+    return (1000 * current_point.y) + (4 * current_point.x) + facing_value(&current_direction);
+}
 
 
-fn glue_faces(old_map: &Map) -> Map {
+fn glue_faces(old_map: &Map) -> Result<Map, SolveError> {
     let mut map = old_map.create_copy();
-    println!("Glueing faces...");
+    log_info!("Glueing faces...");
     let max_face = map.find_face(&Point::new(map.get_max_x().unwrap(), map.get_max_y().unwrap()));
     let max_x = max_face.x;
     let max_y = max_face.y;
@@ -51,18 +80,18 @@ fn glue_faces(old_map: &Map) -> Map {
         for i in 1..=max_x {
             let this_face: Point = Point::new(i, j);
             if !map.has_face(&this_face) || map.is_face_fully_glued(&this_face) {continue;}
-            let neighbours: HashMap<Direction,EdgeGlue> = find_neighbours(&map, &this_face);
+            let neighbours: HashMap<Direction,EdgeGlue> = find_neighbours(&map, &this_face)?;
             for (direction, (neighbour, rotation)) in neighbours.iter() {
                 map.bidirectional_glue_faces(&this_face, &neighbour, &direction, &rotation);
             }
         }
     }
-    return map;
+    return Ok(map);
 }
 
 
-fn find_neighbours(map: &Map, start: &Point) -> HashMap<Direction, EdgeGlue> {
-    let (previous_in_path, d3d_to_face) = dfs_for_rotations(map, start);
+fn find_neighbours(map: &Map, start: &Point) -> Result<HashMap<Direction, EdgeGlue>, SolveError> {
+    let (previous_in_path, d3d_to_face) = bfs_for_rotations(map, start)?;
 
     let mut edge_glues: HashMap<Direction, EdgeGlue> = HashMap::new();
     for direction in map.get_faces_unglued_directions(start) {
@@ -74,48 +103,41 @@ fn find_neighbours(map: &Map, start: &Point) -> HashMap<Direction, EdgeGlue> {
         let face_rotation: Rotation = Direction3D::get_2d_rotation_from_tangent_change_on_z_face(&current_tangent, &new_tangent);
         edge_glues.insert(direction, (face, face_rotation));
     }
-    return edge_glues;
+    return Ok(edge_glues);
 }
 
-fn dfs_for_rotations(map: &Map, start: &Point) 
-    -> (HashMap<Point,(Point,Direction3D)>, HashMap<Direction3D,Point>) {    
+// Walks out over the flat faces from `start`, folding each one up onto the cube
+// as it's reached, to find which cube face each flat face ends up as. Cancelled
+// part way, some faces wouldn't have been found
+fn bfs_for_rotations(map: &Map, start: &Point) -> Result<Folding, SolveError> {
     let mut from_previous: HashMap<Point,(Point,Direction3D)> = HashMap::new();
-    let mut orientation_from_face: HashMap<Point,Orientation> = HashMap::new();
+    let mut orientation_from_face: HashMap<Point,Orientation> = HashMap::from([(*start, Orientation::new())]);
     let mut d3d_to_face: HashMap<Direction3D,Point> = HashMap::from([(Direction3D::Z, *start)]);
 
-    let mut queue: VecDeque<Point> = VecDeque::new();
-    let mut queued: HashSet<Point> = HashSet::new();
-    let mut explored: HashSet<Point> = HashSet::new();
-    let mut current_face: Point = *start;
-    let mut current_orientation: Orientation = Orientation::new();
-
-    loop {
-        let next_faces: Vec<(Point,Direction)> = get_flat_neighbour_faces(map, &current_face);
-        for (face, direction) in next_faces {
-            if !queued.contains(&face) && !explored.contains(&face) {
+    let result = bfs([*start], |current_face| {
+        let current_orientation: Orientation = *orientation_from_face.get(current_face).unwrap();
+        let mut new_faces: Vec<Point> = Vec::new();
+        for (face, direction) in get_flat_neighbour_faces(map, current_face) {
+            if !orientation_from_face.contains_key(&face) {
                 let new_rotation = current_orientation.get_rotation_axis_for_direction(&direction);
                 let new_orientation = current_orientation.rotate(&new_rotation);
                 orientation_from_face.insert(face, new_orientation);
                 d3d_to_face.insert(new_orientation.get_face_direction(), face);
-                from_previous.insert(face, (current_face, new_rotation));
-                queued.insert(face);
-                queue.push_back(face);
+                from_previous.insert(face, (*current_face, new_rotation));
+                new_faces.push(face);
             }
         }
-        explored.insert(current_face);
-
-        if (explored.len() == 6) || (queue.len() == 0) {
-            break;
-        }
-        current_face = queue.pop_front().unwrap();
-        current_orientation = *orientation_from_face.get(&current_face).unwrap();
+        return new_faces;
+    }, |_| false);
+    if result.cancelled {
+        return Err(SolveError::Interrupted {best: None});
     }
-    return (from_previous, d3d_to_face);
+    return Ok((from_previous, d3d_to_face));
 }
 
 fn get_flat_neighbour_faces(map: &Map, current_face: &Point) -> Vec<(Point, Direction)> {
     let mut neighbours: Vec<(Point, Direction)> = Vec::new();
-    for direction in Direction::get_directions() {
+    for direction in Direction::all() {
         let new_flat_position = *current_face + direction.as_vector();
         if map.has_face(&new_flat_position) {
             neighbours.push((new_flat_position, direction));
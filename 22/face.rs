@@ -1,6 +1,12 @@
-use std::collections::HashMap;
+#![allow(
+    clippy::needless_borrow,
+    clippy::redundant_pattern_matching,
+    clippy::wrong_self_convention,
+)]
 
-use space::{Direction,Point,Rotation,StdInt};
+use crate::common::grid::{Bounds,DenseGrid,Grid};
+
+use super::space::{Direction,Point,Rotation,StdInt};
 
 #[derive(PartialEq,Copy,Clone)]
 pub enum Tile {
@@ -9,11 +15,11 @@ pub enum Tile {
 }
 
 impl Tile {
-    pub fn from_char(character: char) -> Self {
+    pub fn from_char(character: char) -> Option<Self> {
         return match character {
-            '#' => Self::Stone,
-            '.' => Self::Clear,
-            other => panic!("'{}' is not a valid tile character", other),
+            '#' => Some(Self::Stone),
+            '.' => Some(Self::Clear),
+            _ => None,
         }
     }
 
@@ -29,7 +35,7 @@ pub type EdgeGlue = (Point, Rotation);
 
 pub struct Face {
     size: StdInt,
-    places: HashMap<Point,Tile>,
+    places: DenseGrid<StdInt,Tile>,
     left: Option<EdgeGlue>,
     right: Option<EdgeGlue>,
     top: Option<EdgeGlue>,
@@ -39,7 +45,7 @@ pub struct Face {
 impl Face {
     pub fn new(size: StdInt) -> Self {
         return Self {
-            places: HashMap::new(), 
+            places: DenseGrid::with_bounds(Bounds::new(Point::new(1, 1), Point::new(size, size))), 
             size: size, 
             left: None, 
             right:None, 
@@ -49,7 +55,7 @@ impl Face {
     }
 
     pub fn add_point(&mut self, point: &Point, tile: &Tile) {
-        self.places.insert(*point, *tile);
+        self.places.set(*point, *tile);
     }
 
     pub fn get_left_glue(&self) -> Option<EdgeGlue> {
@@ -74,19 +80,19 @@ impl Face {
 
     pub fn get_glue_from_direction(&self, direction: &Direction) -> Option<EdgeGlue> {
         return match direction {
-            Direction::Up => self.get_top_glue(),
-            Direction::Down => self.get_bottom_glue(),
-            Direction::Left => self.get_left_glue(),
-            Direction::Right => self.get_right_glue(),
+            Direction::North => self.get_top_glue(),
+            Direction::South => self.get_bottom_glue(),
+            Direction::West => self.get_left_glue(),
+            Direction::East => self.get_right_glue(),
         };
     }
 
     pub fn add_glue(&mut self, other_face: &Point, direction: &Direction, rotation: &Rotation) {
         match direction {
-            Direction::Up => self.add_top_glue(other_face, rotation),
-            Direction::Down => self.add_bottom_glue(other_face, rotation),
-            Direction::Left => self.add_left_glue(other_face, rotation),
-            Direction::Right => self.add_right_glue(other_face, rotation),
+            Direction::North => self.add_top_glue(other_face, rotation),
+            Direction::South => self.add_bottom_glue(other_face, rotation),
+            Direction::West => self.add_left_glue(other_face, rotation),
+            Direction::East => self.add_right_glue(other_face, rotation),
         }
     }
 
@@ -108,7 +114,7 @@ impl Face {
 
     #[allow(dead_code)]
     pub fn is_fully_glued(&self) -> bool {
-        for direction in Direction::get_directions() {
+        for direction in Direction::all() {
             if !self.is_glued_in_direction(&direction) {
                 return false;
             }
@@ -118,7 +124,7 @@ impl Face {
 
     pub fn get_unglued_directions(&self) -> Vec<Direction> {
         let mut unglued: Vec<Direction> = Vec::new();
-        for direction in Direction::get_directions() {
+        for direction in Direction::all() {
             if !self.is_glued_in_direction(&direction) {
                 unglued.push(direction);
             }
@@ -129,10 +135,10 @@ impl Face {
     #[allow(dead_code)]
     pub fn is_glued_in_direction(&self, direction: &Direction) -> bool {
         return match direction {
-            Direction::Up => self.is_top_glued(),
-            Direction::Down => self.is_bottom_glued(),
-            Direction::Left => self.is_left_glued(),
-            Direction::Right => self.is_right_glued(),
+            Direction::North => self.is_top_glued(),
+            Direction::South => self.is_bottom_glued(),
+            Direction::West => self.is_left_glued(),
+            Direction::East => self.is_right_glued(),
         }
     }
 
@@ -169,12 +175,8 @@ impl Face {
     }
 
     pub fn create_copy(&self) -> Self {
-        let mut hashmap_copy: HashMap<Point,Tile> = HashMap::new();
-        for (key, value) in self.places.iter() {
-            hashmap_copy.insert(*key, *value);
-        }
         return Self {
-            places: hashmap_copy, 
+            places: self.places.clone(), 
             size: self.size, 
             left: self.left, 
             right: self.right, 
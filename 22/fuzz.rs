@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::random::Rng;
+
+use super::part2;
+
+type Cell = (i64, i64);
+type Vector = [i64; 3];
+// Which way right, down and out of the cube each face of a net ends up pointing once it's folded
+type Frame = (Vector, Vector, Vector);
+// The map's tiles by column and row from 1, and each distance with the turn after it
+type Notes = (HashMap<Cell, char>, Vec<(i64, char)>);
+
+// East, south, west, north, in the order the password counts them
+const STEPS: [Cell; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+// One of the eleven cube nets, turned or flipped at random, with random walls and a random route
+pub fn generate(rng: &mut Rng) -> (String, Params) {
+    let nets = get_cube_nets();
+    let net = get_transformed(&nets[rng.index(nets.len())], rng.index(8));
+    let face_size = rng.range(2, 8);
+    let wall_chance = rng.next_f64() * 0.25;
+
+    let width = net.iter().map(|cell| cell.0).max().unwrap() + 1;
+    let height = net.iter().map(|cell| cell.1).max().unwrap() + 1;
+    let mut input = String::new();
+    for y in 0..(height * face_size) {
+        let mut row = String::new();
+        for x in 0..(width * face_size) {
+            let is_first = (y == 0) && row.trim().is_empty();
+            row.push(match net.contains(&(x / face_size, y / face_size)) {
+                false => ' ',
+                true if is_first || !rng.chance(wall_chance) => '.',
+                true => '#',
+            });
+        }
+        input += row.trim_end();
+        input += "\n";
+    }
+    input += "\n";
+    input += &rng.range(0, 4 * face_size).to_string();
+    for _ in 0..rng.range(0, 30) {
+        input.push(*rng.choose(&['L', 'R']));
+        input += &rng.range(0, 4 * face_size).to_string();
+    }
+    input += "\n";
+
+    let mut params = Params::new();
+    params.insert("face_size", &face_size.to_string());
+    return (input, params);
+}
+
+// Grows every shape of six squares, keeps the ones that fold up into a cube, and counts the ones
+// that are the same after turning or flipping once
+pub fn get_cube_nets() -> Vec<Vec<Cell>> {
+    let mut shapes: HashSet<Vec<Cell>> = HashSet::from([vec![(0, 0)]]);
+    for _ in 1..6 {
+        let mut bigger: HashSet<Vec<Cell>> = HashSet::new();
+        for shape in &shapes {
+            for cell in shape {
+                for step in STEPS {
+                    let next = (cell.0 + step.0, cell.1 + step.1);
+                    if !shape.contains(&next) {
+                        bigger.insert(get_normalised([shape.clone(), vec![next]].concat()));
+                    }
+                }
+            }
+        }
+        shapes = bigger;
+    }
+    let nets: BTreeSet<Vec<Cell>> = shapes.iter().filter(|shape| fold(shape).is_some()).map(|shape| {
+        return (0..8).map(|transform| get_transformed(shape, transform)).min().unwrap();
+    }).collect();
+    return nets.into_iter().collect();
+}
+
+// The first four transforms are quarter turns and the last four are the same after a flip
+fn get_transformed(cells: &[Cell], transform: usize) -> Vec<Cell> {
+    return get_normalised(cells.iter().map(|&(x, y)| {
+        let (x, y) = if transform >= 4 {(-x, y)} else {(x, y)};
+        return match transform % 4 {
+            0 => (x, y),
+            1 => (-y, x),
+            2 => (-x, -y),
+            _ => (y, -x),
+        };
+    }).collect());
+}
+
+// Moved to start at 0, 0 and sorted into reading order
+fn get_normalised(mut cells: Vec<Cell>) -> Vec<Cell> {
+    let min_x = cells.iter().map(|cell| cell.0).min().unwrap();
+    let min_y = cells.iter().map(|cell| cell.1).min().unwrap();
+    cells = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+    cells.sort_by_key(|&(x, y)| (y, x));
+    return cells;
+}
+
+// Folds the other faces up around the first one, which faces out of the page. Crossing an edge
+// turns the direction of travel into the outward direction of the next face.
+fn fold(cells: &[Cell]) -> Option<HashMap<Cell, Frame>> {
+    let mut frames: HashMap<Cell, Frame> = HashMap::from([(cells[0], ([1, 0, 0], [0, 1, 0], [0, 0, 1]))]);
+    let mut queue: Vec<Cell> = vec![cells[0]];
+    while let Some(cell) = queue.pop() {
+        let (right, down, out) = frames[&cell];
+        for (step, frame) in [
+            ((1, 0), (negate(out), down, right)),
+            ((0, 1), (right, negate(out), down)),
+            ((-1, 0), (out, down, negate(right))),
+            ((0, -1), (right, out, negate(down))),
+        ] {
+            let next = (cell.0 + step.0, cell.1 + step.1);
+            if cells.contains(&next) && !frames.contains_key(&next) {
+                frames.insert(next, frame);
+                queue.push(next);
+            }
+        }
+    }
+    let outs: HashSet<Vector> = frames.values().map(|frame| frame.2).collect();
+    return if (cells.len() == 6) && (outs.len() == 6) {Some(frames)} else {None};
+}
+
+fn negate(a: Vector) -> Vector {
+    return [-a[0], -a[1], -a[2]];
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    return [a[0] + b[0], a[1] + b[1], a[2] + b[2]];
+}
+
+fn scale(a: Vector, factor: i64) -> Vector {
+    return [a[0] * factor, a[1] * factor, a[2] * factor];
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    return [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+}
+
+// Part 1 wraps by looking back along the row or column, and part 2 walks around an actual cube
+pub fn brute_force(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    let face_size: i64 = params.value(&part2::FACE_SIZE)?;
+    let (tiles, instructions) = read_notes(input)?;
+    let start = *tiles.keys().filter(|&&(x, y)| (y == 1) && (tiles[&(x, y)] == '.')).min_by_key(|&&(x, _)| x)
+        .ok_or(SolveError::BadInput("the top row has no open tiles".to_string()))?;
+    let (position, facing) = match part {
+        Part::One => walk_flat(&tiles, &instructions, start),
+        Part::Two => walk_cube(&tiles, &instructions, start, face_size)?,
+    };
+    return Ok(Answer::from(1000 * position.1 + 4 * position.0 + facing as i64));
+}
+
+fn walk_flat(tiles: &HashMap<Cell, char>, instructions: &[(i64, char)], start: Cell) -> (Cell, usize) {
+    let (mut position, mut facing) = (start, 0);
+    for &(distance, turn) in instructions {
+        for _ in 0..distance {
+            let step = STEPS[facing];
+            let mut next = (position.0 + step.0, position.1 + step.1);
+            if !tiles.contains_key(&next) {
+                next = position;
+                while tiles.contains_key(&(next.0 - step.0, next.1 - step.1)) {
+                    next = (next.0 - step.0, next.1 - step.1);
+                }
+            }
+            if tiles[&next] == '#' {
+                break;
+            }
+            position = next;
+        }
+        facing = match turn {
+            'R' => (facing + 1) % 4,
+            'L' => (facing + 3) % 4,
+            _ => facing,
+        };
+    }
+    return (position, facing);
+}
+
+// Tiles are kept as their centres on a cube with sides 2 * face_size long, centred on the origin,
+// so every centre and every step is a whole number
+fn walk_cube(tiles: &HashMap<Cell, char>, instructions: &[(i64, char)], start: Cell, face_size: i64) -> Result<(Cell, usize), SolveError> {
+    let cells: Vec<Cell> = get_normalised(tiles.keys().map(|&(x, y)| ((x - 1) / face_size, (y - 1) / face_size)).collect::<HashSet<Cell>>().into_iter().collect());
+    let offset = (tiles.keys().map(|&(x, _)| (x - 1) / face_size).min().unwrap(), tiles.keys().map(|&(_, y)| (y - 1) / face_size).min().unwrap());
+    let frames = fold(&cells).ok_or(SolveError::BadInput("the map doesn't fold up into a cube".to_string()))?;
+
+    let mut flat: HashMap<Vector, (Cell, Frame)> = HashMap::new();
+    let mut centres: HashMap<Cell, (Vector, Vector)> = HashMap::new();
+    for (&cell, &(right, down, out)) in &frames {
+        for j in 0..face_size {
+            for i in 0..face_size {
+                let centre = add(scale(out, face_size), add(scale(right, 2 * i - (face_size - 1)), scale(down, 2 * j - (face_size - 1))));
+                let position = ((cell.0 + offset.0) * face_size + i + 1, (cell.1 + offset.1) * face_size + j + 1);
+                flat.insert(centre, (position, (right, down, out)));
+                centres.insert(position, (centre, out));
+            }
+        }
+    }
+
+    let (mut centre, mut out) = centres[&start];
+    let mut direction = flat[&centre].1.0;
+    for &(distance, turn) in instructions {
+        for _ in 0..distance {
+            let (mut next, mut next_out, mut next_direction) = (add(centre, scale(direction, 2)), out, direction);
+            if !flat.contains_key(&next) {
+                next = add(add(centre, direction), negate(out));
+                (next_out, next_direction) = (direction, negate(out));
+            }
+            if tiles[&flat[&next].0] == '#' {
+                break;
+            }
+            (centre, out, direction) = (next, next_out, next_direction);
+        }
+        direction = match turn {
+            'R' => cross(out, direction),
+            'L' => cross(direction, out),
+            _ => direction,
+        };
+    }
+    let (position, (right, down, _)) = flat[&centre];
+    let facing = [right, down, negate(right), negate(down)].iter().position(|&way| way == direction).unwrap();
+    return Ok((position, facing));
+}
+
+fn read_notes(input: &str) -> Result<Notes, SolveError> {
+    let (map, route) = input.split_once("\n\n").ok_or(SolveError::BadInput("expected a blank line between the map and the route".to_string()))?;
+    let mut tiles: HashMap<Cell, char> = HashMap::new();
+    for (y, row) in map.lines().enumerate() {
+        for (x, tile) in row.chars().enumerate().filter(|&(_, tile)| tile != ' ') {
+            if (tile != '.') && (tile != '#') {
+                return Err(SolveError::BadInput(format!("'{}' isn't a tile", tile)));
+            }
+            tiles.insert((x as i64 + 1, y as i64 + 1), tile);
+        }
+    }
+    let mut instructions: Vec<(i64, char)> = Vec::new();
+    let route = route.trim();
+    let mut rest = route;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let distance = rest[..digits].parse::<i64>().map_err(|_| SolveError::BadInput(format!("'{}' should be a distance then a turn", rest)))?;
+        let turn = rest[digits..].chars().next().unwrap_or(' ');
+        instructions.push((distance, turn));
+        rest = rest[digits..].get(1..).unwrap_or("");
+    }
+    return Ok((tiles, instructions));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn there_are_eleven_nets() {
+        assert_eq!(get_cube_nets().len(), 11);
+        // A row of four with one square on each side folds up; a row of five doesn't
+        assert!(fold(&get_normalised(vec![(0, 1), (1, 1), (2, 1), (3, 1), (1, 0), (2, 2)])).is_some());
+        assert!(fold(&get_normalised(vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (0, 1)])).is_none());
+    }
+
+    #[test]
+    fn brute_force_example() {
+        let example = include_str!("../examples/22.txt");
+        let mut params = Params::new();
+        params.insert("face_size", "4");
+        assert_eq!(brute_force(Part::One, example, &params), Ok(Answer::from(6032)));
+        assert_eq!(brute_force(Part::Two, example, &params), Ok(Answer::from(5031)));
+    }
+
+    #[test]
+    fn solver_matches_brute_force() {
+        for seed in 0..20 {
+            let (input, params) = generate(&mut Rng::new(seed));
+            for part in [Part::One, Part::Two] {
+                assert_eq!(super::super::solve_with_params(part, &input, &params), brute_force(part, &input, &params), "seed {} part {}\n{}", seed, part, input);
+            }
+        }
+    }
+}
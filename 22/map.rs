@@ -1,31 +1,34 @@
+#![allow(clippy::needless_borrow)]
+
 use std::collections::HashMap;
-use std::io::stdout;
-use std::io::Write;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use space::{Direction,Marker,Point,Rotation,StdInt};
-use face::{EdgeGlue,Face,Tile};
+use crate::common::grid::{Bounds,Grid,SparseGrid};
+use crate::common::render::{Cell,Colour,Render,RenderOptions,ScreenPoint};
+use crate::common::simulation::Simulation;
+
+use super::space::{Direction,Marker,Point,Rotation,StdInt};
+use super::face::{EdgeGlue,Face,Tile};
 
 
 pub struct Map {
-    faces: HashMap<Point, Rc<RefCell<Face>>>,
+    faces: SparseGrid<StdInt, Rc<RefCell<Face>>>,
     face_size: StdInt,
-    max_x: Option<StdInt>,
-    max_y: Option<StdInt>,
+    bounds: Option<Bounds<StdInt>>,
 }
 
 impl Map {
     pub fn new(size: StdInt) -> Self {
-        return Self {faces: HashMap::new(), face_size: size, max_x: None, max_y: None};
+        return Self {faces: SparseGrid::new(), face_size: size, bounds: None};
     }
 
     pub fn get_max_x(&self) -> Option<StdInt> {
-        return self.max_x
+        return self.bounds.map(|bounds| bounds.max.x);
     }
 
     pub fn get_max_y(&self) -> Option<StdInt> {
-        return self.max_y
+        return self.bounds.map(|bounds| bounds.max.y);
     }
 
     pub fn find_face(&self, point: &Point) -> Point {
@@ -33,12 +36,14 @@ impl Map {
     }
 
     pub fn has_face(&self, face: &Point) -> bool {
-        return self.faces.contains_key(face);
+        return self.faces.contains(face);
     }
 
     pub fn bidirectional_glue_faces(&mut self, face1: &Point, face2: &Point, direction: &Direction, rotation: &Rotation) {
         self.glue_faces(face1, face2, direction, rotation);
-        self.glue_faces(face2, face1, &direction.inverse(), &rotation.inverse());
+        // Crossing a turned edge turns the marker too, so the way back is over the edge it ends up facing away from
+        let return_direction = direction.rotate(&rotation.inverse()).inverse();
+        self.glue_faces(face2, face1, &return_direction, &rotation.inverse());
     }
 
     pub fn glue_faces(&mut self, face1: &Point, face2: &Point, direction: &Direction, rotation: &Rotation) {
@@ -74,16 +79,16 @@ impl Map {
         let point_on_face: Point = self.find_point_on_face(point);
         self.add_point_to_face(&face, &point_on_face, tile);
 
-        if (self.max_x == None) || (point.x > self.max_x.unwrap()) {
-            self.max_x = Some(point.x);
-        }
-        if (self.max_y == None) || (point.y > self.max_y.unwrap()) {
-            self.max_y = Some(point.y);
-        }
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(point),
+            None => self.bounds = Some(Bounds::from_point(point)),
+        };
     }
 
     pub fn add_face(&mut self, face: &Point) {
-        self.faces.entry(*face).or_insert(Rc::new(RefCell::new(Face::new(self.face_size))));
+        if !self.faces.contains(face) {
+            self.faces.set(*face, Rc::new(RefCell::new(Face::new(self.face_size))));
+        }
     }
 
     pub fn add_point_to_face(&mut self, face: &Point, point_on_face: &Point, tile: &Tile) {
@@ -110,7 +115,26 @@ impl Map {
         return current_marker;
     }
 
-    #[allow(dead_code)]
+    // Follows every instruction, remembering which way the marker last faced on each tile it visited
+    pub fn follow_instructions_with_trail(&self, instructions: &[String], marker: Marker) -> (Marker, HashMap<Point,Direction>) {
+        let mut current_marker: Marker = marker;
+        let mut trail: HashMap<Point,Direction> = HashMap::new();
+        for instruction in instructions.iter() {
+            match instruction.parse::<StdInt>() {
+                Ok(distance) => {
+                    let (new_marker, new_trail) = self.get_new_position_with_trail(&current_marker, distance);
+                    current_marker = new_marker;
+                    trail.extend(new_trail);
+                },
+                Err(_) => {
+                    current_marker = current_marker.get_rotated_marker(&instruction.parse::<Rotation>().unwrap());
+                    trail.insert(current_marker.get_position(), current_marker.get_direction());
+                },
+            };
+        }
+        return (current_marker, trail);
+    }
+
     pub fn get_new_position_with_trail(&self, marker: &Marker, distance: StdInt) -> (Marker, HashMap<Point,Direction>) {
         let mut current_marker: Marker = *marker;
         let mut trail: HashMap<Point,Direction> = HashMap::new();
@@ -164,10 +188,10 @@ impl Map {
 
     fn get_unrotated_position_on_other_glued_edge(&self, current_point_on_face: &Point, direction: &Direction) -> Point {
         return match direction {
-            Direction::Up => Point::new(current_point_on_face.x, self.face_size),
-            Direction::Down => Point::new(current_point_on_face.x, 1),
-            Direction::Left => Point::new(self.face_size, current_point_on_face.y),
-            Direction::Right => Point::new(1, current_point_on_face.y),
+            Direction::North => Point::new(current_point_on_face.x, self.face_size),
+            Direction::South => Point::new(current_point_on_face.x, 1),
+            Direction::West => Point::new(self.face_size, current_point_on_face.y),
+            Direction::East => Point::new(1, current_point_on_face.y),
         };
     }
 
@@ -190,70 +214,169 @@ impl Map {
     }
 
     #[allow(dead_code)]
-    pub fn render_map(&self) {
-        for j in 1..=self.max_y.unwrap() {
-            for i in 1..=self.max_x.unwrap() {
-                let this_point: Point = Point::new(i, j);
-                match self.get_tile(&this_point) {
-                    Some(tile) => print!("{}", tile.to_char()),
-                    None => print!(" "),
-                };
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-        }
-        println!("");
+    pub fn render_map(&self) -> String {
+        return MapView::new(self).render_to_string(&RenderOptions::default());
     }
 
     #[allow(dead_code)]
-    pub fn render_map_with_marker(&self, marker: &Marker) {
-        let marker_pos = marker.get_position();
-        for j in 1..=self.max_y.unwrap() {
-            for i in 1..=self.max_x.unwrap() {
-                let this_point: Point = Point::new(i, j);
-                if this_point == marker_pos {
-                    print!("{}", marker.get_direction().as_char());
-                }
-                else {
-                    match self.get_tile(&this_point) {
-                        Some(tile) => print!("{}", tile.to_char()),
-                        None => print!(" "),
-                    };
-                }
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-        }
-        println!("");
+    pub fn render_map_with_marker(&self, marker: &Marker) -> String {
+        return MapView::new(self).with_marker(*marker).render_to_string(&RenderOptions::default());
     }
 
     #[allow(dead_code)]
-    pub fn render_map_with_trail(&self, trail: &HashMap<Point,Direction>) {
-        for j in 1..=self.max_y.unwrap() {
-            for i in 1..=self.max_x.unwrap() {
-                let this_point: Point = Point::new(i, j);
-                if trail.contains_key(&this_point) {
-                    print!("{}", trail.get(&this_point).unwrap().as_char());
-                }
-                else {
-                    match self.get_tile(&this_point) {
-                        Some(tile) => print!("{}", tile.to_char()),
-                        None => print!(" "),
-                    };
-                }
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-        }
-        println!("");
+    pub fn render_map_with_trail(&self, trail: &HashMap<Point,Direction>) -> String {
+        return MapView::new(self).with_trail(trail).render_to_string(&RenderOptions::default());
     }
 
     pub fn create_copy(&self) -> Self {
-        let mut hashmap_copy: HashMap<Point, Rc<RefCell<Face>>> = HashMap::new();
+        let mut faces_copy: SparseGrid<StdInt, Rc<RefCell<Face>>> = SparseGrid::new();
         for (key, value) in self.faces.iter() {
             let new_value: Rc<RefCell<Face>> = Rc::new(RefCell::new(value.as_ref().borrow().create_copy()));
-            hashmap_copy.insert(*key, new_value);
+            faces_copy.set(key, new_value);
+        }
+        return Self {faces: faces_copy, face_size: self.face_size, bounds: self.bounds};
+    }
+}
+// The map, optionally with the marker and the trail it's left drawn over it
+pub struct MapView<'a> {
+    map: &'a Map,
+    marker: Option<Marker>,
+    trail: Option<&'a HashMap<Point,Direction>>,
+}
+
+impl<'a> MapView<'a> {
+    pub fn new(map: &'a Map) -> Self {
+        return Self {map: map, marker: None, trail: None};
+    }
+
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = Some(marker);
+        return self;
+    }
+
+    pub fn with_trail(mut self, trail: &'a HashMap<Point,Direction>) -> Self {
+        self.trail = Some(trail);
+        return self;
+    }
+}
+
+impl Render for MapView<'_> {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        let max_point = Point::new(self.map.get_max_x()?, self.map.get_max_y()?);
+        return Some(Bounds::new(Point::new(1, 1), max_point).to_i64());
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let this_point = Point::from_i64(point);
+        if let Some(marker) = self.marker.filter(|marker| marker.get_position() == this_point) {
+            return Cell::new(marker.get_direction().as_char(), Colour::Red);
+        }
+        if let Some(direction) = self.trail.and_then(|trail| trail.get(&this_point)) {
+            return Cell::new(direction.as_char(), Colour::Yellow);
         }
-        return Self {faces: hashmap_copy, face_size: self.face_size, max_x: self.max_x, max_y: self.max_y};
+        return match self.map.get_tile(&this_point) {
+            Some(tile @ Tile::Stone) => Cell::new(tile.to_char(), Colour::White),
+            Some(tile @ Tile::Clear) => Cell::new(tile.to_char(), Colour::Grey),
+            None => Cell::plain(' '),
+        };
+    }
+}
+
+impl Render for Map {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return MapView::new(self).render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return MapView::new(self).cell_at(point);
+    }
+}
+
+// One step is one square moved or one turn made, leaving the trail behind the marker
+pub struct MarkerSimulation {
+    map: Map,
+    instructions: Vec<String>,
+    initial_marker: Marker,
+    marker: Marker,
+    trail: HashMap<Point,Direction>,
+    next_instruction: usize,
+    distance_left: StdInt,
+}
+
+impl MarkerSimulation {
+    pub fn new(map: Map, instructions: Vec<String>, marker: Marker) -> Self {
+        let trail = HashMap::from([(marker.get_position(), marker.get_direction())]);
+        return Self {
+            map: map,
+            instructions: instructions,
+            initial_marker: marker,
+            marker: marker,
+            trail: trail,
+            next_instruction: 0,
+            distance_left: 0,
+        };
+    }
+}
+
+impl Render for MarkerSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return MapView::new(&self.map).render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return MapView::new(&self.map).with_trail(&self.trail).with_marker(self.marker).cell_at(point);
     }
-}
\ No newline at end of file
+}
+
+impl Simulation for MarkerSimulation {
+    fn step(&mut self) -> bool {
+        if self.distance_left == 0 {
+            let instruction = match self.instructions.get(self.next_instruction) {
+                Some(instruction) => instruction,
+                None => return false,
+            };
+            self.next_instruction += 1;
+            match instruction.parse::<StdInt>() {
+                Ok(distance) => self.distance_left = distance,
+                Err(_) => {
+                    self.marker = self.marker.get_rotated_marker(&instruction.parse::<Rotation>().unwrap());
+                    self.trail.insert(self.marker.get_position(), self.marker.get_direction());
+                    return true;
+                },
+            }
+            if self.distance_left == 0 {
+                return true;
+            }
+        }
+        self.marker = self.map.attempt_move(&self.marker);
+        self.distance_left -= 1;
+        self.trail.insert(self.marker.get_position(), self.marker.get_direction());
+        return true;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.map.create_copy(), self.instructions.clone(), self.initial_marker);
+    }
+
+    fn status(&self) -> String {
+        let instruction = match self.next_instruction.checked_sub(1) {
+            Some(i) => format!("instruction {} of {} ({})", i + 1, self.instructions.len(), self.instructions[i]),
+            None => format!("{} instructions to follow", self.instructions.len()),
+        };
+        return format!("Marker at {}, {} squares left to move, {}", self.marker, self.distance_left, instruction);
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let point = Point::from_i64(point);
+        let tile = match self.map.get_tile(&point) {
+            Some(Tile::Stone) => "a wall",
+            Some(Tile::Clear) => "open",
+            None => return format!("{}: off the map", point),
+        };
+        let visited = match self.trail.get(&point) {
+            Some(direction) => format!(", last crossed facing {}", direction.as_char()),
+            None => String::new(),
+        };
+        return format!("{} on the face at {}: {}{}", point, self.map.find_face(&point), tile, visited);
+    }
+}
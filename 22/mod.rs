@@ -0,0 +1,71 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+
+#[path = "22_part1.rs"]
+pub mod part1;
+#[path = "22_part2.rs"]
+pub mod part2;
+
+pub mod fuzz;
+
+mod space;
+mod space3d;
+mod face;
+mod map;
+mod reading_input;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => part2::PARAMS,
+    };
+}
+
+pub fn picture(part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return match part {
+        Part::One => part1::picture(input, params),
+        Part::Two => part2::picture(input, params),
+    };
+}
+
+pub fn simulation(part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return match part {
+        Part::One => part1::simulation(input, params),
+        Part::Two => part2::simulation(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/22.txt");
+
+    #[test]
+    fn part1_example() {
+        let mut params = Params::new();
+        params.insert("face_size", "4");
+        assert_eq!(solve_with_params(Part::One, EXAMPLE, &params), Ok(Answer::from(6032)));
+    }
+
+    #[test]
+    fn part2_example() {
+        let mut params = Params::new();
+        params.insert("face_size", "4");
+        assert_eq!(solve_with_params(Part::Two, EXAMPLE, &params), Ok(Answer::from(5031)));
+    }
+}
@@ -1,26 +1,32 @@
+#![allow(clippy::len_zero, clippy::partialeq_to_none)]
+
 use std::collections::HashSet;
 
-use space::{Direction,Marker,Point,StdInt};
-use face::Tile;
-use map::Map;
+use crate::common::parse::{lines, ParseError};
+
+use super::space::{Direction,Marker,Point,StdInt};
+use super::face::Tile;
+use super::map::Map;
 
-pub fn get_input_data(input: String, size: StdInt) -> (Map, Vec<String>, Marker) {
+pub fn get_input_data(input: &str, size: StdInt) -> Result<(Map, Vec<String>, Marker), ParseError> {
     let digits: HashSet<char> = HashSet::from(['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']);
     let mut map: Map = Map::new(size); 
     let mut initial_point: Option<Point> = None;
     let mut read_map_state = true;
     let mut instructions: Vec<String> = Vec::new();
-    for (i, line) in input.lines().enumerate() {
-        if line.trim() == "" {
+    for line in lines(input) {
+        let i = line.number - 1;
+        if line.text.trim() == "" {
             read_map_state = false;
             continue;
         }
         else if read_map_state {
-            for (j, tile_char) in line.chars().enumerate() {
+            for (j, tile_char) in line.text.chars().enumerate() {
                 if tile_char == ' ' {
                     continue;
                 }
-                let this_tile = Tile::from_char(tile_char);
+                let this_tile = Tile::from_char(tile_char)
+                    .ok_or(line.error_at(j + 1, &tile_char.to_string(), "a tile ('.', '#' or ' ')"))?;
                 let this_point = Point::new(j as StdInt + 1, i as StdInt + 1);
                 map.add_point(&this_point, &this_tile);
 
@@ -31,20 +37,26 @@ pub fn get_input_data(input: String, size: StdInt) -> (Map, Vec<String>, Marker)
         }
         else {
             let mut curr_string: String = "".to_string();
-            for instruction_char in line.chars() {
+            for (j, instruction_char) in line.text.chars().enumerate() {
                 if digits.contains(&instruction_char) {
                     curr_string.push(instruction_char);
                 }
-                else {
-                    instructions.push(curr_string);
+                else if (instruction_char == 'L') || (instruction_char == 'R') {
+                    if curr_string.len() > 0 {
+                        instructions.push(curr_string);
+                    }
                     instructions.push(instruction_char.to_string());
                     curr_string = "".to_string();
                 }
+                else {
+                    return Err(line.error_at(j + 1, &instruction_char.to_string(), "a distance or a turn ('L' or 'R')"));
+                }
             }
             if curr_string.len() > 0 {
                 instructions.push(curr_string);
             }
         }
     }
-    return (map, instructions, Marker::new(initial_point.unwrap(), Direction::Right));
+    let initial_point = initial_point.ok_or(ParseError::whole_input("no open tiles", "a map with at least one '.'"))?;
+    return Ok((map, instructions, Marker::new(initial_point, Direction::East)));
 }
@@ -1,5 +1,5 @@
 use std::ops::{Mul,Neg};
-use space::{Direction,Rotation};
+use super::space::{Direction,Rotation};
 
 #[derive(Debug,Copy,Clone,Hash,PartialEq,Eq)]
 pub enum Direction3D {
@@ -22,10 +22,10 @@ impl Direction3D {
 
     pub fn from2d_as_face_direction_rel_z(direction: &Direction) -> Self {
         return match direction {
-            Direction::Up => Self::Y,
-            Direction::Down => Self::NegY,
-            Direction::Right => Self::X,
-            Direction::Left => Self::NegX,
+            Direction::North => Self::Y,
+            Direction::South => Self::NegY,
+            Direction::East => Self::X,
+            Direction::West => Self::NegX,
         };
     }
 
@@ -96,10 +96,10 @@ impl Orientation {
     pub fn new() -> Self {
         return Self{
             face: Direction3D::Z,
-            left: Direction3D::from2d_as_face_direction_rel_z(&Direction::Left),
-            right: Direction3D::from2d_as_face_direction_rel_z(&Direction::Right),
-            up: Direction3D::from2d_as_face_direction_rel_z(&Direction::Up),
-            down: Direction3D::from2d_as_face_direction_rel_z(&Direction::Down),
+            left: Direction3D::from2d_as_face_direction_rel_z(&Direction::West),
+            right: Direction3D::from2d_as_face_direction_rel_z(&Direction::East),
+            up: Direction3D::from2d_as_face_direction_rel_z(&Direction::North),
+            down: Direction3D::from2d_as_face_direction_rel_z(&Direction::South),
         }
     }
 
@@ -115,10 +115,10 @@ impl Orientation {
 
     pub fn get_rotation_axis_for_direction(&self, direction: &Direction) -> Direction3D {
         return match direction {
-            Direction::Up => self.left,
-            Direction::Down => self.right,
-            Direction::Left => self.down,
-            Direction::Right => self.up,
+            Direction::North => self.left,
+            Direction::South => self.right,
+            Direction::West => self.down,
+            Direction::East => self.up,
         };
     }
 
@@ -129,3 +129,61 @@ impl Orientation {
 
 
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AXES: [Direction3D; 6] = [Direction3D::X, Direction3D::NegX, Direction3D::Y, Direction3D::NegY, Direction3D::Z, Direction3D::NegZ];
+
+    #[test]
+    fn cross_product_is_right_handed() {
+        assert_eq!(Direction3D::X * Direction3D::Y, Direction3D::Z);
+        assert_eq!(Direction3D::Y * Direction3D::Z, Direction3D::X);
+        assert_eq!(Direction3D::Z * Direction3D::X, Direction3D::Y);
+        assert_eq!(Direction3D::Y * Direction3D::X, Direction3D::NegZ);
+    }
+
+    #[test]
+    fn cross_product_is_anticommutative_and_zero_for_parallel_axes() {
+        for a in AXES {
+            for b in AXES {
+                if (a == b) || (a == -b) {
+                    assert_eq!(a * b, Direction3D::Zero);
+                }
+                else {
+                    assert_eq!(a * b, -(b * a));
+                }
+            }
+            assert_eq!(a * Direction3D::Zero, Direction3D::Zero);
+        }
+    }
+
+    #[test]
+    fn rotating_around_an_axis_leaves_the_axis_alone() {
+        for axis in AXES {
+            assert_eq!(axis.rotate_around(&axis), axis);
+            assert_eq!((-axis).rotate_around(&axis), -axis);
+        }
+        assert_eq!(Direction3D::Y.rotate_around(&Direction3D::X), Direction3D::Z);
+    }
+
+    #[test]
+    fn four_quarter_turns_are_the_identity() {
+        let mut orientation = Orientation::new();
+        for _ in 0..4 {
+            orientation = orientation.rotate(&Direction3D::X);
+        }
+        assert_eq!(orientation.get_face_direction(), Direction3D::Z);
+        assert_eq!(orientation.get_rotation_axis_for_direction(&Direction::North), Direction3D::NegX);
+    }
+
+    #[test]
+    fn tangent_changes_on_the_z_face() {
+        assert_eq!(Direction3D::get_2d_rotation_from_tangent_change_on_z_face(&Direction3D::Y, &Direction3D::X), Rotation::Right);
+        assert_eq!(Direction3D::get_2d_rotation_from_tangent_change_on_z_face(&Direction3D::X, &Direction3D::Y), Rotation::Left);
+        assert_eq!(Direction3D::get_2d_rotation_from_tangent_change_on_z_face(&Direction3D::Y, &Direction3D::NegZ), Rotation::None);
+        assert_eq!(Direction3D::get_2d_rotation_from_tangent_change_on_z_face(&Direction3D::Y, &Direction3D::Z), Rotation::Half);
+    }
+}
@@ -1,5 +1,5 @@
 use std::fs;
-use space::{Marker};
+use super::space::{Marker};
 
 pub fn write_steps(steps: Vec<Marker>, file: &String) {
     let mut body: String = "".to_string();
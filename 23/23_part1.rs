@@ -1,18 +1,44 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, ParseError};
+use crate::common::recorder;
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+use crate::log_info;
 
-mod space;
-use space::{Point,StdInt};
-mod elves;
-use elves::Elves;
+use super::space::{Point,StdInt};
+use super::elves::{Elves, ElvesSimulation};
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut elves = read_elves(input)?;
+    log_info!("Input read. Simulating elf moves...");
 
+    recorder::frame(&elves);
+    for _ in 0..10 {
+        elves.run_round();
+        recorder::frame(&elves);
+    }
+    recorder::last_frame(&elves);
+
+    return Ok(Answer::from(elves.count_empty_tiles()));
+}
+
+// The spread after the ten rounds
+pub fn picture(input: &str, _params: &Params) -> Result<Picture, SolveError> {
+    let mut elves = read_elves(input)?;
+    for _ in 0..10 {
+        elves.run_round();
+    }
+    return Ok(Picture::capture(&elves));
+}
+
+pub fn simulation(input: &str, _params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return Ok(Box::new(ElvesSimulation::new(read_elves(input)?, Some(10))));
+}
+
+
+fn read_elves(input: &str) -> Result<Elves, ParseError> {
+    check_chars(input, |c| (c == '.') || (c == '#') || c.is_whitespace(), "'.' or '#'")?;
     let mut elves = Elves::new();
     for (i, line) in input.lines().enumerate() {
         for (j, tile_char) in line.trim().chars().enumerate() {
@@ -21,11 +47,5 @@ fn main() {
             elves.add_elf(&this_point);
         }
     }
-    println!("Input read. Simulating elf moves...");
-
-    for _ in 0..10 {
-        elves.run_round();
-    }
-
-    println!("Number of empty tiles in bounding box: {}", elves.count_empty_tiles());
-} 
+    return Ok(elves);
+}
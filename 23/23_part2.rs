@@ -1,18 +1,43 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::parse::{check_chars, ParseError};
+use crate::common::recorder;
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+use crate::log_info;
 
-mod space;
-use space::{Point,StdInt};
-mod elves;
-use elves::Elves;
+use super::space::{Point,StdInt};
+use super::elves::{Elves, ElvesSimulation};
 
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
+pub fn solve(input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let mut elves = read_elves(input)?;
+    log_info!("Input read. Simulating elf moves...");
 
+    let mut at_stationary_state = false;
+    let mut num_rounds = 0;
+    while !at_stationary_state  {
+        recorder::frame(&elves);
+        at_stationary_state = elves.run_round();
+        num_rounds += 1;
+    }
+    recorder::last_frame(&elves);
+    return Ok(Answer::from(num_rounds));
+}
+
+// The spread once no elf wants to move
+pub fn picture(input: &str, _params: &Params) -> Result<Picture, SolveError> {
+    let mut elves = read_elves(input)?;
+    while !elves.run_round() {}
+    return Ok(Picture::capture(&elves));
+}
+
+pub fn simulation(input: &str, _params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return Ok(Box::new(ElvesSimulation::new(read_elves(input)?, None)));
+}
+
+
+fn read_elves(input: &str) -> Result<Elves, ParseError> {
+    check_chars(input, |c| (c == '.') || (c == '#') || c.is_whitespace(), "'.' or '#'")?;
     let mut elves = Elves::new();
     for (i, line) in input.lines().enumerate() {
         for (j, tile_char) in line.trim().chars().enumerate() {
@@ -21,13 +46,5 @@ fn main() {
             elves.add_elf(&this_point);
         }
     }
-    println!("Input read. Simulating elf moves...");
-
-    let mut at_stationary_state = false;
-    let mut num_rounds = 0;
-    while !at_stationary_state  {
-        at_stationary_state = elves.run_round();
-        num_rounds += 1;
-    }
-    println!("Number of rounds to get to stationary state: {}", num_rounds);
-} 
+    return Ok(elves);
+}
@@ -1,48 +1,27 @@
-use std::io::stdout;
+#![allow(clippy::len_zero, clippy::map_entry, clippy::needless_borrow)]
+
 use std::collections::{HashMap,HashSet,VecDeque};
-use std::io::Write;
-use space::{Point,Direction,StdInt};
+use crate::common::grid::{Bounds,Grid,SparseGrid};
+use crate::common::render::{Cell,Colour,Render,RenderOptions,ScreenPoint};
+use crate::common::simulation::Simulation;
+use super::space::{Point,Direction,Direction8,StdInt};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Elves {
-    map: HashSet<Point>,
+    map: SparseGrid<StdInt,()>,
     check_order: VecDeque<Direction>,
-    min_x: Option<StdInt>,
-    max_x: Option<StdInt>,
-    min_y: Option<StdInt>,
-    max_y: Option<StdInt>,
 }
 
 impl Elves {
     pub fn new() -> Self {
         return Self{
-            map: HashSet::new(), 
+            map: SparseGrid::new(), 
             check_order: VecDeque::from([Direction::North, Direction::South, Direction::West, Direction::East]),
-            min_x: None,
-            max_x: None,
-            min_y: None,
-            max_y: None,
         }
     }
 
     pub fn add_elf(&mut self, elf: &Point) {
-        self.map.insert(*elf);
-        self.update_bounds(elf);
-    }
-
-    pub fn update_bounds(&mut self, new_point: &Point) {
-        if (self.min_x == None) || (self.min_x.unwrap() > new_point.x) {
-            self.min_x = Some(new_point.x);
-        } 
-        if (self.max_x == None) || (self.max_x.unwrap() < new_point.x) {
-            self.max_x = Some(new_point.x);
-        } 
-        if (self.min_y == None) || (self.min_y.unwrap() > new_point.y) {
-            self.min_y = Some(new_point.y);
-        } 
-        if (self.max_y == None) || (self.max_y.unwrap() < new_point.y) {
-            self.max_y = Some(new_point.y);
-        } 
+        self.map.set(*elf, ());
     }
 
     pub fn run_round(&mut self) -> bool {
@@ -58,10 +37,10 @@ impl Elves {
     pub fn get_accepted_proposals(&self) -> HashMap<Point,Point> {
         let mut proposals: HashMap<Point,Point> = HashMap::new();
         let mut counts: HashMap<Point,u32> = HashMap::new();
-        for elf in &self.map {
+        for elf in self.map.points() {
             let this_proposal = self.get_proposal_for_elf(&elf);
             if let Some(proposed_point) = this_proposal {
-                proposals.insert(*elf, proposed_point);
+                proposals.insert(elf, proposed_point);
                 if !counts.contains_key(&proposed_point) {
                     counts.insert(proposed_point, 0);
                 }
@@ -84,17 +63,17 @@ impl Elves {
         if neighbours.len() == 0 {return None;}
 
         for direction in &self.check_order {
-            let all_directions = direction.get_directionlies();
-            let directions_as_vec: Vec<Point> = all_directions.iter().map(|x| Point::from_direction(&x)).collect();
+            let all_directions = direction.with_diagonals();
+            let directions_as_vec: Vec<Point> = all_directions.iter().map(|x| x.as_vector()).collect();
             let direction_empty = directions_as_vec.iter().map(|x| !neighbours.contains(&x)).reduce(|x, y| x & y).unwrap();
-            if direction_empty {return Some(*elf + Point::from_direction(&direction));}
+            if direction_empty {return Some(*elf + direction.as_vector());}
         }
         return None;
     }
 
     pub fn get_elf_neighbours(&self, elf: &Point) -> HashSet<Point> {
         let mut elf_directions = HashSet::new();
-        for vec_direction in Direction::get_directions().iter().map(|x| Point::from_direction(x)) {
+        for vec_direction in Direction8::all().iter().map(|x| x.as_vector()) {
             let potential_elf: Point = *elf + vec_direction;
             if self.map.contains(&potential_elf) {
                 elf_directions.insert(vec_direction);
@@ -105,31 +84,87 @@ impl Elves {
 
     pub fn move_elf(&mut self, elf: &Point, to: &Point) {
         self.map.remove(elf);
-        self.map.insert(*to);
-        self.update_bounds(to);
+        self.map.set(*to, ());
     }
 
-    pub fn render_map(&self) {
-        let y_start = self.min_y.unwrap() - 1;
-        let y_end = self.max_y.unwrap() + 1;
-        let x_start = self.min_x.unwrap() - 1;
-        let x_end = self.max_x.unwrap() + 1;
-
-        for j in y_start..=y_end {
-            for i in x_start..=x_end {
-                let this_point: Point = Point::new(i, j);
-                let char_to_print = if self.map.contains(&this_point) {'#'} else {'.'};
-                print!("{}", char_to_print);
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-        }
-        println!("");
+    #[allow(dead_code)]
+    pub fn render_map(&self) -> String {
+        return self.render_to_string(&RenderOptions::default());
     }
 
     pub fn count_empty_tiles(&self) -> StdInt {
         let num_elves = self.map.len() as StdInt;
-        let num_tiles = (self.max_x.unwrap() - self.min_x.unwrap() + 1) * (self.max_y.unwrap() - self.min_y.unwrap() + 1);
+        // No elves cover no ground at all
+        let num_tiles = match self.map.bounds() {
+            Some(bounds) => bounds.area(),
+            None => return 0,
+        };
         return num_tiles - num_elves;
     }
-}
\ No newline at end of file
+}
+// Drawn with a one tile border of empty ground around the elves
+impl Render for Elves {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.map.bounds().map(|bounds| bounds.expand(1).to_i64());
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return if self.map.contains(&Point::from_i64(point)) {Cell::new('#', Colour::Green)} else {Cell::new('.', Colour::Grey)};
+    }
+}
+
+// One step is one round, up to `max_rounds` if there is one
+pub struct ElvesSimulation {
+    initial_elves: Elves,
+    elves: Elves,
+    max_rounds: Option<usize>,
+    num_rounds: usize,
+    settled: bool,
+}
+
+impl ElvesSimulation {
+    pub fn new(elves: Elves, max_rounds: Option<usize>) -> Self {
+        return Self {initial_elves: elves.clone(), elves: elves, max_rounds: max_rounds, num_rounds: 0, settled: false};
+    }
+}
+
+impl Render for ElvesSimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.elves.render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return self.elves.cell_at(point);
+    }
+}
+
+impl Simulation for ElvesSimulation {
+    fn step(&mut self) -> bool {
+        if self.settled || self.max_rounds.is_some_and(|max_rounds| self.num_rounds >= max_rounds) {
+            return false;
+        }
+        self.settled = self.elves.run_round();
+        self.num_rounds += 1;
+        return true;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.initial_elves.clone(), self.max_rounds);
+    }
+
+    fn status(&self) -> String {
+        let settled = if self.settled {", and no elf moved"} else {""};
+        return format!("Round {}: {} empty tiles around the elves{}", self.num_rounds, self.elves.count_empty_tiles(), settled);
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let point = Point::from_i64(point);
+        if !self.elves.map.contains(&point) {
+            return format!("{}: empty ground", point);
+        }
+        return match self.elves.get_proposal_for_elf(&point) {
+            Some(proposal) => format!("{}: an elf, who'd like to move to {}", point, proposal),
+            None => format!("{}: an elf, staying put", point),
+        };
+    }
+}
@@ -0,0 +1,60 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+
+#[path = "23_part1.rs"]
+pub mod part1;
+#[path = "23_part2.rs"]
+pub mod part2;
+
+mod space;
+mod elves;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => part2::solve(input, params),
+    };
+}
+
+pub fn picture(part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return match part {
+        Part::One => part1::picture(input, params),
+        Part::Two => part2::picture(input, params),
+    };
+}
+
+pub fn simulation(part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return match part {
+        Part::One => part1::simulation(input, params),
+        Part::Two => part2::simulation(input, params),
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/23.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(110)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(20)));
+    }
+
+    #[test]
+    fn no_elves() {
+        assert_eq!(solve(Part::One, "...\n...\n"), Ok(Answer::from(0)));
+        assert_eq!(solve(Part::One, ""), Ok(Answer::from(0)));
+    }
+}
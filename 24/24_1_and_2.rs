@@ -1,59 +1,161 @@
-use std::env;
-use std::fs;
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap,HashSet,HashMap};
-
-mod valley;
-use valley::{Tile,Valley};
-mod space;
-use space::{Direction,Point,StdInt};
-mod path;
-use path::PathPoint;
-
-const DEBUG: bool = false;
-const DISPLAY_PATH: bool = false;
-
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
-
-    let mut valley = Valley::new();
-    let mut start_option: Option<Point> = None;
-    let mut end_option: Option<Point> = None;
-    for (i, line) in input.lines().enumerate() {
-        for (j, tile_char) in line.trim().chars().enumerate() {
-            let this_point = Point::new(j as StdInt + 1, i as StdInt + 1);
-            if (i == 0) & (tile_char == '.') {start_option = Some(this_point);}
-            if (i > 0) & (tile_char == '.') {end_option = Some(this_point);}
-            if tile_char == '.' {continue;}
-            let this_point = Point::new(j as StdInt + 1, i as StdInt + 1);
-            valley.add_tile(&this_point, &Tile::from_char(tile_char).unwrap());
+#![allow(clippy::ptr_arg, clippy::single_match)]
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::grid::{Bounds,Grid,SparseGrid};
+use crate::common::parse::{check_chars, ParseError};
+use crate::common::search::{a_star,SearchResult};
+use crate::common::log::{self, Level};
+use crate::common::recorder;
+use crate::common::render::{Cell, Picture, Render, ScreenPoint};
+use crate::common::simulation::Simulation;
+use crate::{log_debug, log_info, log_trace};
+
+use super::valley::{Tile,Valley,ValleyView};
+use super::space::{get_moves,move_vector,Point,StdInt};
+
+// Which of the repeating valley states we're in, and where the party is
+type State = (usize, Point);
+
+pub fn solve(part: Part, input: &str, _params: &Params) -> Result<Answer, SolveError> {
+    let (valley, start, end) = read_valley(input)?;
+    log_debug!("Input read:\n{}", valley.render());
+    log_info!("Start: {start}, End: {end}");
+
+    let shortest_1: StdInt = find_shortest_path(start, end, valley.copy_valley(), 0)?;
+    log::detail("first_trip", shortest_1);
+    if part == Part::One {
+        return Ok(Answer::from(shortest_1));
+    }
+    
+    let shortest_2: StdInt = find_shortest_path(end, start, valley.copy_valley(), shortest_1)?;
+    log::detail("trip_back", shortest_2 - shortest_1);
+
+    let shortest_3: StdInt = find_shortest_path(start, end, valley.copy_valley(), shortest_2)?;
+    log::detail("second_trip", shortest_3 - shortest_2);
+    return Ok(Answer::from(shortest_3));
+}
+
+// The blizzards as the party reaches the end (for the last time, in part two)
+pub fn picture(part: Part, input: &str, _params: &Params) -> Result<Picture, SolveError> {
+    let (valley, start, end) = read_valley(input)?;
+    let mut arrival: StdInt = find_shortest_path(start, end, valley.copy_valley(), 0)?;
+    if part == Part::Two {
+        arrival = find_shortest_path(end, start, valley.copy_valley(), arrival)?;
+        arrival = find_shortest_path(start, end, valley.copy_valley(), arrival)?;
+    }
+    let valley_states = get_all_valley_states(valley);
+    let final_valley: &Valley = &valley_states[arrival as usize % valley_states.len()];
+    return Ok(Picture::capture(&ValleyView {valley: final_valley, party: Some(end)}));
+}
+
+// Finds the route first, then steps along it a minute at a time (all three trips in part two)
+pub fn simulation(part: Part, input: &str, _params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    let (valley, start, end) = read_valley(input)?;
+    let valley_states = get_all_valley_states(valley);
+    let mut path: Vec<State> = get_path(start, end, &valley_states, 0)?;
+    if part == Part::Two {
+        for (from, to) in [(end, start), (start, end)] {
+            let trip = get_path(from, to, &valley_states, (path.len() - 1) as StdInt)?;
+            path.extend(trip.into_iter().skip(1));
         }
     }
-    println!("Input read.");
-    let start = start_option.expect("Starting point should have been found");
-    let end = end_option.expect("Ending point should have been found");
-    valley.render();
-    println!("Start: {start}, End: {end}");
+    return Ok(Box::new(PartySimulation {valley_states: valley_states, path: path, minute: 0}));
+}
 
-    let shortest_1: PathPoint = find_shortest_path(start, end, valley.copy_valley(), 0);
-    println!("Shortest path avoiding blizzards (answer for part one): {}", shortest_1.steps_from_start);
-    
-    let shortest_2: PathPoint = find_shortest_path(end, start, valley.copy_valley(), shortest_1.steps_from_start as usize);
-    println!("Path back to start to get snacks: {}", shortest_2.steps_from_start);
+struct PartySimulation {
+    valley_states: Vec<Valley>,
+    path: Vec<State>,
+    minute: usize,
+}
+
+impl PartySimulation {
+    fn get_view(&self) -> ValleyView<'_> {
+        let (valley_state_ind, party) = self.path[self.minute];
+        return ValleyView {valley: &self.valley_states[valley_state_ind], party: Some(party)};
+    }
+}
 
-    let shortest_3: PathPoint = find_shortest_path(start, end, valley.copy_valley(), shortest_2.steps_from_start as usize);
-    println!("Shortest path avoiding blizzards with snacks (answer for part 2): {}", shortest_3.steps_from_start);
+impl Render for PartySimulation {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.get_view().render_bounds();
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return self.get_view().cell_at(point);
+    }
 }
 
-fn find_shortest_path(start: Point, end: Point, valley: Valley, start_state: usize) -> PathPoint {
-    let start_path = PathPoint::new_start(&start, start.distance(&end));
+impl Simulation for PartySimulation {
+    fn step(&mut self) -> bool {
+        if self.minute + 1 >= self.path.len() {
+            return false;
+        }
+        self.minute += 1;
+        return true;
+    }
+
+    fn reset(&mut self) {
+        self.minute = 0;
+    }
+
+    fn status(&self) -> String {
+        return format!("Minute {} of {}: the party is at {}", self.minute, self.path.len() - 1, self.path[self.minute].1);
+    }
+
+    fn describe(&self, point: &ScreenPoint) -> String {
+        let view = self.get_view();
+        let point = Point::from_i64(point);
+        let party = if view.party == Some(point) {"the party, "} else {""};
+        let contents: String = match view.valley.map.get(&point) {
+            Some(contents) if contents.contains(&Tile::Wall) => "a wall".to_string(),
+            Some(contents) => format!("blizzards going {}", contents.iter().map(|tile| tile.to_char()).collect::<String>()),
+            None => "open ground".to_string(),
+        };
+        return format!("{}: {}{}", point, party, contents);
+    }
+}
+
+
+fn read_valley(input: &str) -> Result<(Valley, Point, Point), ParseError> {
+    check_chars(input, Tile::is_tile_char, "a wall, open ground or a blizzard ('#', '.', '>', '<', '^' or 'v')")?;
+    let mut start_option: Option<Point> = None;
+    let mut end_option: Option<Point> = None;
+    let map = SparseGrid::from_chars(input, Point::new(1, 1), |this_point, tile_char| {
+        if (this_point.y == 1) & (tile_char == '.') {start_option = Some(this_point);}
+        if (this_point.y > 1) & (tile_char == '.') {end_option = Some(this_point);}
+        return Tile::from_char(tile_char).map(|tile| vec![tile]);
+    });
+    let valley = Valley {map: map};
+    return match (start_option, end_option) {
+        (Some(start), Some(end)) => Ok((valley, start, end)),
+        _ => Err(ParseError::whole_input("no gap in the walls", "a gap in the top and bottom walls of the valley")),
+    };
+}
+
+// Returns the time the party arrives at `end`, having set off at `start_time`
+fn find_shortest_path(start: Point, end: Point, valley: Valley, start_time: StdInt) -> Result<StdInt, SolveError> {
     let valley_states = get_all_valley_states(valley);
-    let (previous_points, final_pathpoint) = search_for_shortest_path(&start_path, end, start_state, &valley_states);
-    if DISPLAY_PATH {replay_path(&valley_states, previous_points, &final_pathpoint);}
-    return final_pathpoint;
+    let start_state: State = (start_time as usize % valley_states.len(), start);
+    let result = search_for_shortest_path(start_state, end, &valley_states)?;
+    if log::is_enabled(Level::Trace) {
+        if let Some(path) = result.goal_path() {replay_path(&valley_states, &path);}
+    }
+    if recorder::is_recording() {
+        if let Some(path) = result.goal_path() {record_path(&valley_states, &path);}
+    }
+    return match result.goal_distance() {
+        Some(steps) => Ok(start_time + steps),
+        None => Err(SolveError::NoSolution(format!("There's no way through the blizzards from {} to {}", start, end))),
+    };
+}
+
+// The states on the quickest way from `start` to `end`, setting off at `start_time`
+fn get_path(start: Point, end: Point, valley_states: &Vec<Valley>, start_time: StdInt) -> Result<Vec<State>, SolveError> {
+    let start_state: State = (start_time as usize % valley_states.len(), start);
+    return match search_for_shortest_path(start_state, end, valley_states)?.goal_path() {
+        Some(path) => Ok(path),
+        None => Err(SolveError::NoSolution(format!("There's no way through the blizzards from {} to {}", start, end))),
+    };
 }
 
 pub fn gcd(x: StdInt, y: StdInt) -> StdInt {
@@ -76,8 +178,9 @@ pub fn gcd(x: StdInt, y: StdInt) -> StdInt {
 }
 
 fn get_all_valley_states(initial_valley: Valley) -> Vec<Valley> {
-    let valley_width: StdInt = initial_valley.max_x.unwrap() - initial_valley.min_x.unwrap() - 1;
-    let valley_height: StdInt = initial_valley.max_y.unwrap() - initial_valley.min_y.unwrap() - 1;
+    let bounds = initial_valley.get_bounds();
+    let valley_width: StdInt = bounds.max.x - bounds.min.x - 1;
+    let valley_height: StdInt = bounds.max.y - bounds.min.y - 1;
     let periodicity: StdInt = valley_height * valley_width / gcd(valley_height, valley_width);
 
     let mut states: Vec<Valley> = Vec::new();
@@ -89,71 +192,27 @@ fn get_all_valley_states(initial_valley: Valley) -> Vec<Valley> {
     return states;
 }
 
-fn search_for_shortest_path(start: &PathPoint, end: Point, initial_valley_state: usize, valley_states: &Vec<Valley>) -> (HashMap<(usize, Point), PathPoint>, PathPoint)  {
-    let mut queue: BinaryHeap<Reverse<PathPoint>> = BinaryHeap::new();
-    let mut queued: HashSet<(usize, Point)> = HashSet::new();
-    let mut explored: HashSet<(usize, Point)> = HashSet::new();
-    let mut previous: HashMap<(usize, Point), PathPoint> = HashMap::new();
-
-    let mut current_best: Option<StdInt> = None;
-    let mut current_best_path: Option<PathPoint> = None;
-
+// The valley repeats, so a state only needs visiting once per valley state. Each step
+// (or wait) takes a minute and the distance to the end never overestimates the time left
+fn search_for_shortest_path(start: State, end: Point, valley_states: &Vec<Valley>) -> Result<SearchResult<State, StdInt>, SolveError> {
     let num_states: usize = valley_states.len();
-    let mut current_node: PathPoint = PathPoint::new(&start.point, initial_valley_state as StdInt, start.point.distance(&end));
-    loop {
-        // Update the best path if necessary
-        if current_node.point == end {
-            match current_best {
-                None => {
-                    current_best = Some(current_node.steps_from_start);
-                    current_best_path = Some(current_node);
-                },
-                Some(best) => {
-                    if current_node.steps_from_start < best {
-                        current_best = Some(current_node.steps_from_start);
-                        current_best_path = Some(current_node);
-                    }
-                },
-            }
-        }
-        else {
-            let next_valley_state_ind: usize = (current_node.steps_from_start as usize + 1) % num_states;
-            let next_valley_state: Valley = valley_states[next_valley_state_ind].copy_valley();
-            let this_state: (usize, Point) = (current_node.steps_from_start as usize % num_states , current_node.point);
-            let point_choices: Vec<Point> = get_point_choices(&current_node.point, &next_valley_state);
-            for point in point_choices {
-                let state: (usize, Point) = (next_valley_state_ind, point);
-                if !queued.contains(&state) && !explored.contains(&state) {
-                    let new_path_point = PathPoint::new(&point, &current_node.steps_from_start + 1, point.distance(&end));
-                    previous.insert(state, current_node);
-                    queued.insert(state);
-                    queue.push(Reverse(new_path_point));
-                }
-                if DEBUG {
-                    println!("Current step: {}", current_node.steps_from_start + 1);
-                    next_valley_state.render_with_party_position(Some(state.1));
-                    println!("{:?}, {:?}", this_state, state);
-                    println!("{:?}", queue);
-                }
-            }
-            explored.insert(this_state);
-        }
-
-        if queue.len() == 0 {
-            break;
-        }
-        current_node = queue.pop().unwrap().0;
-        if let Some(best) = current_best {
-            if best <= current_node.estimated_path_length() {break;}
-        }
+    let result = a_star([start], |state: &State| {
+        let next_valley_state_ind: usize = (state.0 + 1) % num_states;
+        let next_valley_state: &Valley = &valley_states[next_valley_state_ind];
+        let point_choices: Vec<Point> = get_point_choices(&state.1, next_valley_state);
+        log_trace!("{:?} -> {:?}", state, point_choices);
+        return point_choices.into_iter().map(|point| ((next_valley_state_ind, point), 1)).collect();
+    }, |state: &State| state.1.manhattan(&end), |state: &State| state.1 == end);
+    if result.cancelled {
+        return Err(SolveError::Interrupted {best: None});
     }
-    return (previous, current_best_path.expect("We should have found at least one path to the end!"));
+    return Ok(result);
 }
 
 fn get_point_choices(current_point: &Point, next_state: &Valley) -> Vec<Point> {
     let mut choices: Vec<Point> = Vec::new();
-    for direction in Direction::get_directions() {
-        let vector_dir: Point = Point::from_direction(&direction);
+    for direction in get_moves() {
+        let vector_dir: Point = move_vector(&direction);
         let new_point: Point = *current_point + vector_dir;
 
         match (next_state.map.get(&new_point), next_state.check_point_in_bounds(&new_point)) {
@@ -165,32 +224,17 @@ fn get_point_choices(current_point: &Point, next_state: &Valley) -> Vec<Point> {
 }
 
 
-fn replay_path(valley_states: &Vec<Valley>, previous_points: HashMap<(usize, Point), PathPoint>, final_pathpoint: &PathPoint) {
-    let num_states: usize = valley_states.len();
-    let path_points: Vec<Point> = get_path_as_points(previous_points, final_pathpoint, num_states as usize);
-    println!("{:?}", &path_points);
-    println!("Total length of path: {}", &path_points.len());
-    for (ind, point) in path_points.iter().enumerate() {
-        let valley_state_ind: usize = ind % num_states;
-        let valley_state: Valley = valley_states[valley_state_ind].copy_valley();
-        valley_state.render_with_party_position(Some(*point));
-    }    
-}
-
-fn get_path_as_points(previous_points: HashMap<(usize, Point), PathPoint>, final_pathpoint: &PathPoint, num_valley_states: usize) -> Vec<Point> {
-    let mut current_pt: PathPoint = *final_pathpoint;
-    let mut output: Vec<Point> = vec![current_pt.point];
-
-    loop {
-        let current_ind: usize = (current_pt.steps_from_start as usize) % num_valley_states;
-        if !previous_points.contains_key(&(current_ind, current_pt.point)) {
-            break;
-        }
-        current_pt = previous_points[&(current_ind, current_pt.point)];
-        output.push(current_pt.point);
+fn replay_path(valley_states: &Vec<Valley>, path: &Vec<State>) {
+    let path_points: Vec<Point> = path.iter().map(|state| state.1).collect();
+    log_trace!("Path: {:?}", &path_points);
+    log_trace!("Total length of path: {}", &path_points.len());
+    for (valley_state_ind, point) in path {
+        log_trace!("\n{}", valley_states[*valley_state_ind].render_with_party_position(Some(*point)));
     }
-    output.reverse();
-    return output;
 }
 
-
+fn record_path(valley_states: &[Valley], path: &[State]) {
+    for (valley_state_ind, point) in path {
+        recorder::frame(&ValleyView {valley: &valley_states[*valley_state_ind], party: Some(*point)});
+    }
+}
@@ -0,0 +1,43 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::render::Picture;
+use crate::common::simulation::Simulation;
+
+#[path = "24_1_and_2.rs"]
+pub mod part1_and_2;
+
+mod valley;
+mod space;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return part1_and_2::solve(part, input, params);
+}
+
+pub fn picture(part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return part1_and_2::picture(part, input, params);
+}
+
+pub fn simulation(part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return part1_and_2::simulation(part, input, params);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/24.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from(18)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Ok(Answer::from(54)));
+    }
+}
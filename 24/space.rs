@@ -1,126 +1,20 @@
-use std::ops::{Add,Sub,Neg,AddAssign};
-use std::fmt;
+use crate::common::geometry::Point2;
 
-pub type StdInt = i64;
-
-#[derive(Debug,Copy,Clone,PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Direction {
-    pub fn get_directions() -> Vec<Option<Self>> {
-        return vec![
-            Some(Self::North),
-            Some(Self::East),
-            Some(Self::South),
-            Some(Self::West),
-            None,
-        ];
-    }
-
-    pub fn from_char(direction: char) -> Option<Self> {
-        return match direction {
-            '^' => Some(Self::North),
-            '>' => Some(Self::East),
-            '<' => Some(Self::West),
-            'v' => Some(Self::South),
-            other => panic!("'{}' is not an acceptable direction", other),
-        };
-    }
-
-    pub fn to_char(&self) -> char {
-        return match self {
-            Self::North => '^',
-            Self::South => 'v',
-            Self::West => '<',
-            Self::East => '>',
-        }
-    }
-}
-
-#[derive(Debug,Copy,Clone,Hash)]
-pub struct Point {
-    pub x: StdInt,
-    pub y: StdInt,
-}
-
-impl Point {
-    pub fn new(x: StdInt, y: StdInt) -> Self {
-        return Point {x: x, y: y}
-    }
-
-    #[allow(dead_code)]
-    pub fn from_direction(direction: &Option<Direction>) -> Self {
-        return match *direction {
-            Some(Direction::North) => Self::new(0, -1),
-            Some(Direction::South) => Self::new(0, 1),
-            Some(Direction::West) => Self::new(-1, 0),
-            Some(Direction::East) => Self::new(1, 0),
-            None => Self::new(0, 0),
-        }
-    }
-
-    #[allow(dead_code)]
-    pub fn length(&self) -> StdInt {
-        return self.x.abs() + self.y.abs();
-    }
+pub use crate::common::geometry::Direction;
 
-    #[allow(dead_code)]
-    pub fn scalar_multiplication(&self, scalar: StdInt) -> Self {
-        return Self::new(self.x * scalar, self.y * scalar);
-    }
-
-    #[allow(dead_code)]
-    pub fn scalar_division(&self, scalar: StdInt) -> Self {
-        return Self::new(self.x / scalar, self.y / scalar);
-    }
-
-    pub fn distance(&self, &other: &Self) -> StdInt {
-        return (*self - other).length();
-    }
-}
-
-impl Add for Point { 
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        return Self::new(self.x + other.x, self.y + other.y);
-    }
-}
-
-impl Sub for Point { 
-    type Output = Self;
-    fn sub(self, other: Self) -> Self {
-        return Self::new(self.x - other.x, self.y - other.y);
-    }
-}
-
-impl AddAssign for Point {
-    fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
-    }
-}
+pub type StdInt = i64;
+pub type Point = Point2<StdInt>;
 
-impl Neg for Point {
-    type Output = Self;
-    fn neg(self) -> Self {
-        return Self::new(-self.x, -self.y);
-    }
+// The party can move in any of the four directions or wait (None) where it is
+pub fn get_moves() -> Vec<Option<Direction>> {
+    let mut moves: Vec<Option<Direction>> = Direction::all().into_iter().map(Some).collect();
+    moves.push(None);
+    return moves;
 }
 
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        return (self.x == other.x) && (self.y == other.y);
-    }
+pub fn move_vector(direction: &Option<Direction>) -> Point {
+    return match direction {
+        Some(direction) => direction.as_vector(),
+        None => Point::zero(),
+    };
 }
-
-impl Eq for Point {}
-
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        return write!(f, "({}, {})", self.x, self.y);
-    }
-}
\ No newline at end of file
@@ -1,10 +1,9 @@
-use std::io::stdout;
-use std::collections::HashMap;
-use std::io::Write;
+#![allow(clippy::wrong_self_convention)]
+
 use std::char;
-use std::rc::Rc;
-use std::cell::RefCell;
-use space::{Point,Direction,StdInt};
+use crate::common::grid::{Bounds,Grid,SparseGrid};
+use crate::common::render::{Cell,Colour,Render,RenderOptions,ScreenPoint};
+use super::space::{Point,Direction,StdInt};
 
 #[derive(Debug,Copy,Clone,PartialEq)]
 pub enum Tile {
@@ -13,124 +12,84 @@ pub enum Tile {
 }
 
 impl Tile {
+    // Open ground has no tile, so this can't tell it apart from a bad character
     pub fn from_char(tile_char: char) -> Option<Self> {
         return match tile_char {
             '#' => Some(Self::Wall),
-            '.' => None,
-            '>' | '<' | '^' | 'v' => Some(Self::Blizzard(Direction::from_char(tile_char).unwrap())),
-            other => panic!("'{}' is not an admissable tile char", other),
+            '>' | '<' | '^' | 'v' => Direction::from_char(tile_char).map(Self::Blizzard),
+            _ => None,
         };
     }
 
+    pub fn is_tile_char(tile_char: char) -> bool {
+        return (tile_char == '.') || Self::from_char(tile_char).is_some();
+    }
+
     pub fn to_char(&self) -> char {
         return match self {
             Self::Wall => '#',
-            Self::Blizzard(direction) => direction.to_char(),
+            Self::Blizzard(direction) => direction.as_char(),
         };
     }
 }
 
 #[derive(Debug)]
 pub struct Valley {
-    pub map: HashMap<Point, Rc<RefCell<Vec<Tile>>>>,
-    pub min_x: Option<StdInt>,
-    pub max_x: Option<StdInt>,
-    pub min_y: Option<StdInt>,
-    pub max_y: Option<StdInt>,
+    pub map: SparseGrid<StdInt, Vec<Tile>>,
 }
 
 impl Valley {
     pub fn new() -> Self {
-        return Self{
-            map: HashMap::new(), 
-            min_x: None,
-            max_x: None,
-            min_y: None,
-            max_y: None,
-        }
+        return Self{map: SparseGrid::new()}
     }
 
     pub fn add_tile(&mut self, point: &Point, tile: &Tile) {
-        if !self.map.contains_key(point) {
-            self.map.insert(*point, Rc::new(RefCell::new(Vec::new())));
+        if !self.map.contains(point) {
+            self.map.set(*point, Vec::new());
         }
-        self.update_bounds(point);
-        let boxed_contents = self.map.get(point).unwrap();
-        let mut contents = boxed_contents.as_ref().borrow_mut();
-        contents.push(*tile);
+        self.map.get_mut(point).unwrap().push(*tile);
     }
 
-    pub fn update_bounds(&mut self, new_point: &Point) {
-        if (self.min_x == None) || (self.min_x.unwrap() > new_point.x) {
-            self.min_x = Some(new_point.x);
-        } 
-        if (self.max_x == None) || (self.max_x.unwrap() < new_point.x) {
-            self.max_x = Some(new_point.x);
-        } 
-        if (self.min_y == None) || (self.min_y.unwrap() > new_point.y) {
-            self.min_y = Some(new_point.y);
-        } 
-        if (self.max_y == None) || (self.max_y.unwrap() < new_point.y) {
-            self.max_y = Some(new_point.y);
-        } 
+    pub fn get_bounds(&self) -> Bounds<StdInt> {
+        return self.map.bounds().expect("The valley should have walls");
     }
 
+    // The walls don't move, so the new valley keeps the same bounds
     pub fn move_blizzards(&self) -> Self {
-        let mut new_map: HashMap<Point, Rc<RefCell<Vec<Tile>>>> = HashMap::new();
-        for (point, boxed_blizzards) in &self.map {
-            let contents = boxed_blizzards.as_ref().borrow();
+        let mut new_valley = Self::new();
+        for (point, contents) in self.map.iter() {
             for tile in contents.iter() {
                 if *tile == Tile::Wall {
-                    new_map.insert(*point, Rc::new(RefCell::new(vec![*tile])));
+                    new_valley.add_tile(&point, tile);
                     continue;
                 }
                 let new_position = self.find_new_blizzard_pos(&point, tile);
-
-                if !new_map.contains_key(&new_position) {
-                    new_map.insert(new_position, Rc::new(RefCell::new(Vec::new())));
-                }
-                let boxed_contents = new_map.get(&new_position).unwrap();
-                let mut contents = boxed_contents.as_ref().borrow_mut();
-                contents.push(*tile);
+                new_valley.add_tile(&new_position, tile);
             }
         }
-        return Self {map: new_map, min_x: self.min_x, max_x: self.max_x, min_y: self.min_y, max_y: self.max_y} 
+        return new_valley;
     }
 
     pub fn copy_valley(&self) -> Self {
-        let mut new_map: HashMap<Point, Rc<RefCell<Vec<Tile>>>> = HashMap::new();
-        for (point, boxed_blizzards) in &self.map {
-            let contents = boxed_blizzards.as_ref().borrow();
-            for tile in contents.iter() {
-                if !new_map.contains_key(&point) {
-                    new_map.insert(*point, Rc::new(RefCell::new(Vec::new())));
-                }
-                let boxed_contents = new_map.get(&point).unwrap();
-                let mut contents = boxed_contents.as_ref().borrow_mut();
-                contents.push(*tile);
-            }
-        }
-        return Self {map: new_map, min_x: self.min_x, max_x: self.max_x, min_y: self.min_y, max_y: self.max_y} 
+        return Self {map: self.map.clone()};
     }
 
     pub fn find_new_blizzard_pos(&self, point: &Point, blizzard: &Tile) -> Point {
         if let Tile::Blizzard(direction) = blizzard {
-            let attempted_position: Point = *point + Point::from_direction(&Some(*direction));
+            let attempted_position: Point = *point + direction.as_vector();
+            let bounds = self.get_bounds();
             let returned_position: Point;
-            if (self.min_x == None) | (self.max_x == None) | (self.min_y == None) | (self.max_y == None) {
-                unreachable!();
+            if attempted_position.x <= bounds.min.x {
+                returned_position = Point::new(bounds.max.x - 1, attempted_position.y);
             }
-            else if attempted_position.x <= self.min_x.unwrap() {
-                returned_position = Point::new(self.max_x.unwrap() - 1, attempted_position.y);
+            else if attempted_position.x >= bounds.max.x {
+                returned_position = Point::new(bounds.min.x + 1, attempted_position.y);
             }
-            else if attempted_position.x >= self.max_x.unwrap() {
-                returned_position = Point::new(self.min_x.unwrap() + 1, attempted_position.y);
+            else if attempted_position.y <= bounds.min.y {
+                returned_position = Point::new(attempted_position.x, bounds.max.y - 1);
             }
-            else if attempted_position.y <= self.min_y.unwrap() {
-                returned_position = Point::new(attempted_position.x, self.max_y.unwrap() - 1);
-            }
-            else if attempted_position.y >= self.max_y.unwrap() {
-                returned_position = Point::new(attempted_position.x, self.min_y.unwrap() + 1);
+            else if attempted_position.y >= bounds.max.y {
+                returned_position = Point::new(attempted_position.x, bounds.min.y + 1);
             }
             else {
                 returned_position =  attempted_position;
@@ -146,45 +105,48 @@ impl Valley {
     }
 
     pub fn check_point_in_bounds(&self, point: &Point) -> bool {
-        return match (self.max_x, self.min_x, self.max_y, self.min_y) {
-            (Some(x_max), Some(x_min), Some(y_max), Some(y_min)) => (point.x <= x_max) & (point.x >= x_min) & (point.y <= y_max) & (point.y >= y_min),
-            (None, None, None, None) => false,
-            _ => unreachable!(),
-        };
+        return self.map.in_bounds(point);
     }
 
-    pub fn render(&self) {
-        self.render_with_party_position(None);
+    pub fn render(&self) -> String {
+        return self.render_with_party_position(None);
     }
 
-    pub fn render_with_party_position(&self, party: Option<Point>) {
-        let y_start = self.min_y.unwrap();
-        let y_end = self.max_y.unwrap();
-        let x_start = self.min_x.unwrap();
-        let x_end = self.max_x.unwrap();
-
-        let party_point = if let Some(some_point) = party {some_point} else {Point::new(x_start - 1, y_start - 1)};
-
-        for j in y_start..=y_end {
-            for i in x_start..=x_end {
-                let this_point: Point = Point::new(i, j);
-                let party_here: bool = this_point == party_point;
-                let position = self.map.get(&this_point);
-                let char_to_print: char = match (position, party_here) {
-                    (Some(_), true) => 'x',
-                    (None, true) => 'o',
-                    (None, false) => '.',
-                    (Some(boxed_contents), false) => {
-                        let contents = boxed_contents.as_ref().borrow();
-                        let num_blizzards = contents.len();
-                        if num_blizzards > 1 {char::from_digit(num_blizzards as u32, 10).unwrap()} else {contents[0].to_char()}
-                    },
-                };
-                print!("{}", char_to_print);
-            }
-            print!("\n");
-            stdout().flush().expect("This should print to screen");
-        }
-        println!("");
+    pub fn render_with_party_position(&self, party: Option<Point>) -> String {
+        return ValleyView {valley: self, party: party}.render_to_string(&RenderOptions::default());
     }
-}
\ No newline at end of file
+}
+
+// The valley with the party drawn in: 'o' on open ground, or 'x' if it's sharing a square with a blizzard
+pub struct ValleyView<'a> {
+    pub valley: &'a Valley,
+    pub party: Option<Point>,
+}
+
+impl Render for ValleyView<'_> {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.valley.map.bounds().map(|bounds| bounds.to_i64());
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        let this_point = Point::from_i64(point);
+        let party_here: bool = self.party == Some(this_point);
+        return match (self.valley.map.get(&this_point), party_here) {
+            (Some(_), true) => Cell::new('x', Colour::Red),
+            (None, true) => Cell::new('o', Colour::Green),
+            (None, false) => Cell::new('.', Colour::Grey),
+            (Some(contents), false) => {
+                let num_blizzards = contents.len();
+                if num_blizzards > 1 {
+                    Cell::new(char::from_digit(num_blizzards as u32, 10).unwrap_or('*'), Colour::Cyan)
+                }
+                else if contents[0] == Tile::Wall {
+                    Cell::new(contents[0].to_char(), Colour::White)
+                }
+                else {
+                    Cell::new(contents[0].to_char(), Colour::Cyan)
+                }
+            },
+        };
+    }
+}
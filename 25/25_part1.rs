@@ -1,33 +1,38 @@
-use std::env;
-use std::fs;
+use crate::common::{Answer, Params, SolveError};
+use crate::common::bigint::BigInt;
+use crate::common::integer::{use_bigint, Checked, Integer, BIGINT};
+use crate::common::params::ParamInfo;
+use crate::common::parse::lines;
+use crate::common::log;
+use crate::{log_debug, log_trace};
 
-fn main() {
-    let env_args: Vec<String> = env::args().collect();
-    let file_name = &env_args[1];
-    println!("File name is '{}'. Reading input...", file_name);
-    let input = fs::read_to_string(file_name).expect("Should have been able to read the file"); 
-    
-    let mut total = 0;
-    for (i, line) in input.lines().enumerate() {
-        let decimal_ver: i64 = snafu_to_decimal(&(line.to_string()));
-        println!("line {}: Original: {}, Decimal: {}", i, &line, decimal_ver);
-        total += decimal_ver;
-    }
+pub const PARAMS: &[&ParamInfo] = &[&BIGINT.info];
 
-    println!("Total in decimal: {}", total);
-    let total_snafu: String = decimal_to_snafu(total);
-    println!("Total in SNAFU: {}", total_snafu);
-} 
+pub fn solve(input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match use_bigint(params)? {
+        true => solve_with::<BigInt>(input),
+        false => solve_with::<Checked>(input),
+    };
+}
 
-fn snafu_to_decimal(line: &String) -> i64 {
-    let mut output = 0;
-    for (ind, character) in line.trim().chars().rev().enumerate() {
-        let digit_value: i64 = snafu_digit_to_decimal(character);
-        let power: u32 = ind as u32;
-        let new_value: i64 = digit_value * 5_i64.pow(power);
-        output += new_value;
+fn solve_with<T: Integer>(input: &str) -> Result<Answer, SolveError> {
+    let mut total: T = T::from(0);
+    for (i, line) in lines(input).enumerate() {
+        line.check_chars(|c| "=-012".contains(c), "a SNAFU digit ('=', '-', '0', '1' or '2')")?;
+        let decimal_ver: T = snafu_to_decimal(line.text);
+        log_trace!("line {}: Original: {}, Decimal: {}", i, line.text, decimal_ver);
+        total = total + decimal_ver;
     }
-    return output;
+    if total.overflowed() {
+        return Err(SolveError::Overflow("The sum of the fuel requirements".to_string()));
+    }
+
+    log::detail("decimal_total", total.to_string());
+    return Ok(Answer::from(decimal_to_snafu(total)?));
+}
+
+fn snafu_to_decimal<T: Integer>(line: &str) -> T {
+    return line.trim().chars().fold(T::from(0), |total, digit| total * T::from(5) + T::from(snafu_digit_to_decimal(digit)));
 }
 
 fn snafu_digit_to_decimal(digit: char) -> i64 {
@@ -38,50 +43,31 @@ fn snafu_digit_to_decimal(digit: char) -> i64 {
     };
 }
 
-fn decimal_to_snafu(decimal: i64) -> String {
-    let base5: String = decimal_to_base5(decimal);
-    println!("base5: {}", base5);
-    return base5_to_snafu(base5);
-}
-
-fn decimal_to_base5(decimal: i64) -> String {
-    let length: usize = (decimal as f64).log(5.0) as usize + 1;
-    let mut out_vec: Vec<i64> = Vec::new();
-    let mut leftover: i64 = decimal;
-    for i in (0..length).rev() {
-        let divisor = 5_i64.pow(i as u32);
-        let new_digit = leftover / divisor;
-        leftover -= new_digit * divisor;
-        out_vec.push(new_digit);
+// Each digit is the remainder mod 5 moved into -2..=2, and moving it carries one into the next digit.
+// Dividing first, rather than taking the digit off and then dividing, means even i64::MIN can't overflow.
+fn decimal_to_snafu<T: Integer>(decimal: T) -> Result<String, SolveError> {
+    if decimal.is_zero() {
+        return Ok("0".to_string());
     }
-    return out_vec.iter().map(|x| char::from_digit((*x) as u32, 5).unwrap()).collect::<String>();
-}
-
-fn base5_to_snafu(base5: String) -> String {
-    let length: usize = base5.len();
-    let mut snafu_vec: Vec<char> = vec!['0'; length];
-    let mut carry: char = '0';
-    for (ind, digit) in base5.chars().rev().enumerate() {
-        let (_, snafu_val): (char, char) = sum_snafu_digits(snafu_vec[ind], carry);
-        let (this_carry, new_val): (char, char) = sum_snafu_digits(snafu_val, digit);
-        carry = this_carry;
-        snafu_vec[ind] = new_val;
+    let five = T::from(5);
+    let mut digits: Vec<char> = Vec::new();
+    let mut leftover: T = decimal;
+    while !leftover.is_zero() {
+        // Division rounds towards zero, so the remainder has the sign of what's left, from -4 to 4
+        let remainder: i64 = (leftover.clone() % five.clone()).to_i64()
+            .ok_or(SolveError::Overflow("The sum of the fuel requirements".to_string()))?;
+        let (digit, carry): (i64, i64) = match remainder {
+            3..=4 => (remainder - 5, 1),
+            -4..=-3 => (remainder + 5, -1),
+            _ => (remainder, 0),
+        };
+        digits.push(match digit {
+            -2 => '=',
+            -1 => '-',
+            d => char::from_digit(d as u32, 10).unwrap(),
+        });
+        leftover = leftover / five.clone() + T::from(carry);
     }
-    return snafu_vec.into_iter().rev().collect::<String>();
-}
-
-fn sum_snafu_digits(digit1: char, digit2: char) -> (char, char) {
-    let dec1: i64 = snafu_digit_to_decimal(digit1);
-    let dec2: i64 = snafu_digit_to_decimal(digit2);
-    let result_dec: i64 = dec1 + dec2;
-    return base5_digit_to_snafu(result_dec);
-}
-
-fn base5_digit_to_snafu(digit: i64) -> (char, char) {
-    return match digit {
-        5 => ('1', '0'),
-        4 => ('1', '-'),
-        3 => ('1', '='),
-        a => ('0', char::from_digit(a as u32, 10).unwrap()),
-    };
+    log_debug!("{} SNAFU digits", digits.len());
+    return Ok(digits.into_iter().rev().collect::<String>());
 }
@@ -0,0 +1,69 @@
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::params::ParamInfo;
+
+#[path = "25_part1.rs"]
+pub mod part1;
+
+pub fn solve(part: Part, input: &str) -> Result<Answer, SolveError> {
+    return solve_with_params(part, input, &Params::new());
+}
+
+pub fn solve_with_params(part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match part {
+        Part::One => part1::solve(input, params),
+        Part::Two => Err(SolveError::NoSuchPart(25, part)),
+    };
+}
+
+pub fn params(part: Part) -> &'static [&'static ParamInfo] {
+    return match part {
+        Part::One => part1::PARAMS,
+        Part::Two => &[],
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/25.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve(Part::One, EXAMPLE), Ok(Answer::from("2=-1=0")));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve(Part::Two, EXAMPLE), Err(SolveError::NoSuchPart(25, Part::Two)));
+    }
+
+    #[test]
+    fn carries_into_a_new_digit() {
+        assert_eq!(solve(Part::One, "1\n2\n"), Ok(Answer::from("1=")));
+        assert_eq!(solve(Part::One, "1=-0-2\n-\n"), Ok(Answer::from("1=-0-1")));
+    }
+
+    #[test]
+    fn big_totals() {
+        // Twenty-eight 2s is more than i64 can hold
+        let input = "2".repeat(28);
+        assert!(matches!(solve(Part::One, &input), Err(SolveError::Overflow(_))));
+        let mut params = Params::new();
+        params.insert("bigint", "true");
+        assert_eq!(solve_with_params(Part::One, &input, &params), Ok(Answer::from(input.clone())));
+    }
+
+    // These add up to exactly i64::MIN
+    #[test]
+    fn smallest_total() {
+        let input = "---011210=2-00220102--2--=02\n=0\n";
+        assert_eq!(solve(Part::One, input), Ok(Answer::from("---011210=2-00220102--2--==2")));
+        let mut params = Params::new();
+        params.insert("bigint", "true");
+        assert_eq!(solve_with_params(Part::One, input, &params), Ok(Answer::from("---011210=2-00220102--2--==2")));
+        assert_eq!(solve(Part::One, "-\n1\n-\n"), Ok(Answer::from("-")));
+        assert_eq!(solve(Part::One, "=\n-\n"), Ok(Answer::from("-2")));
+    }
+}
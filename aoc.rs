@@ -0,0 +1,13 @@
+use std::env;
+use std::process;
+
+use aoc2022::runner;
+use aoc2022::runner::alloc::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let env_args: Vec<String> = env::args().collect();
+    process::exit(runner::main(&env_args));
+}
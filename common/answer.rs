@@ -0,0 +1,123 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::parse::ParseError;
+use super::part::Part;
+
+// What a solver hands back: most puzzles want a number, a few want text (crate tops, the CRT, SNAFU)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Answer {
+    pub fn as_number(&self) -> Option<i64> {
+        return match self {
+            Answer::Number(value) => Some(*value),
+            Answer::Text(_) => None,
+        };
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+        };
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        return Answer::Number(value);
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        return Answer::Number(value as i64);
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        return Answer::Number(value as i64);
+    }
+}
+
+// Anything too big for an i64 is still a valid answer, it just gets reported as text
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        return match i64::try_from(value) {
+            Ok(number) => Answer::Number(number),
+            Err(_) => Answer::Text(value.to_string()),
+        };
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        return Answer::from(value as u64);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        return Answer::Text(value);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        return Answer::Text(value.to_string());
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    NoSuchDay(usize),
+    NoSuchPart(usize, Part),
+    MissingParam(String),
+    BadParam {name: String, value: String},
+    BadInput(String),
+    Parse(ParseError),
+    NoSolution(String),
+    NoPicture(usize),
+    NoSimulation(usize),
+    NoGenerator(usize),
+    // Something didn't fit in 64 bits, and the day can use big integers instead
+    Overflow(String),
+    // Cancelled part way, with the best answer found up to then if the solver had one
+    Interrupted {best: Option<Answer>},
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            SolveError::NoSuchDay(day) => write!(f, "There is no day {}", day),
+            SolveError::NoSuchPart(day, part) => write!(f, "Day {} has no part {}", day, part),
+            SolveError::MissingParam(name) => write!(f, "Missing parameter '{}' (pass it with --param {}=<value>)", name, name),
+            SolveError::BadParam {name, value} => write!(f, "'{}' isn't a valid value for parameter '{}'", value, name),
+            SolveError::BadInput(message) => write!(f, "Bad input: {}", message),
+            SolveError::Parse(error) => write!(f, "Couldn't parse the input: {}", error),
+            SolveError::NoSolution(message) => write!(f, "No solution: {}", message),
+            SolveError::NoPicture(day) => write!(f, "Day {} has nothing to draw", day),
+            SolveError::NoSimulation(day) => write!(f, "Day {} can't be stepped through", day),
+            SolveError::NoGenerator(day) => write!(f, "Day {} has no input generator", day),
+            SolveError::Overflow(what) => write!(f, "{} overflowed 64 bits (pass --param bigint=true to work it out exactly)", what),
+            SolveError::Interrupted {best: Some(best)} => write!(f, "Interrupted, and the best answer found so far was {}", best),
+            SolveError::Interrupted {best: None} => write!(f, "Interrupted before finding an answer"),
+        };
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        return SolveError::Parse(error);
+    }
+}
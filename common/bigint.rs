@@ -0,0 +1,400 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+// An arbitrary-precision integer, for the days whose numbers can outgrow 64 bits. It's a sign and
+// the size as base 2^32 limbs, least significant first, with no zero limbs on the end, so zero is
+// no limbs at all and never negative. Division rounds towards zero like it does for i64.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+// The biggest power of ten in a limb, for reading and writing decimal nine digits at a time
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        return BigInt::default();
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        return BigInt {negative: negative, limbs: limbs};
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    pub fn is_negative(&self) -> bool {
+        return self.negative;
+    }
+
+    pub fn abs(&self) -> BigInt {
+        return BigInt {negative: false, limbs: self.limbs.clone()};
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.limbs.len() > 2 {
+            return None;
+        }
+        let size = self.limbs.iter().rev().fold(0u64, |size, &limb| (size << 32) | limb as u64);
+        return if self.negative {
+            0i64.checked_sub_unsigned(size)
+        } else {
+            i64::try_from(size).ok()
+        };
+    }
+
+    // Rounds the quotient towards zero, and the remainder takes the sign of self
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "Can't divide by zero");
+        let (quotient, remainder) = div_rem_limbs(&self.limbs, &divisor.limbs);
+        return (BigInt::from_parts(self.negative != divisor.negative, quotient), BigInt::from_parts(self.negative, remainder));
+    }
+
+    // Always positive, except that the gcd of 0 and 0 is 0
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        return a;
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        let mut result = BigInt::from(1);
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent >>= 1;
+        }
+        return result;
+    }
+
+    fn num_bits(&self) -> usize {
+        return match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        };
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let size = value.unsigned_abs();
+        return BigInt::from_parts(value < 0, vec![size as u32, (size >> 32) as u32]);
+    }
+}
+
+fn compare_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    return a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()));
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() {(a, b)} else {(b, a)};
+    let mut sum: Vec<u32> = Vec::with_capacity(long.len() + 1);
+    let mut carry: u64 = 0;
+    for (i, &limb) in long.iter().enumerate() {
+        let total = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    return sum;
+}
+
+// a has to be at least as big as b
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference: Vec<u32> = Vec::with_capacity(a.len());
+    let mut borrow: i64 = 0;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut total = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = if total < 0 {1} else {0};
+        total += borrow << 32;
+        difference.push(total as u32);
+    }
+    return difference;
+}
+
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product: Vec<u32> = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let total = product[i + j] as u64 + x as u64 * y as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    return product;
+}
+
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient: Vec<u32> = vec![0; a.len()];
+    let mut remainder: u64 = 0;
+    for i in (0..a.len()).rev() {
+        let current = (remainder << 32) | a[i] as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    return (quotient, remainder as u32);
+}
+
+// Long division a bit at a time. Slow next to Knuth's algorithm, but the numbers here are only a few limbs long.
+fn div_rem_limbs(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_limbs(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (quotient, remainder) = div_rem_small(a, b[0]);
+        return (quotient, vec![remainder]);
+    }
+    let num_bits = BigInt {negative: false, limbs: a.to_vec()}.num_bits();
+    let mut quotient: Vec<u32> = vec![0; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..num_bits).rev() {
+        // remainder = remainder * 2 + the next bit of a
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for limb in remainder.iter_mut() {
+            let top = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = top;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+        if compare_limbs(&remainder, b) != Ordering::Less {
+            remainder = sub_limbs(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    return (quotient, remainder);
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_limbs(&self.limbs, &other.limbs),
+            (true, true) => compare_limbs(&other.limbs, &self.limbs),
+        };
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_limbs(&self.limbs, &other.limbs));
+        }
+        // Opposite signs, so it's the difference of the sizes with the sign of the bigger one
+        return match compare_limbs(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_limbs(&other.limbs, &self.limbs)),
+            _ => BigInt::from_parts(self.negative, sub_limbs(&self.limbs, &other.limbs)),
+        };
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+    fn sub(self, other: &BigInt) -> BigInt {
+        return self + &(-other);
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+    fn mul(self, other: &BigInt) -> BigInt {
+        return BigInt::from_parts(self.negative != other.negative, mul_limbs(&self.limbs, &other.limbs));
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+    fn div(self, other: &BigInt) -> BigInt {
+        return self.div_rem(other).0;
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+    fn rem(self, other: &BigInt) -> BigInt {
+        return self.div_rem(other).1;
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        return BigInt::from_parts(!self.negative, self.limbs.clone());
+    }
+}
+
+// The same again for owned values, so BigInts can be used like the built in integers
+macro_rules! owned_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait for BigInt {
+            type Output = BigInt;
+            fn $method(self, other: BigInt) -> BigInt {
+                return (&self).$method(&other);
+            }
+        }
+    };
+}
+
+owned_op!(Add, add);
+owned_op!(Sub, sub);
+owned_op!(Mul, mul);
+owned_op!(Div, div);
+owned_op!(Rem, rem);
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        return -&self;
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    // Decimal, with an optional sign
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("'{}' is not a whole number", text));
+        }
+        let mut limbs: Vec<u32> = Vec::new();
+        let first_chunk = match digits.len() % DECIMAL_CHUNK_DIGITS {
+            0 => DECIMAL_CHUNK_DIGITS,
+            length => length,
+        };
+        let mut start = 0;
+        let mut end = first_chunk;
+        while start < digits.len() {
+            let chunk: u32 = digits[start..end].parse().unwrap();
+            let scale = 10u32.pow((end - start) as u32);
+            limbs = add_limbs(&mul_limbs(&limbs, &[scale]), &[chunk]);
+            start = end;
+            end += DECIMAL_CHUNK_DIGITS;
+        }
+        return Ok(BigInt::from_parts(negative, limbs));
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut chunks: Vec<u32> = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (quotient, chunk) = div_rem_small(&limbs, DECIMAL_CHUNK);
+            chunks.push(chunk);
+            limbs = BigInt::from_parts(false, quotient).limbs;
+        }
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        text.push_str(&chunks.pop().unwrap().to_string());
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        return f.pad(&text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::random::Rng;
+
+    use super::*;
+
+    fn big(text: &str) -> BigInt {
+        return text.parse().unwrap();
+    }
+
+    #[test]
+    fn parse_and_display() {
+        for text in ["0", "7", "-42", "4294967296", "-9223372036854775808", "123456789012345678901234567890"] {
+            assert_eq!(big(text).to_string(), text);
+        }
+        assert_eq!(big("+0012").to_string(), "12");
+        assert_eq!(big("-0"), BigInt::zero());
+        assert!("12a".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn matches_i128_arithmetic() {
+        let mut rng = Rng::new(19);
+        for _ in 0..500 {
+            let a = rng.next_u64() as i64 >> rng.below(63);
+            let b = rng.next_u64() as i64 >> rng.below(63);
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            let (a, b) = (a as i128, b as i128);
+            assert_eq!((&x + &y).to_string(), (a + b).to_string());
+            assert_eq!((&x - &y).to_string(), (a - b).to_string());
+            assert_eq!((&x * &y).to_string(), (a * b).to_string());
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            if b != 0 {
+                assert_eq!((&x / &y).to_string(), (a / b).to_string());
+                assert_eq!((&x % &y).to_string(), (a % b).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_64_bits() {
+        let factorial = (1..=30).fold(BigInt::from(1), |product, n| product * BigInt::from(n));
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(factorial.to_i64(), None);
+        let (quotient, remainder) = factorial.div_rem(&big("1000000000000000000007"));
+        assert_eq!(quotient.to_string(), "265252859812");
+        assert_eq!(remainder.to_string(), "191058634451709981316");
+        assert_eq!(BigInt::from(5).pow(40).to_string(), "9094947017729282379150390625");
+        assert_eq!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+    }
+
+    #[test]
+    fn gcd() {
+        assert_eq!(big("-12").gcd(&big("18")), big("6"));
+        assert_eq!(BigInt::zero().gcd(&big("-7")), big("7"));
+        // 30! has 2^26 and 5^7 in it
+        assert_eq!(big("265252859812191058636308480000000").gcd(&BigInt::from(10).pow(21)), big("163840000000"));
+    }
+}
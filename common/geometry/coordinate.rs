@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// The signed integer types the puzzles use for coordinates
+pub trait Coordinate:
+    Copy + Debug + Display + Hash + Ord + FromStr
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    + Neg<Output = Self> + AddAssign
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn abs(self) -> Self;
+    // For indexing into dense storage
+    fn to_i64(self) -> i64;
+    fn from_i64(value: i64) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($int:ty),*) => {
+        $(
+            impl Coordinate for $int {
+                fn zero() -> Self {
+                    return 0;
+                }
+
+                fn one() -> Self {
+                    return 1;
+                }
+
+                fn abs(self) -> Self {
+                    return <$int>::abs(self);
+                }
+
+                fn to_i64(self) -> i64 {
+                    return self as i64;
+                }
+
+                fn from_i64(value: i64) -> Self {
+                    return value as $int;
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, isize);
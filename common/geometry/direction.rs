@@ -0,0 +1,221 @@
+use std::str::FromStr;
+
+use super::coordinate::Coordinate;
+use super::point::Point2;
+
+// Turns are given as if looking down on the grid, so with y pointing down
+// `Right` is clockwise on screen
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    Left,
+    Right,
+    Half,
+    None,
+}
+
+impl Rotation {
+    pub fn inverse(&self) -> Self {
+        return match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Half => Self::Half,
+            Self::None => Self::None,
+        };
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        return match string.trim().chars().next() {
+            Some('R') => Ok(Self::Right),
+            Some('L') => Ok(Self::Left),
+            Some('H') => Ok(Self::Half),
+            Some('N') => Ok(Self::None),
+            _ => Err(format!("'{}' is not a valid rotation", string)),
+        };
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub fn all() -> [Self; 4] {
+        return [Self::North, Self::East, Self::South, Self::West];
+    }
+
+    pub fn rotate(&self, rotation: &Rotation) -> Self {
+        return match rotation {
+            Rotation::None => *self,
+            Rotation::Half => self.inverse(),
+            Rotation::Right => match self {
+                Self::North => Self::East,
+                Self::East => Self::South,
+                Self::South => Self::West,
+                Self::West => Self::North,
+            },
+            Rotation::Left => match self {
+                Self::North => Self::West,
+                Self::West => Self::South,
+                Self::South => Self::East,
+                Self::East => Self::North,
+            },
+        };
+    }
+
+    pub fn inverse(&self) -> Self {
+        return match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        };
+    }
+
+    // North is towards smaller y, as it is when reading a grid from the input
+    pub fn as_vector<T: Coordinate>(&self) -> Point2<T> {
+        let zero = T::zero();
+        let one = T::one();
+        return match self {
+            Self::North => Point2::new(zero, -one),
+            Self::East => Point2::new(one, zero),
+            Self::South => Point2::new(zero, one),
+            Self::West => Point2::new(-one, zero),
+        };
+    }
+
+    pub fn as_char(&self) -> char {
+        return match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        };
+    }
+
+    pub fn from_char(direction: char) -> Option<Self> {
+        return match direction {
+            '^' => Some(Self::North),
+            '>' => Some(Self::East),
+            'v' => Some(Self::South),
+            '<' => Some(Self::West),
+            _ => None,
+        };
+    }
+
+    // The direction itself and the two diagonals either side of it
+    pub fn with_diagonals(&self) -> [Direction8; 3] {
+        return match self {
+            Self::North => [Direction8::North, Direction8::NorthEast, Direction8::NorthWest],
+            Self::East => [Direction8::East, Direction8::NorthEast, Direction8::SouthEast],
+            Self::South => [Direction8::South, Direction8::SouthEast, Direction8::SouthWest],
+            Self::West => [Direction8::West, Direction8::NorthWest, Direction8::SouthWest],
+        };
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub fn all() -> [Self; 8] {
+        return [
+            Self::North,
+            Self::NorthEast,
+            Self::East,
+            Self::SouthEast,
+            Self::South,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+        ];
+    }
+
+    pub fn as_vector<T: Coordinate>(&self) -> Point2<T> {
+        let zero = T::zero();
+        let one = T::one();
+        return match self {
+            Self::North => Point2::new(zero, -one),
+            Self::NorthEast => Point2::new(one, -one),
+            Self::East => Point2::new(one, zero),
+            Self::SouthEast => Point2::new(one, one),
+            Self::South => Point2::new(zero, one),
+            Self::SouthWest => Point2::new(-one, one),
+            Self::West => Point2::new(-one, zero),
+            Self::NorthWest => Point2::new(-one, -one),
+        };
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        return match direction {
+            Direction::North => Self::North,
+            Direction::East => Self::East,
+            Direction::South => Self::South,
+            Direction::West => Self::West,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::North.rotate(&Rotation::Right), Direction::East);
+        assert_eq!(Direction::North.rotate(&Rotation::Left), Direction::West);
+        assert_eq!(Direction::East.rotate(&Rotation::Half), Direction::West);
+        assert_eq!(Direction::South.rotate(&Rotation::None), Direction::South);
+        for direction in Direction::all() {
+            for rotation in [Rotation::Left, Rotation::Right, Rotation::Half, Rotation::None] {
+                assert_eq!(direction.rotate(&rotation).rotate(&rotation.inverse()), direction);
+            }
+            let four_rights = (0..4).fold(direction, |turned, _| turned.rotate(&Rotation::Right));
+            assert_eq!(four_rights, direction);
+            assert_eq!(Direction::from_char(direction.as_char()), Some(direction));
+        }
+        assert_eq!("R".parse::<Rotation>(), Ok(Rotation::Right));
+        assert_eq!(" L".parse::<Rotation>(), Ok(Rotation::Left));
+        assert!("X".parse::<Rotation>().is_err());
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    // Clockwise from north, with y pointing down
+    #[test]
+    fn vectors() {
+        let vectors: Vec<Point2<i32>> = Direction::all().iter().map(|direction| direction.as_vector()).collect();
+        assert_eq!(vectors, vec![Point2::new(0, -1), Point2::new(1, 0), Point2::new(0, 1), Point2::new(-1, 0)]);
+        let vectors: Vec<Point2<i32>> = Direction8::all().iter().map(|direction| direction.as_vector()).collect();
+        assert_eq!(vectors, vec![
+            Point2::new(0, -1), Point2::new(1, -1), Point2::new(1, 0), Point2::new(1, 1),
+            Point2::new(0, 1), Point2::new(-1, 1), Point2::new(-1, 0), Point2::new(-1, -1),
+        ]);
+        for direction in Direction::all() {
+            assert_eq!(Direction8::from(direction).as_vector::<i32>(), direction.as_vector());
+            assert_eq!(direction.inverse().as_vector::<i32>(), -direction.as_vector::<i32>());
+            // Each diagonal lies next to the direction it goes with
+            for diagonal in direction.with_diagonals() {
+                let dot = diagonal.as_vector::<i32>().component_mult(&direction.as_vector());
+                assert_eq!(dot.x + dot.y, 1);
+            }
+        }
+    }
+}
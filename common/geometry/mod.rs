@@ -0,0 +1,7 @@
+pub mod coordinate;
+pub mod direction;
+pub mod point;
+
+pub use self::coordinate::Coordinate;
+pub use self::direction::{Direction, Direction8, Rotation};
+pub use self::point::{Point2, Point3};
@@ -0,0 +1,294 @@
+use std::cmp::max;
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub};
+use std::str::FromStr;
+
+use super::coordinate::Coordinate;
+use super::direction::{Direction, Direction8, Rotation};
+
+// Points derive Ord so they sort by x, then y (then z) and can go in BTree collections
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        return Self {x: x, y: y};
+    }
+
+    pub fn zero() -> Self {
+        return Self::new(T::zero(), T::zero());
+    }
+
+    pub fn length(&self) -> T {
+        return self.x.abs() + self.y.abs();
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        return (*self - *other).length();
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let difference = *self - *other;
+        return max(difference.x.abs(), difference.y.abs());
+    }
+
+    pub fn scalar_mult(&self, scalar: T) -> Self {
+        return Self::new(self.x * scalar, self.y * scalar);
+    }
+
+    pub fn scalar_div(&self, scalar: T) -> Self {
+        return Self::new(self.x / scalar, self.y / scalar);
+    }
+
+    pub fn component_mult(&self, other: &Self) -> Self {
+        return Self::new(self.x * other.x, self.y * other.y);
+    }
+
+    // Rotates about the origin, with y pointing down like the puzzle grids
+    pub fn rotate(&self, rotation: &Rotation) -> Self {
+        return match rotation {
+            Rotation::None => *self,
+            Rotation::Half => -*self,
+            Rotation::Right => Self::new(-self.y, self.x),
+            Rotation::Left => Self::new(self.y, -self.x),
+        };
+    }
+
+    pub fn neighbours4(&self) -> [Self; 4] {
+        return Direction::all().map(|direction| *self + direction.as_vector());
+    }
+
+    pub fn neighbours8(&self) -> [Self; 8] {
+        return Direction8::all().map(|direction| *self + direction.as_vector());
+    }
+
+    // For code that works in one coordinate type whatever the day uses (rendering, mostly)
+    pub fn to_i64(&self) -> Point2<i64> {
+        return Point2::new(self.x.to_i64(), self.y.to_i64());
+    }
+
+    pub fn from_i64(point: &Point2<i64>) -> Self {
+        return Self::new(T::from_i64(point.x), T::from_i64(point.y));
+    }
+}
+
+impl<T: Coordinate> Add for Point2<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        return Self::new(self.x + other.x, self.y + other.y);
+    }
+}
+
+impl<T: Coordinate> Sub for Point2<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        return Self::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+impl<T: Coordinate> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Coordinate> Neg for Point2<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Self::new(-self.x, -self.y);
+    }
+}
+
+impl<T: Coordinate> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "({}, {})", self.x, self.y);
+    }
+}
+
+// Accepts "3,4" as well as the labelled "x=3, y=4"
+impl<T: Coordinate> FromStr for Point2<T> {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let coords: Vec<T> = parse_coordinates(string, &["x=", "y="])?;
+        return Ok(Self::new(coords[0], coords[1]));
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        return Self {x: x, y: y, z: z};
+    }
+
+    pub fn zero() -> Self {
+        return Self::new(T::zero(), T::zero(), T::zero());
+    }
+
+    pub fn length(&self) -> T {
+        return self.x.abs() + self.y.abs() + self.z.abs();
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        return (*self - *other).length();
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let difference = *self - *other;
+        return max(max(difference.x.abs(), difference.y.abs()), difference.z.abs());
+    }
+
+    pub fn scalar_mult(&self, scalar: T) -> Self {
+        return Self::new(self.x * scalar, self.y * scalar, self.z * scalar);
+    }
+
+    pub fn component_mult(&self, other: &Self) -> Self {
+        return Self::new(self.x * other.x, self.y * other.y, self.z * other.z);
+    }
+
+    pub fn unit_vectors() -> [Self; 6] {
+        let zero = T::zero();
+        let one = T::one();
+        return [
+            Self::new(zero, zero, one),
+            Self::new(zero, zero, -one),
+            Self::new(zero, one, zero),
+            Self::new(zero, -one, zero),
+            Self::new(one, zero, zero),
+            Self::new(-one, zero, zero),
+        ];
+    }
+
+    pub fn neighbours6(&self) -> [Self; 6] {
+        return Self::unit_vectors().map(|vector| *self + vector);
+    }
+}
+
+impl<T: Coordinate> Add for Point3<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        return Self::new(self.x + other.x, self.y + other.y, self.z + other.z);
+    }
+}
+
+impl<T: Coordinate> Sub for Point3<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        return Self::new(self.x - other.x, self.y - other.y, self.z - other.z);
+    }
+}
+
+impl<T: Coordinate> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Coordinate> Neg for Point3<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Self::new(-self.x, -self.y, -self.z);
+    }
+}
+
+impl<T: Coordinate> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "({}, {}, {})", self.x, self.y, self.z);
+    }
+}
+
+impl<T: Coordinate> FromStr for Point3<T> {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let coords: Vec<T> = parse_coordinates(string, &["x=", "y=", "z="])?;
+        return Ok(Self::new(coords[0], coords[1], coords[2]));
+    }
+}
+
+fn parse_coordinates<T: Coordinate>(string: &str, labels: &[&str]) -> Result<Vec<T>, String> {
+    let parts: Vec<&str> = string.trim().split(',').collect();
+    if parts.len() != labels.len() {
+        return Err(format!("'{}' should have {} comma separated coordinates", string, labels.len()));
+    }
+    let mut coords: Vec<T> = Vec::new();
+    for (part, label) in parts.iter().zip(labels) {
+        let part = part.trim();
+        let number = part.strip_prefix(label).unwrap_or(part);
+        match number.parse::<T>() {
+            Ok(value) => coords.push(value),
+            Err(_) => return Err(format!("'{}' is not a valid coordinate", part)),
+        }
+    }
+    return Ok(coords);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a: Point2<i32> = Point2::new(1, -2);
+        let b: Point2<i32> = Point2::new(-3, 4);
+        assert_eq!((a.manhattan(&b), b.manhattan(&a)), (10, 10));
+        assert_eq!((a.chebyshev(&b), b.chebyshev(&a)), (6, 6));
+        assert_eq!(a.manhattan(&a), 0);
+        assert_eq!(b.length(), 7);
+        let c: Point3<i64> = Point3::new(1, 2, 3);
+        let d: Point3<i64> = Point3::new(-1, 5, 3);
+        assert_eq!((c.manhattan(&d), c.chebyshev(&d)), (5, 3));
+    }
+
+    // With y pointing down, turning right takes north (0, -1) to east (1, 0)
+    #[test]
+    fn rotations() {
+        let point: Point2<i32> = Point2::new(2, -1);
+        assert_eq!(point.rotate(&Rotation::Right), Point2::new(1, 2));
+        assert_eq!(point.rotate(&Rotation::Left), Point2::new(-1, -2));
+        assert_eq!(point.rotate(&Rotation::Half), Point2::new(-2, 1));
+        assert_eq!(point.rotate(&Rotation::None), point);
+        for direction in Direction::all() {
+            for rotation in [Rotation::Left, Rotation::Right, Rotation::Half, Rotation::None] {
+                assert_eq!(direction.as_vector::<i32>().rotate(&rotation), direction.rotate(&rotation).as_vector());
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours() {
+        let point: Point2<i32> = Point2::new(5, 5);
+        assert_eq!(point.neighbours4(), [Point2::new(5, 4), Point2::new(6, 5), Point2::new(5, 6), Point2::new(4, 5)]);
+        let neighbours8 = point.neighbours8();
+        assert_eq!((neighbours8[0], neighbours8[1], neighbours8[7]), (Point2::new(5, 4), Point2::new(6, 4), Point2::new(4, 4)));
+        assert!(neighbours8.iter().all(|neighbour| neighbour.chebyshev(&point) == 1));
+        let origin: Point3<i32> = Point3::zero();
+        assert!(origin.neighbours6().iter().all(|neighbour| neighbour.manhattan(&origin) == 1));
+        assert_eq!(origin.neighbours6().iter().fold(origin, |total, &neighbour| total + neighbour), origin);
+    }
+
+    #[test]
+    fn parsing_and_arithmetic() {
+        assert_eq!("3,4".parse::<Point2<i32>>(), Ok(Point2::new(3, 4)));
+        assert_eq!("x=-3, y=4".parse::<Point2<i64>>(), Ok(Point2::new(-3, 4)));
+        assert_eq!("1,2,3".parse::<Point3<i32>>(), Ok(Point3::new(1, 2, 3)));
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert!("1,x".parse::<Point2<i32>>().is_err());
+        let mut point: Point2<i32> = Point2::new(1, 2);
+        point += Point2::new(3, -4);
+        assert_eq!(point, Point2::new(4, -2));
+        assert_eq!(point.scalar_mult(3) - Point2::new(2, 2), Point2::new(10, -8));
+        assert_eq!(point.scalar_div(2), Point2::new(2, -1));
+        assert_eq!(point.to_string(), "(4, -2)");
+        assert_eq!(Point2::<i8>::from_i64(&point.to_i64()), Point2::new(4, -2));
+    }
+}
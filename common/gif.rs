@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use super::raster::Rgb;
+
+// A minimal animated GIF writer: one global colour table, full-size frames and LZW-compressed pixels
+
+const MAX_CODE: u16 = 4096;
+
+// Each frame is one palette index per pixel, row by row from the top left, and the animation loops forever
+pub fn encode_gif(width: u16, height: u16, palette: &[Rgb], frames: &[Vec<u8>], delay_centis: u16) -> Vec<u8> {
+    assert!(!palette.is_empty() && palette.len() <= 256, "A GIF palette has between 1 and 256 colours");
+    let table_bits = get_table_bits(palette.len());
+
+    let mut gif: Vec<u8> = b"GIF89a".to_vec();
+    gif.extend_from_slice(&width.to_le_bytes());
+    gif.extend_from_slice(&height.to_le_bytes());
+    // Global colour table present, 8 bits per primary, the table size, then the background colour and aspect ratio
+    gif.push(0x80 | 0x70 | (table_bits - 1));
+    gif.extend_from_slice(&[0, 0]);
+    for i in 0..(1 << table_bits) {
+        let rgb = palette.get(i).copied().unwrap_or(Rgb(0, 0, 0));
+        gif.extend_from_slice(&[rgb.0, rgb.1, rgb.2]);
+    }
+    // The NETSCAPE2.0 extension, with a loop count of 0 meaning forever
+    gif.extend_from_slice(&[0x21, 0xff, 0x0b]);
+    gif.extend_from_slice(b"NETSCAPE2.0");
+    gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    let min_code_size = table_bits.max(2);
+    for frame in frames {
+        assert_eq!(frame.len(), width as usize * height as usize, "Expected one index for every pixel");
+        // Graphic control: leave the frame in place, no transparency
+        gif.extend_from_slice(&[0x21, 0xf9, 0x04, 0x04]);
+        gif.extend_from_slice(&delay_centis.to_le_bytes());
+        gif.extend_from_slice(&[0x00, 0x00]);
+
+        gif.push(0x2c);
+        gif.extend_from_slice(&[0, 0, 0, 0]);
+        gif.extend_from_slice(&width.to_le_bytes());
+        gif.extend_from_slice(&height.to_le_bytes());
+        gif.push(0x00);
+
+        gif.push(min_code_size);
+        for block in lzw_compress(frame, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend_from_slice(block);
+        }
+        gif.push(0x00);
+    }
+    gif.push(0x3b);
+    return gif;
+}
+
+// The colour table has 2^bits entries, and bits is at least 1
+fn get_table_bits(num_colours: usize) -> u8 {
+    let mut bits: u8 = 1;
+    while (1 << bits) < num_colours {
+        bits += 1;
+    }
+    return bits;
+}
+
+// Packs variable-width codes least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    num_bits: u8,
+}
+
+impl BitWriter {
+    fn new() -> BitWriter {
+        return BitWriter {bytes: Vec::new(), buffer: 0, num_bits: 0};
+    }
+
+    fn write(&mut self, code: u16, code_size: u8) {
+        self.buffer |= (code as u32) << self.num_bits;
+        self.num_bits += code_size;
+        while self.num_bits >= 8 {
+            self.bytes.push((self.buffer & 0xff) as u8);
+            self.buffer >>= 8;
+            self.num_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_bits > 0 {
+            self.bytes.push((self.buffer & 0xff) as u8);
+        }
+        return self.bytes;
+    }
+}
+
+fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear_code: u16 = 1 << min_code_size;
+    let end_code: u16 = clear_code + 1;
+    let mut writer = BitWriter::new();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code: u16 = end_code + 1;
+    let mut code_size: u8 = min_code_size + 1;
+    writer.write(clear_code, code_size);
+
+    let mut pixels = indices.iter();
+    let mut prefix: u16 = match pixels.next() {
+        Some(first) => *first as u16,
+        None => {
+            writer.write(end_code, code_size);
+            return writer.finish();
+        },
+    };
+    for &index in pixels {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        if next_code < MAX_CODE {
+            table.insert((prefix, index), next_code);
+            // The decoder is one code behind, so it widens once it has seen this code's number
+            if next_code == (1 << code_size) {
+                code_size += 1;
+            }
+            next_code += 1;
+        }
+        else {
+            // The table's full, so start again
+            writer.write(clear_code, code_size);
+            table.clear();
+            next_code = end_code + 1;
+            code_size = min_code_size + 1;
+        }
+        prefix = index as u16;
+    }
+    writer.write(prefix, code_size);
+    writer.write(end_code, code_size);
+    return writer.finish();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A straightforward decoder, to check the encoder against
+    fn lzw_decompress(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear_code: u16 = 1 << min_code_size;
+        let end_code: u16 = clear_code + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut output: Vec<u8> = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        let (mut buffer, mut num_bits, mut position) = (0u32, 0u8, 0usize);
+        loop {
+            while num_bits < code_size {
+                buffer |= (bytes[position] as u32) << num_bits;
+                position += 1;
+                num_bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as u16;
+            buffer >>= code_size;
+            num_bits -= code_size;
+
+            if code == clear_code {
+                table = (0..clear_code).map(|i| vec![i as u8]).collect();
+                table.push(Vec::new());
+                table.push(Vec::new());
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end_code {
+                return output;
+            }
+            let entry: Vec<u8> = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("Bad first code"),
+            };
+            if let Some(previous) = previous {
+                if table.len() < MAX_CODE as usize {
+                    table.push([previous, vec![entry[0]]].concat());
+                    if (table.len() == (1 << code_size)) && (code_size < 12) {
+                        code_size += 1;
+                    }
+                }
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let short: Vec<u8> = vec![0, 0, 0, 1, 1, 0, 0, 0, 1, 1, 1, 1];
+        assert_eq!(lzw_decompress(&lzw_compress(&short, 2), 2), short);
+
+        // Long and varied enough to fill the table and force a clear
+        let long: Vec<u8> = (0..60000u32).map(|i| ((i * 7919) % 13 + (i / 1000) % 3) as u8).collect();
+        assert_eq!(lzw_decompress(&lzw_compress(&long, 4), 4), long);
+    }
+
+    #[test]
+    fn table_sizes() {
+        assert_eq!(get_table_bits(1), 1);
+        assert_eq!(get_table_bits(2), 1);
+        assert_eq!(get_table_bits(3), 2);
+        assert_eq!(get_table_bits(9), 4);
+        assert_eq!(get_table_bits(256), 8);
+    }
+
+    #[test]
+    fn gif_layout() {
+        let gif = encode_gif(2, 1, &[Rgb(0, 0, 0), Rgb(255, 255, 255)], &[vec![0, 1], vec![1, 0]], 10);
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[2, 0, 1, 0]);
+        assert_eq!(gif[10], 0xf0);
+        assert!(gif.iter().filter(|&&byte| byte == 0x2c).count() >= 2);
+        assert_eq!(gif[gif.len() - 1], 0x3b);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::common::geometry::{Coordinate, Point2};
+
+// Inclusive on both ends, like the min/max pairs the days used to track
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bounds<C> {
+    pub min: Point2<C>,
+    pub max: Point2<C>,
+}
+
+impl<C: Coordinate> Bounds<C> {
+    pub fn new(min: Point2<C>, max: Point2<C>) -> Self {
+        return Self {min: min, max: max};
+    }
+
+    pub fn from_point(point: &Point2<C>) -> Self {
+        return Self::new(*point, *point);
+    }
+
+    pub fn include(&mut self, point: &Point2<C>) {
+        self.min = Point2::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point2::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = *self;
+        union.include(&other.min);
+        union.include(&other.max);
+        return union;
+    }
+
+    pub fn contains(&self, point: &Point2<C>) -> bool {
+        return (self.min.x <= point.x) && (point.x <= self.max.x)
+            && (self.min.y <= point.y) && (point.y <= self.max.y);
+    }
+
+    pub fn width(&self) -> C {
+        return self.max.x - self.min.x + C::one();
+    }
+
+    pub fn height(&self) -> C {
+        return self.max.y - self.min.y + C::one();
+    }
+
+    pub fn area(&self) -> i64 {
+        return self.width().to_i64() * self.height().to_i64();
+    }
+
+    pub fn to_i64(&self) -> Bounds<i64> {
+        return Bounds::new(self.min.to_i64(), self.max.to_i64());
+    }
+
+    pub fn expand(&self, margin: C) -> Self {
+        let margin = Point2::new(margin, margin);
+        return Self::new(self.min - margin, self.max + margin);
+    }
+
+    // Row-major offset of a point inside the bounds
+    pub fn index_of(&self, point: &Point2<C>) -> Option<usize> {
+        if !self.contains(point) {return None;}
+        let column = (point.x - self.min.x).to_i64();
+        let row = (point.y - self.min.y).to_i64();
+        return Some((row * self.width().to_i64() + column) as usize);
+    }
+
+    pub fn point_at(&self, index: usize) -> Point2<C> {
+        let width = self.width().to_i64();
+        let column = C::from_i64(index as i64 % width);
+        let row = C::from_i64(index as i64 / width);
+        return Point2::new(self.min.x + column, self.min.y + row);
+    }
+
+    pub fn rows(&self) -> Vec<Vec<Point2<C>>> {
+        let mut rows = Vec::new();
+        let mut y = self.min.y;
+        while y <= self.max.y {
+            let mut row = Vec::new();
+            let mut x = self.min.x;
+            while x <= self.max.x {
+                row.push(Point2::new(x, y));
+                x += C::one();
+            }
+            rows.push(row);
+            y += C::one();
+        }
+        return rows;
+    }
+
+    pub fn points(&self) -> Vec<Point2<C>> {
+        return self.rows().into_iter().flatten().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indices_round_trip() {
+        let bounds: Bounds<i32> = Bounds::new(Point2::new(-3, -2), Point2::new(1, 2));
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (5, 5, 25));
+        for (index, point) in bounds.points().into_iter().enumerate() {
+            assert_eq!(bounds.index_of(&point), Some(index));
+            assert_eq!(bounds.point_at(index), point);
+        }
+        assert_eq!(bounds.index_of(&Point2::new(-3, -2)), Some(0));
+        assert_eq!(bounds.index_of(&Point2::new(0, -2)), Some(3));
+        assert_eq!(bounds.index_of(&Point2::new(-3, -1)), Some(5));
+        assert_eq!(bounds.index_of(&Point2::new(2, 0)), None);
+        assert_eq!(bounds.index_of(&Point2::new(0, -3)), None);
+    }
+
+    #[test]
+    fn growing_bounds() {
+        let mut bounds: Bounds<i64> = Bounds::from_point(&Point2::new(2, 2));
+        bounds.include(&Point2::new(-1, 5));
+        assert_eq!(bounds, Bounds::new(Point2::new(-1, 2), Point2::new(2, 5)));
+        assert!(bounds.contains(&Point2::new(0, 3)));
+        assert!(!bounds.contains(&Point2::new(0, 1)));
+        let other = Bounds::from_point(&Point2::new(7, -4));
+        assert_eq!(bounds.union(&other), Bounds::new(Point2::new(-1, -4), Point2::new(7, 5)));
+        assert_eq!(bounds.expand(1), Bounds::new(Point2::new(-2, 1), Point2::new(3, 6)));
+        assert_eq!(bounds.rows()[0], vec![Point2::new(-1, 2), Point2::new(0, 2), Point2::new(1, 2), Point2::new(2, 2)]);
+    }
+}
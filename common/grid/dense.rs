@@ -0,0 +1,215 @@
+use crate::common::geometry::{Coordinate, Point2};
+
+use super::bounds::Bounds;
+use super::{char_positions, Grid};
+
+// Cells are stored row by row in one Vec covering `storage`, which can be bigger than the bounds of
+// what has been set. Setting a point outside it re-lays the storage out at least twice as far on
+// the side it had to grow, so growing a cell at a time takes amortised constant time.
+#[derive(Debug, Clone)]
+pub struct DenseGrid<C, T> {
+    cells: Vec<Option<T>>,
+    storage: Option<Bounds<C>>,
+    bounds: Option<Bounds<C>>,
+    len: usize,
+}
+
+impl<C: Coordinate, T> DenseGrid<C, T> {
+    pub fn new() -> Self {
+        return Self {cells: Vec::new(), storage: None, bounds: None, len: 0};
+    }
+
+    pub fn with_bounds(bounds: Bounds<C>) -> Self {
+        let mut cells = Vec::new();
+        cells.resize_with(bounds.area() as usize, || None);
+        return Self {cells: cells, storage: Some(bounds), bounds: Some(bounds), len: 0};
+    }
+
+    pub fn filled(bounds: Bounds<C>, value: T) -> Self where T: Clone {
+        let mut cells = Vec::new();
+        cells.resize(bounds.area() as usize, Some(value));
+        let len = cells.len();
+        return Self {cells: cells, storage: Some(bounds), bounds: Some(bounds), len: len};
+    }
+
+    fn grow_to_include(&mut self, point: &Point2<C>) {
+        let new_storage = match self.storage {
+            Some(storage) => get_grown_storage(&storage, point),
+            None => Bounds::from_point(point),
+        };
+        let mut new_cells: Vec<Option<T>> = Vec::new();
+        new_cells.resize_with(new_storage.area() as usize, || None);
+        if let Some(old_storage) = self.storage {
+            for (index, cell) in self.cells.drain(..).enumerate() {
+                let new_index = new_storage.index_of(&old_storage.point_at(index)).unwrap();
+                new_cells[new_index] = cell;
+            }
+        }
+        self.cells = new_cells;
+        self.storage = Some(new_storage);
+    }
+
+    fn index_of(&self, point: &Point2<C>) -> Option<usize> {
+        return self.storage.and_then(|storage| storage.index_of(point));
+    }
+}
+
+// Each side that has to move to take in `point` moves by at least the storage's span that way,
+// unless that goes past what the coordinate type can hold
+fn get_grown_storage<C: Coordinate>(storage: &Bounds<C>, point: &Point2<C>) -> Bounds<C> {
+    let needed = storage.union(&Bounds::from_point(point));
+    let (old, exact) = (storage.to_i64(), needed.to_i64());
+    let (width, height) = (old.max.x - old.min.x + 1, old.max.y - old.min.y + 1);
+    let grow = |exact: i64, old: i64, step: i64| -> i64 {
+        return if exact < old {exact.min(old - step)} else if exact > old {exact.max(old + step)} else {old};
+    };
+    let min = Point2::new(grow(exact.min.x, old.min.x, width), grow(exact.min.y, old.min.y, height));
+    let max = Point2::new(grow(exact.max.x, old.max.x, width), grow(exact.max.y, old.max.y, height));
+    let fits = |value: i64| C::from_i64(value).to_i64() == value;
+    if [min.x, min.y, max.x, max.y, max.x - min.x + 1, max.y - min.y + 1].into_iter().all(fits) {
+        return Bounds::new(Point2::from_i64(&min), Point2::from_i64(&max));
+    }
+    return needed;
+}
+
+impl<C: Coordinate, T> Default for DenseGrid<C, T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<C: Coordinate, T> Grid<C, T> for DenseGrid<C, T> {
+    fn get(&self, point: &Point2<C>) -> Option<&T> {
+        return self.index_of(point).and_then(|index| self.cells[index].as_ref());
+    }
+
+    fn get_mut(&mut self, point: &Point2<C>) -> Option<&mut T> {
+        return match self.index_of(point) {
+            Some(index) => self.cells[index].as_mut(),
+            None => None,
+        };
+    }
+
+    fn set(&mut self, point: Point2<C>, value: T) {
+        if self.index_of(&point).is_none() {
+            self.grow_to_include(&point);
+        }
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(&point),
+            None => self.bounds = Some(Bounds::from_point(&point)),
+        };
+        let index = self.index_of(&point).unwrap();
+        if self.cells[index].is_none() {
+            self.len += 1;
+        }
+        self.cells[index] = Some(value);
+    }
+
+    fn remove(&mut self, point: &Point2<C>) -> Option<T> {
+        let removed = match self.index_of(point) {
+            Some(index) => self.cells[index].take(),
+            None => None,
+        };
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        return removed;
+    }
+
+    fn bounds(&self) -> Option<Bounds<C>> {
+        return self.bounds;
+    }
+
+    fn len(&self) -> usize {
+        return self.len;
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Point2<C>, &'a T)> + 'a> {
+        return match self.storage {
+            Some(storage) => Box::new(
+                self.cells.iter().enumerate()
+                    .filter_map(move |(index, cell)| cell.as_ref().map(|value| (storage.point_at(index), value)))
+            ),
+            None => Box::new(std::iter::empty()),
+        };
+    }
+
+    // Sized up front so parsing doesn't re-lay the storage out for every row, though like any other
+    // grid its bounds only take in the cells that get set
+    fn from_chars<F>(input: &str, origin: Point2<C>, mut parse: F) -> Self
+    where
+        F: FnMut(Point2<C>, char) -> Option<T>,
+    {
+        let positions = char_positions(input, origin);
+        let mut grid = Self::new();
+        if let Some((first, _)) = positions.first() {
+            let mut bounds = Bounds::from_point(first);
+            for (point, _) in &positions {
+                bounds.include(point);
+            }
+            grid = Self::with_bounds(bounds);
+            grid.bounds = None;
+        }
+        for (point, tile_char) in positions {
+            if let Some(value) = parse(point, tile_char) {
+                grid.set(point, value);
+            }
+        }
+        return grid;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Walking away from the start a cell at a time only re-lays the storage out a few times, but
+    // the bounds stay tight around what's been set
+    #[test]
+    fn grows_geometrically() {
+        let mut grid: DenseGrid<i32, i32> = DenseGrid::new();
+        let mut layouts = 0;
+        for x in 0..1000 {
+            for point in [Point2::new(x, 0), Point2::new(-x, -x)] {
+                let storage = grid.storage;
+                grid.set(point, x);
+                if grid.storage != storage {
+                    layouts += 1;
+                }
+            }
+        }
+        assert!(layouts < 40, "re-laid out {} times", layouts);
+        assert_eq!(grid.bounds(), Some(Bounds::new(Point2::new(-999, -999), Point2::new(999, 0))));
+        assert_eq!(grid.len(), 1999);
+        assert_eq!(grid.get(&Point2::new(-500, -500)), Some(&500));
+        assert_eq!(grid.get(&Point2::new(500, 0)), Some(&500));
+        assert_eq!(grid.get(&Point2::new(500, -1)), None);
+        assert_eq!(grid.iter().count(), 1999);
+    }
+
+    // Doubling an i8 grid would make it wider than 127, so then it only grows as far as it has to
+    #[test]
+    fn growth_stays_in_range() {
+        let mut grid: DenseGrid<i8, ()> = DenseGrid::new();
+        for x in [0, 30, 31, -30] {
+            grid.set(Point2::new(x, 0), ());
+        }
+        assert_eq!(grid.storage, Some(Bounds::new(Point2::new(-62, 0), Point2::new(61, 0))));
+        grid.set(Point2::new(-63, 0), ());
+        assert_eq!(grid.storage, Some(Bounds::new(Point2::new(-63, 0), Point2::new(61, 0))));
+        assert_eq!(grid.bounds(), Some(Bounds::new(Point2::new(-63, 0), Point2::new(31, 0))));
+        assert_eq!(grid.len(), 5);
+    }
+
+    #[test]
+    fn sized_up_front() {
+        let bounds = Bounds::new(Point2::new(-1, -1), Point2::new(1, 1));
+        let mut grid: DenseGrid<i64, char> = DenseGrid::filled(bounds, '.');
+        assert_eq!((grid.len(), grid.bounds()), (9, Some(bounds)));
+        assert_eq!(grid.remove(&Point2::new(0, 0)), Some('.'));
+        assert_eq!((grid.len(), grid.bounds()), (8, Some(bounds)));
+        let grid: DenseGrid<i64, char> = DenseGrid::with_bounds(bounds);
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), Some(bounds));
+    }
+}
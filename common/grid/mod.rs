@@ -0,0 +1,163 @@
+pub mod bounds;
+pub mod dense;
+pub mod sparse;
+
+use crate::common::geometry::{Coordinate, Direction, Direction8, Point2};
+
+pub use self::bounds::Bounds;
+pub use self::dense::DenseGrid;
+pub use self::sparse::SparseGrid;
+
+// Storage for things laid out on a 2D grid. Bounds only ever grow: removing
+// a point leaves them where they were, like the hand-rolled min/max tracking did
+pub trait Grid<C: Coordinate, T> {
+    fn get(&self, point: &Point2<C>) -> Option<&T>;
+    fn get_mut(&mut self, point: &Point2<C>) -> Option<&mut T>;
+    fn set(&mut self, point: Point2<C>, value: T);
+    fn remove(&mut self, point: &Point2<C>) -> Option<T>;
+    fn bounds(&self) -> Option<Bounds<C>>;
+    fn len(&self) -> usize;
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Point2<C>, &'a T)> + 'a>;
+
+    fn contains(&self, point: &Point2<C>) -> bool {
+        return self.get(point).is_some();
+    }
+
+    fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    fn in_bounds(&self, point: &Point2<C>) -> bool {
+        return match self.bounds() {
+            Some(bounds) => bounds.contains(point),
+            None => false,
+        };
+    }
+
+    fn neighbours4(&self, point: &Point2<C>) -> Vec<(Point2<C>, &T)> {
+        let mut neighbours = Vec::new();
+        for direction in Direction::all() {
+            let neighbour = *point + direction.as_vector();
+            if let Some(value) = self.get(&neighbour) {
+                neighbours.push((neighbour, value));
+            }
+        }
+        return neighbours;
+    }
+
+    fn neighbours8(&self, point: &Point2<C>) -> Vec<(Point2<C>, &T)> {
+        let mut neighbours = Vec::new();
+        for direction in Direction8::all() {
+            let neighbour = *point + direction.as_vector();
+            if let Some(value) = self.get(&neighbour) {
+                neighbours.push((neighbour, value));
+            }
+        }
+        return neighbours;
+    }
+
+    // Each line of the input is a row, starting at `origin`. Characters `parse`
+    // turns into None are left empty
+    fn from_chars<F>(input: &str, origin: Point2<C>, mut parse: F) -> Self
+    where
+        Self: Sized + Default,
+        F: FnMut(Point2<C>, char) -> Option<T>,
+    {
+        let mut grid = Self::default();
+        for (point, tile_char) in char_positions(input, origin) {
+            if let Some(value) = parse(point, tile_char) {
+                grid.set(point, value);
+            }
+        }
+        return grid;
+    }
+
+    // One line per row of the bounds, padded by `margin` on every side
+    fn render<F>(&self, margin: C, to_char: F) -> String
+    where
+        F: Fn(&Point2<C>, Option<&T>) -> char,
+    {
+        let mut output = String::new();
+        if let Some(bounds) = self.bounds() {
+            let bounds = bounds.expand(margin);
+            for row in bounds.rows() {
+                for point in row {
+                    output.push(to_char(&point, self.get(&point)));
+                }
+                output.push('\n');
+            }
+        }
+        return output;
+    }
+}
+
+pub fn char_positions<C: Coordinate>(input: &str, origin: Point2<C>) -> Vec<(Point2<C>, char)> {
+    let mut positions = Vec::new();
+    for (j, line) in input.lines().enumerate() {
+        for (i, tile_char) in line.chars().enumerate() {
+            let offset = Point2::new(C::from_i64(i as i64), C::from_i64(j as i64));
+            positions.push((origin + offset, tile_char));
+        }
+    }
+    return positions;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_contents<G: Grid<i32, char>>(grid: &G) -> Vec<(Point2<i32>, char)> {
+        let mut contents: Vec<(Point2<i32>, char)> = grid.iter().map(|(point, &value)| (point, value)).collect();
+        contents.sort();
+        return contents;
+    }
+
+    #[test]
+    fn sparse_and_dense_agree() {
+        let mut sparse: SparseGrid<i32, char> = SparseGrid::new();
+        let mut dense: DenseGrid<i32, char> = DenseGrid::new();
+        let points = [(0, 0), (3, -2), (-4, 1), (3, -2), (-1, -7), (2, 5)];
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let value = (b'a' + i as u8) as char;
+            sparse.set(Point2::new(x, y), value);
+            dense.set(Point2::new(x, y), value);
+        }
+        assert_eq!(sparse.remove(&Point2::new(-4, 1)), dense.remove(&Point2::new(-4, 1)));
+        assert_eq!(sparse.remove(&Point2::new(9, 9)), dense.remove(&Point2::new(9, 9)));
+        *sparse.get_mut(&Point2::new(0, 0)).unwrap() = 'z';
+        *dense.get_mut(&Point2::new(0, 0)).unwrap() = 'z';
+
+        assert_eq!(get_contents(&sparse), get_contents(&dense));
+        assert_eq!((sparse.len(), sparse.bounds()), (dense.len(), dense.bounds()));
+        assert_eq!(sparse.bounds(), Some(Bounds::new(Point2::new(-4, -7), Point2::new(3, 5))));
+        for point in sparse.bounds().unwrap().expand(1).points() {
+            assert_eq!(sparse.get(&point), dense.get(&point));
+            assert_eq!(sparse.in_bounds(&point), dense.in_bounds(&point));
+        }
+        let render = |_: &Point2<i32>, value: Option<&char>| *value.unwrap_or(&'.');
+        assert_eq!(sparse.render(1, render), dense.render(1, render));
+    }
+
+    #[test]
+    fn neighbours_in_compass_order() {
+        let grid: DenseGrid<i32, char> = DenseGrid::from_chars("abc\ndef\nghi\n", Point2::new(-1, -1), |_, c| Some(c));
+        let letters = |neighbours: Vec<(Point2<i32>, &char)>| neighbours.into_iter().map(|(_, &c)| c).collect::<String>();
+        assert_eq!(letters(grid.neighbours4(&Point2::zero())), "bfhd");
+        assert_eq!(letters(grid.neighbours8(&Point2::zero())), "bcfihgda");
+        assert_eq!(letters(grid.neighbours4(&Point2::new(-1, -1))), "bd");
+        let sparse: SparseGrid<i32, char> = SparseGrid::from_chars("abc\ndef\nghi\n", Point2::new(-1, -1), |_, c| Some(c));
+        assert_eq!(get_contents(&sparse), get_contents(&grid));
+    }
+
+    #[test]
+    fn from_chars_bounds_only_take_in_what_was_set() {
+        let input = "....\n.#..\n..#.\n....\n";
+        let parse = |_: Point2<i32>, c: char| if c == '#' {Some(c)} else {None};
+        let sparse: SparseGrid<i32, char> = SparseGrid::from_chars(input, Point2::zero(), parse);
+        let dense: DenseGrid<i32, char> = DenseGrid::from_chars(input, Point2::zero(), parse);
+        assert_eq!(sparse.bounds(), Some(Bounds::new(Point2::new(1, 1), Point2::new(2, 2))));
+        assert_eq!(dense.bounds(), sparse.bounds());
+        let empty: DenseGrid<i32, char> = DenseGrid::from_chars("..\n..\n", Point2::zero(), parse);
+        assert_eq!((empty.bounds(), empty.len()), (None, 0));
+    }
+}
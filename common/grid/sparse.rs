@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::common::geometry::{Coordinate, Point2};
+
+use super::bounds::Bounds;
+use super::Grid;
+
+// For grids that are mostly empty or have no fixed size up front
+#[derive(Debug, Clone)]
+pub struct SparseGrid<C, T> {
+    cells: HashMap<Point2<C>, T>,
+    bounds: Option<Bounds<C>>,
+}
+
+impl<C: Coordinate, T> SparseGrid<C, T> {
+    pub fn new() -> Self {
+        return Self {cells: HashMap::new(), bounds: None};
+    }
+
+    pub fn points(&self) -> Vec<Point2<C>> {
+        return self.cells.keys().copied().collect();
+    }
+
+    pub fn include_in_bounds(&mut self, point: &Point2<C>) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(point),
+            None => self.bounds = Some(Bounds::from_point(point)),
+        };
+    }
+}
+
+impl<C: Coordinate, T> Default for SparseGrid<C, T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<C: Coordinate, T> Grid<C, T> for SparseGrid<C, T> {
+    fn get(&self, point: &Point2<C>) -> Option<&T> {
+        return self.cells.get(point);
+    }
+
+    fn get_mut(&mut self, point: &Point2<C>) -> Option<&mut T> {
+        return self.cells.get_mut(point);
+    }
+
+    fn set(&mut self, point: Point2<C>, value: T) {
+        self.include_in_bounds(&point);
+        self.cells.insert(point, value);
+    }
+
+    fn remove(&mut self, point: &Point2<C>) -> Option<T> {
+        return self.cells.remove(point);
+    }
+
+    fn bounds(&self) -> Option<Bounds<C>> {
+        return self.bounds;
+    }
+
+    fn len(&self) -> usize {
+        return self.cells.len();
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Point2<C>, &'a T)> + 'a> {
+        return Box::new(self.cells.iter().map(|(point, value)| (*point, value)));
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+use super::answer::{Answer, SolveError};
+use super::bigint::BigInt;
+use super::params::{Param, Params};
+
+// The days whose numbers can get big (07, 11, 21 and 25) are written against this, and run on
+// Checked by default or on BigInt with `--param bigint=true`
+pub trait Integer: Clone + fmt::Debug + fmt::Display + Ord + From<i64> + FromStr
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self> + Neg<Output = Self>
+{
+    // Whether the arithmetic that made this went past what the type can hold
+    fn overflowed(&self) -> bool;
+
+    fn to_i64(&self) -> Option<i64>;
+
+    fn is_zero(&self) -> bool {
+        return *self == Self::from(0);
+    }
+
+    fn abs(&self) -> Self {
+        return if *self < Self::from(0) {-self.clone()} else {self.clone()};
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() && !b.overflowed() {
+            let remainder = a.clone() % b.clone();
+            a = b;
+            b = remainder;
+        }
+        return a;
+    }
+}
+
+pub const BIGINT: Param<bool> = Param::new("bigint", "false", "work numbers out exactly instead of stopping when they overflow 64 bits");
+
+pub fn use_bigint(params: &Params) -> Result<bool, SolveError> {
+    return params.value(&BIGINT);
+}
+
+// Turns a result into the answer, or the error saying it didn't fit
+pub fn get_answer<T: Integer>(value: T, what: &str) -> Result<Answer, SolveError> {
+    if value.overflowed() {
+        return Err(SolveError::Overflow(what.to_string()));
+    }
+    return Ok(match value.to_i64() {
+        Some(value) => Answer::from(value),
+        None => Answer::from(value.to_string()),
+    });
+}
+
+// An i64 that remembers overflowing instead of wrapping or panicking. Anything worked out from an
+// overflowed value is overflowed too, like NaN, so a solver only has to check its answer at the end.
+// Dividing by zero counts as overflowing. Overflowed values sort below every number, so check for
+// them before comparing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked(Option<i64>);
+
+impl Checked {
+    pub fn get(&self) -> Option<i64> {
+        return self.0;
+    }
+
+    fn apply(self, other: Checked, op: fn(i64, i64) -> Option<i64>) -> Checked {
+        return Checked(self.0.zip(other.0).and_then(|(a, b)| op(a, b)));
+    }
+}
+
+impl From<i64> for Checked {
+    fn from(value: i64) -> Self {
+        return Checked(Some(value));
+    }
+}
+
+impl FromStr for Checked {
+    type Err = String;
+
+    // A whole number too big for i64 reads as overflowed, so it's reported like any other overflow
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = text.parse::<i64>() {
+            return Ok(Checked::from(value));
+        }
+        let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+        if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(Checked(None));
+        }
+        return Err(format!("'{}' is not a whole number", text));
+    }
+}
+
+impl fmt::Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self.0 {
+            Some(value) => write!(f, "{}", value),
+            None => write!(f, "overflow"),
+        };
+    }
+}
+
+impl Add for Checked {
+    type Output = Checked;
+    fn add(self, other: Checked) -> Checked {
+        return self.apply(other, i64::checked_add);
+    }
+}
+
+impl Sub for Checked {
+    type Output = Checked;
+    fn sub(self, other: Checked) -> Checked {
+        return self.apply(other, i64::checked_sub);
+    }
+}
+
+impl Mul for Checked {
+    type Output = Checked;
+    fn mul(self, other: Checked) -> Checked {
+        return self.apply(other, i64::checked_mul);
+    }
+}
+
+impl Div for Checked {
+    type Output = Checked;
+    fn div(self, other: Checked) -> Checked {
+        return self.apply(other, i64::checked_div);
+    }
+}
+
+impl Rem for Checked {
+    type Output = Checked;
+    fn rem(self, other: Checked) -> Checked {
+        return self.apply(other, i64::checked_rem);
+    }
+}
+
+impl Neg for Checked {
+    type Output = Checked;
+    fn neg(self) -> Checked {
+        return Checked(self.0.and_then(i64::checked_neg));
+    }
+}
+
+impl Integer for Checked {
+    fn overflowed(&self) -> bool {
+        return self.0.is_none();
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        return self.0;
+    }
+}
+
+impl Integer for BigInt {
+    fn overflowed(&self) -> bool {
+        return false;
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        return BigInt::to_i64(self);
+    }
+
+    fn is_zero(&self) -> bool {
+        return BigInt::is_zero(self);
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        return BigInt::gcd(self, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_sticks() {
+        let big = Checked::from(i64::MAX);
+        assert_eq!(big + Checked::from(-1), Checked::from(i64::MAX - 1));
+        let overflowed = big * Checked::from(2);
+        assert!(overflowed.overflowed());
+        assert!((overflowed - big).overflowed());
+        assert!((Checked::from(1) / Checked::from(0)).overflowed());
+        assert!((-Checked::from(i64::MIN)).overflowed());
+        assert_eq!(Checked::from(-12).gcd(&Checked::from(18)), Checked::from(6));
+        assert!("99999999999999999999".parse::<Checked>().unwrap().overflowed());
+        assert!("12x".parse::<Checked>().is_err());
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(get_answer(Checked::from(-3), "the total"), Ok(Answer::from(-3)));
+        assert_eq!(get_answer(Checked::from(i64::MAX) + Checked::from(1), "the total"), Err(SolveError::Overflow("the total".to_string())));
+        let big: BigInt = "100000000000000000000".parse().unwrap();
+        assert_eq!(get_answer(big, "the total"), Ok(Answer::from("100000000000000000000")));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use super::answer::Answer;
+
+// Just enough JSON to write out answers and reports; nothing here ever needs to read it back in
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object() -> Json {
+        return Json::Object(Vec::new());
+    }
+
+    // Builder-style, keeping the fields in the order they're added
+    pub fn with(mut self, key: &str, value: impl Into<Json>) -> Json {
+        if let Json::Object(fields) = &mut self {
+            fields.push((key.to_string(), value.into()));
+        }
+        return self;
+    }
+}
+
+fn write_escaped(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    return write!(f, "\"");
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Int(value) => write!(f, "{}", value),
+            Json::Float(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Float(_) => write!(f, "null"),
+            Json::Text(text) => write_escaped(f, text),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {write!(f, ",")?;}
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {write!(f, ",")?;}
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            },
+        };
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        return Json::Bool(value);
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        return Json::Int(value);
+    }
+}
+
+impl From<i32> for Json {
+    fn from(value: i32) -> Self {
+        return Json::Int(value as i64);
+    }
+}
+
+impl From<u32> for Json {
+    fn from(value: u32) -> Self {
+        return Json::Int(value as i64);
+    }
+}
+
+// Like Answer, anything that doesn't fit in an i64 goes out as a string rather than losing precision
+impl From<u64> for Json {
+    fn from(value: u64) -> Self {
+        return match i64::try_from(value) {
+            Ok(number) => Json::Int(number),
+            Err(_) => Json::Text(value.to_string()),
+        };
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        return Json::from(value as u64);
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        return Json::Float(value);
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        return Json::Text(value);
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        return Json::Text(value.to_string());
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        return match answer {
+            Answer::Number(value) => Json::Int(*value),
+            Answer::Text(text) => Json::Text(text.to_string()),
+        };
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        return Json::Array(values.into_iter().map(|value| value.into()).collect());
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        return match value {
+            Some(value) => value.into(),
+            None => Json::Null,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_values() {
+        let json = Json::object()
+            .with("day", 13)
+            .with("answer", &Answer::from("CMZ"))
+            .with("indices", vec![10, 14])
+            .with("missing", None::<i64>);
+        assert_eq!(json.to_string(), r#"{"day":13,"answer":"CMZ","indices":[10,14],"missing":null}"#);
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(Json::from("a \"b\"\n\\c\u{1}").to_string(), r#""a \"b\"\n\\c\u0001""#);
+    }
+
+    #[test]
+    fn big_numbers_become_strings() {
+        assert_eq!(Json::from(u64::MAX).to_string(), "\"18446744073709551615\"");
+        assert_eq!(Json::from(f64::NAN).to_string(), "null");
+    }
+}
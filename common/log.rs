@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use super::json::Json;
+
+// Everything the solvers say besides the answer goes to stderr through here, so stdout stays clean
+// for the answer (or the JSON object). The default only shows warnings; each -v shows one level more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+impl Level {
+    pub fn from_verbosity(num_v: usize) -> Level {
+        return match num_v {
+            0 => Level::Warn,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        };
+    }
+
+    fn as_str(&self) -> &'static str {
+        return match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+    }
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+pub fn set_verbosity(level: Level) {
+    VERBOSITY.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn is_enabled(level: Level) -> bool {
+    return level as u8 <= VERBOSITY.load(Ordering::Relaxed);
+}
+
+pub fn write(level: Level, args: fmt::Arguments) {
+    if is_enabled(level) {
+        eprintln!("[{}] {}", level.as_str(), args);
+    }
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::common::log::write($crate::common::log::Level::Warn, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::common::log::write($crate::common::log::Level::Info, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::common::log::write($crate::common::log::Level::Debug, format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)*) => { $crate::common::log::write($crate::common::log::Level::Trace, format_args!($($arg)*)) };
+}
+
+// Intermediate values a solver wants to show alongside its answer (the separator indices in 13, the
+// grove coordinates in 20...). They're logged at info level, and kept for the JSON output while a
+// recording is running on this thread.
+thread_local! {
+    static DETAILS: RefCell<Option<Vec<(String, Json)>>> = const { RefCell::new(None) };
+}
+
+pub fn detail(name: &str, value: impl Into<Json>) {
+    let value = value.into();
+    write(Level::Info, format_args!("{}: {}", name, value));
+    DETAILS.with(|details| {
+        if let Some(details) = details.borrow_mut().as_mut() {
+            details.push((name.to_string(), value));
+        }
+    });
+}
+
+pub fn start_recording() {
+    DETAILS.with(|details| *details.borrow_mut() = Some(Vec::new()));
+}
+
+pub fn take_details() -> Vec<(String, Json)> {
+    return DETAILS.with(|details| details.borrow_mut().take().unwrap_or_default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details_only_kept_while_recording() {
+        detail("ignored", 1);
+        start_recording();
+        detail("coords", vec![1, 2, 3]);
+        detail("sum", 6);
+        let details = take_details();
+        assert_eq!(details, vec![("coords".to_string(), Json::from(vec![1, 2, 3])), ("sum".to_string(), Json::Int(6))]);
+        assert!(take_details().is_empty());
+    }
+}
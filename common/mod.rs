@@ -0,0 +1,24 @@
+pub mod answer;
+pub mod bigint;
+pub mod geometry;
+pub mod gif;
+pub mod grid;
+pub mod integer;
+pub mod json;
+pub mod log;
+pub mod params;
+pub mod parse;
+pub mod part;
+pub mod png;
+pub mod progress;
+pub mod random;
+pub mod raster;
+pub mod recorder;
+pub mod render;
+pub mod search;
+pub mod simulation;
+
+pub use self::answer::{Answer, SolveError};
+pub use self::params::Params;
+pub use self::parse::ParseError;
+pub use self::part::Part;
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use super::answer::SolveError;
+
+// A puzzle knob a solver reads, declared next to the solver with its type and its default from the
+// puzzle text. The default is kept as text so they can all be listed the same way.
+pub struct Param<T> {
+    pub info: ParamInfo,
+    kind: PhantomData<T>,
+}
+
+impl<T: FromStr> Param<T> {
+    pub const fn new(name: &'static str, default: &'static str, about: &'static str) -> Param<T> {
+        return Param {info: ParamInfo {name: name, default: default, about: about, check: parses::<T>}, kind: PhantomData};
+    }
+}
+
+// What's known about a parameter without its type
+#[derive(Debug, Clone, Copy)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub about: &'static str,
+    check: fn(&str) -> bool,
+}
+
+impl ParamInfo {
+    pub fn accepts(&self, value: &str) -> bool {
+        return (self.check)(value);
+    }
+}
+
+fn parses<T: FromStr>(value: &str) -> bool {
+    return value.parse::<T>().is_ok();
+}
+
+// The values for the knobs, from `--param key=value` on the command line or a config file
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        return Params {values: HashMap::new()};
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.values.insert(key.trim().to_string(), value.trim().to_string());
+    }
+
+    pub fn insert_pair(&mut self, pair: &str) -> Result<(), String> {
+        return match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                self.insert(key, value);
+                Ok(())
+            },
+            _ => Err(format!("'{}' should look like key=value", pair)),
+        };
+    }
+
+    // Values in `other` win
+    pub fn merge(&mut self, other: &Params) {
+        for (key, value) in other.values.iter() {
+            self.insert(key, value);
+        }
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.values.keys().map(|name| name.as_str()).collect();
+        names.sort();
+        return names;
+    }
+
+    // As key=value, sorted so they come out the same way every time
+    pub fn pairs(&self) -> Vec<String> {
+        let mut pairs: Vec<String> = self.values.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+        pairs.sort();
+        return pairs;
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<Option<T>, SolveError> {
+        return match self.values.get(key) {
+            Some(value) => match value.parse::<T>() {
+                Ok(parsed) => Ok(Some(parsed)),
+                Err(_) => Err(SolveError::BadParam {name: key.to_string(), value: value.to_string()}),
+            },
+            None => Ok(None),
+        };
+    }
+
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, SolveError> {
+        return match self.get(key)? {
+            Some(value) => Ok(value),
+            None => Ok(default),
+        };
+    }
+
+    pub fn value<T: FromStr>(&self, param: &Param<T>) -> Result<T, SolveError> {
+        return match self.get(param.info.name)? {
+            Some(value) => Ok(value),
+            None => Ok(param.info.default.parse::<T>().ok().expect("Parameter defaults should parse")),
+        };
+    }
+
+    pub fn require<T: FromStr>(&self, key: &str) -> Result<T, SolveError> {
+        return match self.get(key)? {
+            Some(value) => Ok(value),
+            None => Err(SolveError::MissingParam(key.to_string())),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUNDS: Param<u32> = Param::new("rounds", "10", "how many rounds to play");
+
+    #[test]
+    fn typed_values() {
+        let mut params = Params::new();
+        assert_eq!(params.value(&ROUNDS), Ok(10));
+        params.insert_pair("rounds=3").unwrap();
+        assert_eq!(params.value(&ROUNDS), Ok(3));
+        params.insert("rounds", "-3");
+        assert_eq!(params.value(&ROUNDS), Err(SolveError::BadParam {name: "rounds".to_string(), value: "-3".to_string()}));
+        assert!(ROUNDS.info.accepts("12"));
+        assert!(!ROUNDS.info.accepts("twelve"));
+    }
+
+    #[test]
+    fn merging_overrides() {
+        let mut params = Params::new();
+        params.insert("rounds", "3");
+        params.insert("row", "10");
+        let mut overrides = Params::new();
+        overrides.insert("rounds", "4");
+        params.merge(&overrides);
+        assert_eq!(params.pairs(), vec!["rounds=4", "row=10"]);
+        assert_eq!(params.names(), vec!["rounds", "row"]);
+    }
+}
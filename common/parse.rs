@@ -0,0 +1,154 @@
+use std::fmt;
+use std::str::FromStr;
+
+// Where and why an input line couldn't be parsed. Line and column count from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, found: &str, expected: &str) -> Self {
+        return Self {
+            line: line,
+            column: column,
+            text: text.to_string(),
+            found: found.to_string(),
+            expected: expected.to_string(),
+        };
+    }
+
+    // For problems with the input as a whole, e.g. a missing section
+    pub fn whole_input(found: &str, expected: &str) -> Self {
+        return Self::new(0, 0, "", found, expected);
+    }
+
+    // The offending line with the bad text underlined, like rustc does
+    pub fn diagnostic(&self) -> String {
+        if self.line == 0 {
+            return format!("error: expected {}, found {}", self.expected, self.found);
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        let indent: String = self.text.chars().take(self.column - 1).map(|c| if c == '\t' {'\t'} else {' '}).collect();
+        let underline = "^".repeat(self.found.chars().count().max(1));
+        return format!(
+            "error: expected {}, found '{}'\n{} --> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.expected, self.found,
+            gutter, self.line, self.column,
+            gutter,
+            self.line, self.text,
+            gutter, indent, underline,
+        );
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, "expected {}, found {}", self.expected, self.found);
+        }
+        return write!(f, "line {}, column {}: expected {}, found '{}'", self.line, self.column, self.expected, self.found);
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+
+// One line of the puzzle input, remembering where it came from so errors can point at it
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // Works out the column of `token` if it is a slice of this line, otherwise
+    // falls back to where it first appears
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        let offset = if (token_start >= start) && (token_start + token.len() <= start + self.text.len()) {
+            token_start - start
+        }
+        else {
+            self.text.find(token).unwrap_or(0)
+        };
+        return self.text[..offset].chars().count() + 1;
+    }
+
+    pub fn error(&self, found: &str, expected: &str) -> ParseError {
+        return ParseError::new(self.number, self.column_of(found), self.text, found, expected);
+    }
+
+    pub fn error_at(&self, column: usize, found: &str, expected: &str) -> ParseError {
+        return ParseError::new(self.number, column, self.text, found, expected);
+    }
+
+    // The character at `column`, counting from 1
+    pub fn char_at(&self, column: usize, expected: &str) -> Result<char, ParseError> {
+        return match self.text.chars().nth(column - 1) {
+            Some(found) => Ok(found),
+            None => Err(self.error_at(column, "end of line", expected)),
+        };
+    }
+
+    // Fails at the first character that isn't allowed
+    pub fn check_chars<F: Fn(char) -> bool>(&self, is_allowed: F, expected: &str) -> Result<(), ParseError> {
+        for (index, c) in self.text.chars().enumerate() {
+            if !is_allowed(c) {
+                return Err(self.error_at(index + 1, &c.to_string(), expected));
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        return token.trim().parse::<T>().map_err(|_| self.error(token.trim(), expected));
+    }
+
+    // The `index`th whitespace separated word, counting from 0
+    pub fn word(&self, index: usize, expected: &str) -> Result<&'a str, ParseError> {
+        return match self.text.split_whitespace().nth(index) {
+            Some(word) => Ok(word),
+            None => Err(self.error_at(self.text.chars().count() + 1, "end of line", expected)),
+        };
+    }
+
+    pub fn parse_word<T: FromStr>(&self, index: usize, expected: &str) -> Result<T, ParseError> {
+        let word = self.word(index, expected)?;
+        return self.parse(word, expected);
+    }
+
+    // The text after `prefix`, which the line has to start with
+    pub fn after(&self, prefix: &str) -> Result<&'a str, ParseError> {
+        return match self.text.trim_start().strip_prefix(prefix) {
+            Some(rest) => Ok(rest),
+            None => Err(self.error(self.text.trim_start(), &format!("a line starting '{}'", prefix))),
+        };
+    }
+
+    // Splits around the first `separator`
+    pub fn split_once(&self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        let text: &'a str = self.text;
+        return match text.split_once(separator) {
+            Some(pair) => Ok(pair),
+            None => Err(self.error_at(1, text, &format!("'{}'", separator))),
+        };
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    return input.lines().enumerate().map(|(index, text)| Line {number: index + 1, text: text});
+}
+
+// For the days whose input is a picture or a single string rather than records
+pub fn check_chars<F: Fn(char) -> bool>(input: &str, is_allowed: F, expected: &str) -> Result<(), ParseError> {
+    for line in lines(input) {
+        line.check_chars(&is_allowed, expected)?;
+    }
+    return Ok(());
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn as_int(&self) -> u32 {
+        return match self {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        return match string.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("'{}' is not a valid part (expected 1 or 2)", other)),
+        };
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.as_int());
+    }
+}
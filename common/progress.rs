@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// How long solvers take, and a way to ask them to stop. A solver that notices it's been cancelled
+// gives up with SolveError::Interrupted, carrying the best answer it had found so far if it has one.
+
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+// Set by Ctrl-C, and stops every solver
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static SHOW_BARS: AtomicBool = AtomicBool::new(false);
+
+// Stops the solver running on one thread, like one that's taken too long
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        return CancelToken::default();
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
+    }
+}
+
+thread_local! {
+    static TOKEN: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// Solvers on this thread check this token from now on
+pub fn set_cancel_token(token: CancelToken) {
+    TOKEN.with(|current| *current.borrow_mut() = Some(token));
+}
+
+// Returns whether it had already been interrupted. Only touches an atomic, so it's safe in a signal handler.
+pub fn interrupt() -> bool {
+    return INTERRUPTED.swap(true, Ordering::Relaxed);
+}
+
+pub fn is_interrupted() -> bool {
+    return INTERRUPTED.load(Ordering::Relaxed);
+}
+
+// What the search loops check
+pub fn is_cancelled() -> bool {
+    return is_interrupted() || TOKEN.with(|token| token.borrow().as_ref().is_some_and(|token| token.is_cancelled()));
+}
+
+// Off unless the runner turns them on, since bars from several threads at once would be a mess
+pub fn show_bars(show: bool) {
+    SHOW_BARS.store(show, Ordering::Relaxed);
+}
+
+// A bar on stderr, redrawn at most every REDRAW_EVERY and cleared when it's dropped
+pub struct Progress {
+    label: String,
+    total: usize,
+    done: usize,
+    start: Instant,
+    last_drawn: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: usize) -> Progress {
+        return Progress {label: label.to_string(), total: total, done: 0, start: Instant::now(), last_drawn: None};
+    }
+
+    pub fn tick(&mut self) {
+        self.advance(1);
+    }
+
+    pub fn advance(&mut self, count: usize) {
+        self.done = (self.done + count).min(self.total);
+        if !SHOW_BARS.load(Ordering::Relaxed) {
+            return;
+        }
+        let now = Instant::now();
+        if self.last_drawn.is_some_and(|last_drawn| now - last_drawn < REDRAW_EVERY) {
+            return;
+        }
+        self.last_drawn = Some(now);
+        let bar = get_bar(&self.label, self.done, self.total, now - self.start);
+        let _ = write!(io::stderr(), "\r\x1b[2K{}", bar);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.last_drawn.is_some() {
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+fn get_bar(label: &str, done: usize, total: usize, elapsed: Duration) -> String {
+    let fraction = if total == 0 {1.0} else {done as f64 / total as f64};
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let eta = if done == 0 {
+        "?".to_string()
+    } else {
+        let left = elapsed.as_secs_f64() * (total - done) as f64 / done as f64;
+        format!("{:.0}s", left.ceil())
+    };
+    return format!("{} [{}{}] {:>3}% {}/{}, about {} left", label, "#".repeat(filled), "-".repeat(BAR_WIDTH - filled), (fraction * 100.0) as u32, done, total, eta);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn bars() {
+        assert_eq!(get_bar("Blueprints", 0, 4, Duration::ZERO), "Blueprints [------------------------------]   0% 0/4, about ? left");
+        assert_eq!(get_bar("Blueprints", 3, 4, Duration::from_secs(6)), "Blueprints [#######################-------]  75% 3/4, about 2s left");
+    }
+
+    #[test]
+    fn tokens_only_cancel_their_own_thread() {
+        let token = CancelToken::new();
+        let solver_token = token.clone();
+        let solver = thread::spawn(move || {
+            set_cancel_token(solver_token);
+            while !is_cancelled() {
+                thread::yield_now();
+            }
+        });
+        assert!(!is_cancelled());
+        token.cancel();
+        solver.join().unwrap();
+        assert!(!is_cancelled());
+    }
+}
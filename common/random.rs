@@ -0,0 +1,103 @@
+// A small seeded generator (SplitMix64) for the input generators. Not for anything that needs to be
+// unpredictable, but the same seed always gives the same numbers, which is what reproducing a case needs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        return Rng {state: seed};
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return z ^ (z >> 31);
+    }
+
+    // Uniform in 0..bound, throwing away the draws that would favour the low numbers
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Can't pick a number below 0");
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let draw = self.next_u64();
+            if draw < zone {
+                return draw % bound;
+            }
+        }
+    }
+
+    // Uniform in min..=max
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "The range {}..={} is empty", min, max);
+        let span = (max as i128 - min as i128 + 1) as u128;
+        if span > u64::MAX as u128 {
+            return self.next_u64() as i64;
+        }
+        return (min as i128 + self.below(span as u64) as i128) as i64;
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        return self.below(len as u64) as usize;
+    }
+
+    // In [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        return self.next_f64() < probability;
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.index(items.len())];
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut first = Rng::new(2022);
+        let mut second = Rng::new(2022);
+        let draws: Vec<u64> = (0..5).map(|_| first.next_u64()).collect();
+        assert_eq!(draws, (0..5).map(|_| second.next_u64()).collect::<Vec<u64>>());
+        assert_ne!(Rng::new(2023).next_u64(), draws[0]);
+        // The first output for seed 0, from the reference implementation
+        assert_eq!(Rng::new(0).next_u64(), 0xe220a8397b1dcdaf);
+    }
+
+    #[test]
+    fn ranges_are_inclusive_and_covered() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            let value = rng.range(-2, 2);
+            assert!((-2..=2).contains(&value));
+            seen[(value + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn shuffle_keeps_everything() {
+        let mut items: Vec<u32> = (0..20).collect();
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::png::encode_png;
+use super::render::{Colour, Render, RenderOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    // Six hex digits, with or without a leading '#'
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let hex = text.trim().trim_start_matches('#');
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2).unwrap_or("?"), 16);
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Ok(red), Ok(green), Ok(blue)) => Ok(Rgb(red, green, blue)),
+            _ => Err(format!("'{}' is not a colour like 3c7fbf", text)),
+        };
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{:02x}{:02x}{:02x}", self.0, self.1, self.2);
+    }
+}
+
+// Picks a pixel colour for each cell: a colour for a particular glyph wins, then the colour for the
+// cell's terminal colour. Blank cells are the background.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    pub background: Rgb,
+    colours: HashMap<Colour, Rgb>,
+    glyphs: HashMap<char, Rgb>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let colours = HashMap::from([
+            (Colour::Default, Rgb(0xc8, 0xc8, 0xc8)),
+            (Colour::Grey, Rgb(0x30, 0x30, 0x38)),
+            (Colour::Red, Rgb(0xe0, 0x40, 0x40)),
+            (Colour::Green, Rgb(0x40, 0xc0, 0x50)),
+            (Colour::Yellow, Rgb(0xe8, 0xc0, 0x50)),
+            (Colour::Blue, Rgb(0x40, 0x70, 0xe0)),
+            (Colour::Magenta, Rgb(0xc0, 0x50, 0xc0)),
+            (Colour::Cyan, Rgb(0x50, 0xc0, 0xd0)),
+            (Colour::White, Rgb(0xf0, 0xf0, 0xf0)),
+        ]);
+        return Palette {background: Rgb(0x10, 0x10, 0x18), colours: colours, glyphs: HashMap::new()};
+    }
+}
+
+impl Palette {
+    pub fn set_glyph(&mut self, glyph: char, rgb: Rgb) {
+        self.glyphs.insert(glyph, rgb);
+    }
+
+    pub fn set_colour(&mut self, colour: Colour, rgb: Rgb) {
+        self.colours.insert(colour, rgb);
+    }
+
+    pub fn get_rgb(&self, glyph: char, colour: Colour) -> Rgb {
+        if let Some(rgb) = self.glyphs.get(&glyph) {
+            return *rgb;
+        }
+        if glyph == ' ' {
+            return self.background;
+        }
+        return *self.colours.get(&colour).unwrap_or(&self.background);
+    }
+
+    // Overrides like `background=000000,#=ffffff,red=ff8080`: a single character sets that glyph,
+    // a colour name sets every cell of that colour
+    pub fn apply_overrides(&mut self, overrides: &str) -> Result<(), String> {
+        for pair in overrides.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or(format!("'{}' should look like key=rrggbb", pair))?;
+            let rgb: Rgb = value.parse()?;
+            let mut key_chars = key.chars();
+            match (key_chars.next(), key_chars.next(), get_colour(key)) {
+                (_, _, Some(colour)) => self.set_colour(colour, rgb),
+                _ if key == "background" => self.background = rgb,
+                (Some(glyph), None, None) => self.set_glyph(glyph, rgb),
+                _ => return Err(format!("'{}' is not a glyph, a colour name or 'background'", key)),
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn get_colour(name: &str) -> Option<Colour> {
+    return match name {
+        "default" => Some(Colour::Default),
+        "grey" | "gray" => Some(Colour::Grey),
+        "red" => Some(Colour::Red),
+        "green" => Some(Colour::Green),
+        "yellow" => Some(Colour::Yellow),
+        "blue" => Some(Colour::Blue),
+        "magenta" => Some(Colour::Magenta),
+        "cyan" => Some(Colour::Cyan),
+        "white" => Some(Colour::White),
+        _ => None,
+    };
+}
+
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>,
+}
+
+impl Image {
+    pub fn get_pixel(&self, x: u32, y: u32) -> Rgb {
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        return Rgb(self.rgb[i], self.rgb[i + 1], self.rgb[i + 2]);
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        return encode_png(self.width, self.height, &self.rgb);
+    }
+}
+
+// Each cell becomes a `cell_size` square of its palette colour. The viewport in `options` crops it
+// the same way it does for text; colour in the options doesn't matter here.
+pub fn rasterise(source: &dyn Render, options: &RenderOptions, palette: &Palette, cell_size: u32) -> Image {
+    let bounds = match (source.render_bounds(), options.viewport) {
+        (_, Some(viewport)) => Some(viewport),
+        (bounds, None) => bounds,
+    };
+    let bounds = match bounds {
+        Some(bounds) => bounds,
+        None => return Image {width: 0, height: 0, rgb: Vec::new()},
+    };
+    let picture = source.render_bounds();
+    let cell_size = cell_size.max(1);
+    let width = bounds.width() as u32 * cell_size;
+    let height = bounds.height() as u32 * cell_size;
+    let mut rgb: Vec<u8> = Vec::with_capacity(width as usize * height as usize * 3);
+    for row in bounds.rows() {
+        let mut pixel_row: Vec<u8> = Vec::with_capacity(width as usize * 3);
+        for point in row {
+            let in_picture = picture.is_some_and(|picture| picture.contains(&point));
+            let pixel = if in_picture {
+                let cell = source.cell_at(&point);
+                palette.get_rgb(cell.glyph, cell.colour)
+            } else {
+                palette.background
+            };
+            for _ in 0..cell_size {
+                pixel_row.extend_from_slice(&[pixel.0, pixel.1, pixel.2]);
+            }
+        }
+        for _ in 0..cell_size {
+            rgb.extend_from_slice(&pixel_row);
+        }
+    }
+    return Image {width: width, height: height, rgb: rgb};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::geometry::Point2;
+    use crate::common::grid::Bounds;
+    use crate::common::render::{Cell, ScreenPoint};
+
+    use super::*;
+
+    // A 2x1 picture: a red '#' then a blank
+    struct Sample;
+
+    impl Render for Sample {
+        fn render_bounds(&self) -> Option<Bounds<i64>> {
+            return Some(Bounds::new(Point2::new(0, 0), Point2::new(1, 0)));
+        }
+
+        fn cell_at(&self, point: &ScreenPoint) -> Cell {
+            return if point.x == 0 {Cell::new('#', Colour::Red)} else {Cell::plain(' ')};
+        }
+    }
+
+    #[test]
+    fn cells_become_squares() {
+        let palette = Palette::default();
+        let image = rasterise(&Sample, &RenderOptions::default(), &palette, 3);
+        assert_eq!((image.width, image.height), (6, 3));
+        assert_eq!(image.get_pixel(2, 2), Rgb(0xe0, 0x40, 0x40));
+        assert_eq!(image.get_pixel(3, 0), palette.background);
+    }
+
+    #[test]
+    fn overrides() {
+        let mut palette = Palette::default();
+        palette.apply_overrides("#=ffffff,background=000000,red=123456").unwrap();
+        assert_eq!(palette.get_rgb('#', Colour::Red), Rgb(255, 255, 255));
+        assert_eq!(palette.get_rgb('o', Colour::Red), Rgb(0x12, 0x34, 0x56));
+        assert_eq!(palette.get_rgb(' ', Colour::Default), Rgb(0, 0, 0));
+        assert!(palette.apply_overrides("sand=ffffff").is_err());
+        assert!(palette.apply_overrides("o=fffff").is_err());
+    }
+
+    #[test]
+    fn rgb_round_trip() {
+        assert_eq!("#3c7fbf".parse::<Rgb>(), Ok(Rgb(0x3c, 0x7f, 0xbf)));
+        assert_eq!(Rgb(0x3c, 0x7f, 0xbf).to_string(), "3c7fbf");
+    }
+}
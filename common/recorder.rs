@@ -0,0 +1,102 @@
+use std::cell::RefCell;
+
+use super::render::{Picture, Render};
+
+// Step-by-step simulations call `frame` once per step. Nothing happens unless a recording has been
+// started on this thread, in which case every `every`th step is kept as a picture, up to `max_frames`.
+pub struct Recording {
+    every: usize,
+    max_frames: usize,
+    num_steps: usize,
+    frames: Vec<Picture>,
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+pub fn start_recording(every: usize, max_frames: usize) {
+    let recording = Recording {every: every.max(1), max_frames: max_frames, num_steps: 0, frames: Vec::new()};
+    RECORDING.with(|recording_cell| *recording_cell.borrow_mut() = Some(recording));
+}
+
+pub fn is_recording() -> bool {
+    return RECORDING.with(|recording| recording.borrow().is_some());
+}
+
+pub fn frame(source: &dyn Render) {
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            if (recording.num_steps % recording.every == 0) && (recording.frames.len() < recording.max_frames) {
+                recording.frames.push(Picture::capture(source));
+            }
+            recording.num_steps += 1;
+        }
+    });
+}
+
+// For the end of a simulation, so the final state is always in the recording whatever `every` is
+pub fn last_frame(source: &dyn Render) {
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            let picture = Picture::capture(source);
+            if recording.frames.last() != Some(&picture) {
+                if recording.frames.len() >= recording.max_frames {
+                    recording.frames.pop();
+                }
+                recording.frames.push(picture);
+            }
+        }
+    });
+}
+
+pub fn finish_recording() -> Vec<Picture> {
+    return RECORDING.with(|recording| recording.borrow_mut().take().map(|recording| recording.frames).unwrap_or_default());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::geometry::Point2;
+    use crate::common::grid::Bounds;
+    use crate::common::render::{Cell, RenderOptions, ScreenPoint};
+
+    use super::*;
+
+    struct Counter(i64);
+
+    impl Render for Counter {
+        fn render_bounds(&self) -> Option<Bounds<i64>> {
+            return Some(Bounds::new(Point2::new(0, 0), Point2::new(0, 0)));
+        }
+
+        fn cell_at(&self, _point: &ScreenPoint) -> Cell {
+            return Cell::plain(char::from_digit(self.0 as u32, 10).unwrap());
+        }
+    }
+
+    fn get_glyphs(frames: &[Picture]) -> String {
+        return frames.iter().map(|frame| frame.render_to_string(&RenderOptions::default()).trim().to_string()).collect();
+    }
+
+    #[test]
+    fn every_nth_step_then_the_last() {
+        frame(&Counter(9));
+        start_recording(3, 10);
+        for i in 0..8 {
+            frame(&Counter(i));
+        }
+        last_frame(&Counter(7));
+        assert_eq!(get_glyphs(&finish_recording()), "0367");
+        assert!(!is_recording());
+    }
+
+    #[test]
+    fn max_frames_keeps_the_last() {
+        start_recording(1, 3);
+        for i in 0..8 {
+            frame(&Counter(i));
+        }
+        last_frame(&Counter(7));
+        assert_eq!(get_glyphs(&finish_recording()), "017");
+    }
+}
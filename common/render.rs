@@ -0,0 +1,196 @@
+use std::env;
+use std::io::{self, IsTerminal, Write};
+
+use super::geometry::Point2;
+use super::grid::Bounds;
+
+// Pictures are drawn in screen coordinates: x to the right, y down the screen. Each day maps its
+// own coordinates onto that (day 17's chamber grows upwards, so it flips y).
+pub type ScreenPoint = Point2<i64>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Default,
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    fn ansi_code(&self) -> &'static str {
+        return match self {
+            Colour::Default => "0",
+            Colour::Grey => "90",
+            Colour::Red => "31",
+            Colour::Green => "32",
+            Colour::Yellow => "33",
+            Colour::Blue => "34",
+            Colour::Magenta => "35",
+            Colour::Cyan => "36",
+            Colour::White => "97",
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Colour,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: Colour) -> Cell {
+        return Cell {glyph: glyph, colour: colour};
+    }
+
+    pub fn plain(glyph: char) -> Cell {
+        return Cell::new(glyph, Colour::Default);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    // Only this part of the picture is drawn; anything outside the picture comes out blank
+    pub viewport: Option<Bounds<i64>>,
+    pub colour: bool,
+}
+
+impl RenderOptions {
+    pub fn with_viewport(mut self, viewport: Bounds<i64>) -> RenderOptions {
+        self.viewport = Some(viewport);
+        return self;
+    }
+
+    pub fn with_colour(mut self, colour: bool) -> RenderOptions {
+        self.colour = colour;
+        return self;
+    }
+}
+
+// Anything that can be drawn as a grid of characters
+pub trait Render {
+    // The whole picture, or None if there's nothing to draw
+    fn render_bounds(&self) -> Option<Bounds<i64>>;
+    fn cell_at(&self, point: &ScreenPoint) -> Cell;
+
+    fn render(&self, out: &mut dyn Write, options: &RenderOptions) -> io::Result<()> {
+        let bounds = match (self.render_bounds(), options.viewport) {
+            (_, Some(viewport)) => viewport,
+            (Some(bounds), None) => bounds,
+            (None, None) => return Ok(()),
+        };
+        let picture = self.render_bounds();
+        let mut line = String::new();
+        for row in bounds.rows() {
+            line.clear();
+            let mut current_colour = Colour::Default;
+            for point in row {
+                let in_picture = picture.is_some_and(|picture| picture.contains(&point));
+                let cell = if in_picture {self.cell_at(&point)} else {Cell::plain(' ')};
+                if options.colour && (cell.colour != current_colour) {
+                    line += &format!("\x1b[{}m", cell.colour.ansi_code());
+                    current_colour = cell.colour;
+                }
+                line.push(cell.glyph);
+            }
+            if current_colour != Colour::Default {
+                line += "\x1b[0m";
+            }
+            writeln!(out, "{}", line)?;
+        }
+        return out.flush();
+    }
+
+    fn render_to_string(&self, options: &RenderOptions) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        self.render(&mut output, options).expect("Writing to a Vec shouldn't fail");
+        return String::from_utf8(output).expect("Rendered pictures are built from chars");
+    }
+}
+
+// A snapshot of something rendered, so it can be kept (or handed back) after the original has moved on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Picture {
+    bounds: Option<Bounds<i64>>,
+    cells: Vec<Cell>,
+}
+
+impl Picture {
+    pub fn capture(source: &dyn Render) -> Picture {
+        let bounds = source.render_bounds();
+        let cells: Vec<Cell> = match bounds {
+            Some(bounds) => bounds.points().iter().map(|point| source.cell_at(point)).collect(),
+            None => Vec::new(),
+        };
+        return Picture {bounds: bounds, cells: cells};
+    }
+}
+
+impl Render for Picture {
+    fn render_bounds(&self) -> Option<Bounds<i64>> {
+        return self.bounds;
+    }
+
+    fn cell_at(&self, point: &ScreenPoint) -> Cell {
+        return match self.bounds.and_then(|bounds| bounds.index_of(point)) {
+            Some(index) => self.cells[index],
+            None => Cell::plain(' '),
+        };
+    }
+}
+
+// Colour is only worth sending to a terminal, and https://no-color.org asks for a way to turn it off
+pub fn stdout_supports_colour() -> bool {
+    return io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+}
+
+pub fn stderr_supports_colour() -> bool {
+    return io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 3x2 picture with a coloured cell in the middle of the top row
+    struct Sample;
+
+    impl Render for Sample {
+        fn render_bounds(&self) -> Option<Bounds<i64>> {
+            return Some(Bounds::new(Point2::new(0, 0), Point2::new(2, 1)));
+        }
+
+        fn cell_at(&self, point: &ScreenPoint) -> Cell {
+            return if *point == Point2::new(1, 0) {Cell::new('#', Colour::Red)} else {Cell::plain('.')};
+        }
+    }
+
+    #[test]
+    fn plain_render() {
+        assert_eq!(Sample.render_to_string(&RenderOptions::default()), ".#.\n...\n");
+    }
+
+    #[test]
+    fn viewport_crops_and_pads() {
+        let options = RenderOptions::default().with_viewport(Bounds::new(Point2::new(1, -1), Point2::new(3, 0)));
+        assert_eq!(Sample.render_to_string(&options), "   \n#. \n");
+    }
+
+    #[test]
+    fn picture_matches_source() {
+        let picture = Picture::capture(&Sample);
+        assert_eq!(picture.render_bounds(), Sample.render_bounds());
+        assert_eq!(picture.render_to_string(&RenderOptions::default()), ".#.\n...\n");
+    }
+
+    #[test]
+    fn colour_codes_only_on_change() {
+        let options = RenderOptions::default().with_colour(true);
+        assert_eq!(Sample.render_to_string(&options), ".\x1b[31m#\x1b[0m.\n...\n");
+    }
+}
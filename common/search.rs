@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::common::progress;
+
+// Anything that can be summed along a path and compared, e.g. u32 or i64
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+// Distances are from the nearest start. `goal` is the first goal node reached,
+// if the search was given one and it could be reached. A search that notices the
+// solver's been cancelled stops where it is, so then the distances are only partial
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub distances: HashMap<N, C>,
+    pub previous: HashMap<N, N>,
+    pub goal: Option<N>,
+    pub cancelled: bool,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> SearchResult<N, C> {
+    fn new() -> Self {
+        return Self {distances: HashMap::new(), previous: HashMap::new(), goal: None, cancelled: false};
+    }
+
+    pub fn distance_to(&self, node: &N) -> Option<C> {
+        return self.distances.get(node).copied();
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        return self.goal.as_ref().and_then(|goal| self.distance_to(goal));
+    }
+
+    // The nodes from a start up to and including `node`
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {return None;}
+        let mut path: Vec<N> = vec![node.clone()];
+        let mut current: &N = node;
+        while let Some(previous) = self.previous.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        return Some(path);
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        return self.goal.as_ref().and_then(|goal| self.path_to(goal));
+    }
+}
+
+// Breadth first search where every step costs 1. Stops as soon as a goal is found
+pub fn bfs<N, I, F, G>(starts: I, mut neighbours: F, mut is_goal: G) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> Vec<N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut result: SearchResult<N, usize> = SearchResult::new();
+    let mut queue: VecDeque<N> = VecDeque::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        if progress::is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+        if is_goal(&current) {
+            result.goal = Some(current);
+            break;
+        }
+        let distance = result.distances[&current] + 1;
+        for next in neighbours(&current) {
+            if !result.distances.contains_key(&next) {
+                result.distances.insert(next.clone(), distance);
+                result.previous.insert(next.clone(), current.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    return result;
+}
+
+// Everything reachable from `start`, including `start` itself
+pub fn flood_fill<N, F>(start: N, neighbours: F) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> Vec<N>,
+{
+    return bfs([start], neighbours, |_| false).distances.into_keys().collect();
+}
+
+pub fn dijkstra<N, C, I, F, G>(starts: I, neighbours: F, is_goal: G) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> Vec<(N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    return a_star(starts, neighbours, |_| C::default(), is_goal);
+}
+
+// The heuristic must never overestimate the cost to a goal, or the path found
+// may not be the shortest
+pub fn a_star<N, C, I, F, H, G>(starts: I, mut neighbours: F, mut heuristic: H, mut is_goal: G) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> Vec<(N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut result: SearchResult<N, C> = SearchResult::new();
+    // Nodes live in `nodes` so the heap only has to order indices. The counter
+    // breaks ties in the order nodes were queued
+    let mut nodes: Vec<N> = Vec::new();
+    let mut queue: BinaryHeap<Reverse<(C, usize, C)>> = BinaryHeap::new();
+    let mut explored: HashSet<N> = HashSet::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), C::default());
+            queue.push(Reverse((heuristic(&start), nodes.len(), C::default())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, index, distance))) = queue.pop() {
+        if progress::is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+        let current: N = nodes[index].clone();
+        if explored.contains(&current) || (distance > result.distances[&current]) {
+            continue;
+        }
+        if is_goal(&current) {
+            result.goal = Some(current);
+            break;
+        }
+        for (next, step_cost) in neighbours(&current) {
+            let next_distance: C = distance + step_cost;
+            let improved = match result.distances.get(&next) {
+                Some(existing) => next_distance < *existing,
+                None => true,
+            };
+            if improved && !explored.contains(&next) {
+                result.distances.insert(next.clone(), next_distance);
+                result.previous.insert(next.clone(), current.clone());
+                queue.push(Reverse((next_distance + heuristic(&next), nodes.len(), next_distance)));
+                nodes.push(next);
+            }
+        }
+        explored.insert(current);
+    }
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A directed graph as (from, to, cost) edges
+    fn get_neighbours(edges: &[(char, char, u32)]) -> impl FnMut(&char) -> Vec<(char, u32)> + '_ {
+        return move |node: &char| edges.iter().filter(|edge| edge.0 == *node).map(|edge| (edge.1, edge.2)).collect();
+    }
+
+    // A 5x5 grid with a wall down the middle, open only at the bottom
+    fn get_grid_neighbours(point: &(i32, i32)) -> Vec<((i32, i32), u32)> {
+        let (x, y) = *point;
+        return [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && !((x == 2) && (y < 4)))
+            .map(|point| (point, 1))
+            .collect();
+    }
+
+    #[test]
+    fn shortest_path() {
+        let edges = [('a', 'b', 1), ('a', 'c', 4), ('b', 'c', 1), ('c', 'd', 1), ('b', 'd', 5)];
+        let result = dijkstra(['a'], get_neighbours(&edges), |&node| node == 'd');
+        assert_eq!(result.goal, Some('d'));
+        assert_eq!(result.goal_distance(), Some(3));
+        assert_eq!(result.goal_path(), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(result.path_to(&'a'), Some(vec!['a']));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let edges = [('a', 'b', 1), ('c', 'd', 1)];
+        let result = dijkstra(['a'], get_neighbours(&edges), |&node| node == 'd');
+        assert_eq!((result.goal, result.goal_distance(), result.goal_path()), (None, None, None));
+        assert_eq!(result.distance_to(&'b'), Some(1));
+        assert_eq!(result.path_to(&'c'), None);
+        let result = bfs(['a'], |&node| get_neighbours(&edges)(&node).into_iter().map(|(next, _)| next).collect(), |&node| node == 'd');
+        assert_eq!(result.goal, None);
+    }
+
+    #[test]
+    fn zero_cost_edges() {
+        let edges = [('a', 'b', 0), ('b', 'c', 2), ('a', 'c', 3)];
+        let result = dijkstra(['a'], get_neighbours(&edges), |&node| node == 'c');
+        assert_eq!(result.distance_to(&'b'), Some(0));
+        assert_eq!(result.goal_distance(), Some(2));
+        assert_eq!(result.goal_path(), Some(vec!['a', 'b', 'c']));
+    }
+
+    // Of two equally short paths the one through the neighbour listed first wins
+    #[test]
+    fn ties_go_to_the_first_queued() {
+        let edges = [('a', 'b', 1), ('a', 'c', 1), ('b', 'd', 1), ('c', 'd', 1)];
+        let result = dijkstra(['a'], get_neighbours(&edges), |&node| node == 'd');
+        assert_eq!(result.goal_path(), Some(vec!['a', 'b', 'd']));
+        let edges = [('a', 'c', 1), ('a', 'b', 1), ('b', 'd', 1), ('c', 'd', 1)];
+        let result = bfs(['a'], |&node| get_neighbours(&edges)(&node).into_iter().map(|(next, _)| next).collect(), |&node| node == 'd');
+        assert_eq!(result.goal_path(), Some(vec!['a', 'c', 'd']));
+    }
+
+    #[test]
+    fn a_star_matches_dijkstra() {
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i32, i32)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as u32;
+        let with_heuristic = a_star([(0, 0)], get_grid_neighbours, manhattan, |&point| point == goal);
+        let without = dijkstra([(0, 0)], get_grid_neighbours, |&point| point == goal);
+        // Down the left side, along the bottom and up the right
+        assert_eq!(with_heuristic.goal_distance(), Some(12));
+        assert_eq!(with_heuristic.goal_distance(), without.goal_distance());
+        assert_eq!(with_heuristic.goal_path().unwrap().len(), 13);
+        // The heuristic only saves work
+        assert!(with_heuristic.distances.len() <= without.distances.len());
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        let neighbours = |point: &(i32, i32)| get_grid_neighbours(point).into_iter().map(|(next, _)| next).collect();
+        let result = bfs([(0, 0), (4, 0)], neighbours, |_| false);
+        assert_eq!(result.distance_to(&(1, 0)), Some(1));
+        assert_eq!(result.distance_to(&(3, 0)), Some(1));
+        assert_eq!(result.distance_to(&(2, 4)), Some(6));
+        assert_eq!(result.distance_to(&(2, 0)), None);
+        assert_eq!(result.distances.len(), 21);
+    }
+
+    #[test]
+    fn flood_fills() {
+        let edges = [('a', 'b', 1), ('b', 'a', 1), ('b', 'c', 1), ('d', 'a', 1)];
+        let neighbours = |node: &char| get_neighbours(&edges)(node).into_iter().map(|(next, _)| next).collect();
+        assert_eq!(flood_fill('a', neighbours), HashSet::from(['a', 'b', 'c']));
+        assert_eq!(flood_fill('c', neighbours), HashSet::from(['c']));
+    }
+
+    #[test]
+    fn cancelled_searches_stop() {
+        let token = progress::CancelToken::new();
+        token.cancel();
+        let (bfs_result, dijkstra_result) = std::thread::spawn(move || {
+            progress::set_cancel_token(token);
+            let bfs_result = bfs([(0, 0)], |&point| get_grid_neighbours(&point).into_iter().map(|(next, _)| next).collect(), |&point| point == (3, 3));
+            return (bfs_result, dijkstra([(0, 0)], get_grid_neighbours, |&point| point == (3, 3)));
+        }).join().unwrap();
+        assert!(bfs_result.cancelled && dijkstra_result.cancelled);
+        assert_eq!((bfs_result.goal, dijkstra_result.goal), (None, None));
+        assert!(!bfs([(0, 0)], |_| Vec::new(), |_| false).cancelled);
+    }
+}
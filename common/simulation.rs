@@ -0,0 +1,17 @@
+use super::render::{Render, ScreenPoint};
+
+// A puzzle state that can be advanced a step at a time, for stepping through with `aoc step`.
+// Going backwards is done by resetting and stepping forwards again, so steps must be repeatable.
+pub trait Simulation: Render {
+    // Moves on one step; false (with nothing changed) once the simulation has finished
+    fn step(&mut self) -> bool;
+    // Back to the state before the first step
+    fn reset(&mut self);
+    // A line about the whole state, shown under the picture
+    fn status(&self) -> String;
+
+    // What's at a point of the picture, in the day's own terms
+    fn describe(&self, point: &ScreenPoint) -> String {
+        return format!("'{}'", self.cell_at(point).glyph);
+    }
+}
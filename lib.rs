@@ -0,0 +1,172 @@
+pub mod common;
+pub mod runner;
+
+#[path = "01/mod.rs"]
+pub mod day01;
+#[path = "02/mod.rs"]
+pub mod day02;
+#[path = "03/mod.rs"]
+pub mod day03;
+#[path = "04/mod.rs"]
+pub mod day04;
+#[path = "05/mod.rs"]
+pub mod day05;
+#[path = "06/mod.rs"]
+pub mod day06;
+#[path = "07/mod.rs"]
+pub mod day07;
+#[path = "08/mod.rs"]
+pub mod day08;
+#[path = "09/mod.rs"]
+pub mod day09;
+#[path = "10/mod.rs"]
+pub mod day10;
+#[path = "11/mod.rs"]
+pub mod day11;
+#[path = "12/mod.rs"]
+pub mod day12;
+#[path = "13/mod.rs"]
+pub mod day13;
+#[path = "14/mod.rs"]
+pub mod day14;
+#[path = "15/mod.rs"]
+pub mod day15;
+#[path = "16/mod.rs"]
+pub mod day16;
+#[path = "17/mod.rs"]
+pub mod day17;
+#[path = "18/mod.rs"]
+pub mod day18;
+#[path = "19/mod.rs"]
+pub mod day19;
+#[path = "20/mod.rs"]
+pub mod day20;
+#[path = "21/mod.rs"]
+pub mod day21;
+#[path = "22/mod.rs"]
+pub mod day22;
+#[path = "23/mod.rs"]
+pub mod day23;
+#[path = "24/mod.rs"]
+pub mod day24;
+#[path = "25/mod.rs"]
+pub mod day25;
+
+use common::{Answer, Params, Part, SolveError};
+use common::params::ParamInfo;
+use common::random::Rng;
+use common::render::Picture;
+use common::simulation::Simulation;
+
+pub type SolveFn = fn(Part, &str, &Params) -> Result<Answer, SolveError>;
+
+pub const DAYS: [SolveFn; 25] = [
+    day01::solve_with_params,
+    day02::solve_with_params,
+    day03::solve_with_params,
+    day04::solve_with_params,
+    day05::solve_with_params,
+    day06::solve_with_params,
+    day07::solve_with_params,
+    day08::solve_with_params,
+    day09::solve_with_params,
+    day10::solve_with_params,
+    day11::solve_with_params,
+    day12::solve_with_params,
+    day13::solve_with_params,
+    day14::solve_with_params,
+    day15::solve_with_params,
+    day16::solve_with_params,
+    day17::solve_with_params,
+    day18::solve_with_params,
+    day19::solve_with_params,
+    day20::solve_with_params,
+    day21::solve_with_params,
+    day22::solve_with_params,
+    day23::solve_with_params,
+    day24::solve_with_params,
+    day25::solve_with_params,
+];
+
+pub fn solve(day: usize, part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    if day == 0 || day > DAYS.len() {
+        return Err(SolveError::NoSuchDay(day));
+    }
+    return DAYS[day - 1](part, input, params);
+}
+
+// The knobs each part reads, with their defaults from the puzzle text
+pub fn params(day: usize, part: Part) -> &'static [&'static ParamInfo] {
+    return match day {
+        1 => day01::params(part),
+        2 => day02::params(part),
+        6 => day06::params(part),
+        7 => day07::params(part),
+        9 => day09::params(part),
+        11 => day11::params(part),
+        13 => day13::params(part),
+        14 => day14::params(part),
+        15 => day15::params(part),
+        16 => day16::params(part),
+        17 => day17::params(part),
+        19 => day19::params(part),
+        20 => day20::params(part),
+        21 => day21::params(part),
+        22 => day22::params(part),
+        25 => day25::params(part),
+        _ => &[],
+    };
+}
+
+// The final state of the days that have something worth drawing
+pub fn picture(day: usize, part: Part, input: &str, params: &Params) -> Result<Picture, SolveError> {
+    return match day {
+        10 => day10::picture(part, input, params),
+        14 => day14::picture(part, input, params),
+        17 => day17::picture(part, input, params),
+        22 => day22::picture(part, input, params),
+        23 => day23::picture(part, input, params),
+        24 => day24::picture(part, input, params),
+        _ if (1..=DAYS.len()).contains(&day) => Err(SolveError::NoPicture(day)),
+        _ => Err(SolveError::NoSuchDay(day)),
+    };
+}
+
+// The days whose puzzles play out step by step
+pub fn simulation(day: usize, part: Part, input: &str, params: &Params) -> Result<Box<dyn Simulation>, SolveError> {
+    return match day {
+        9 => day09::simulation(part, input, params),
+        14 => day14::simulation(part, input, params),
+        17 => day17::simulation(part, input, params),
+        22 => day22::simulation(part, input, params),
+        23 => day23::simulation(part, input, params),
+        24 => day24::simulation(part, input, params),
+        _ if (1..=DAYS.len()).contains(&day) => Err(SolveError::NoSimulation(day)),
+        _ => Err(SolveError::NoSuchDay(day)),
+    };
+}
+
+// Random inputs for the days with a simple brute force to check the solvers against
+pub fn generate(day: usize, rng: &mut Rng) -> Result<(String, Params), SolveError> {
+    return match day {
+        9 => Ok(day09::fuzz::generate(rng)),
+        11 => Ok(day11::fuzz::generate(rng)),
+        15 => Ok(day15::fuzz::generate(rng)),
+        16 => Ok(day16::fuzz::generate(rng)),
+        22 => Ok(day22::fuzz::generate(rng)),
+        _ if (1..=DAYS.len()).contains(&day) => Err(SolveError::NoGenerator(day)),
+        _ => Err(SolveError::NoSuchDay(day)),
+    };
+}
+
+pub fn brute_force(day: usize, part: Part, input: &str, params: &Params) -> Result<Answer, SolveError> {
+    return match day {
+        9 => day09::fuzz::brute_force(part, input, params),
+        11 => day11::fuzz::brute_force(part, input, params),
+        15 => day15::fuzz::brute_force(part, input, params),
+        16 => day16::fuzz::brute_force(part, input, params),
+        22 => day22::fuzz::brute_force(part, input, params),
+        _ if (1..=DAYS.len()).contains(&day) => Err(SolveError::NoGenerator(day)),
+        _ => Err(SolveError::NoSuchDay(day)),
+    };
+}
@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::progress::{self, CancelToken};
+use crate::log_info;
+use crate::log_warn;
+
+use super::config::{self, Config};
+use super::input::{read_input, InputSource};
+use super::interrupt::INTERRUPTED_EXIT_CODE;
+use super::table;
+use super::verify::{format_duration, summarise};
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct AllOptions {
+    pub days: Vec<usize>,
+    // Defaults to the number of cores
+    pub jobs: Option<usize>,
+    pub timeout: Duration,
+    pub example: bool,
+    // From the command line, so the config file still has to be applied
+    pub params: Params,
+}
+
+struct Task {
+    day: usize,
+    part: Part,
+    input: Arc<String>,
+    params: Arc<Params>,
+}
+
+enum Status {
+    Finished(Result<Answer, SolveError>),
+    Panicked,
+    TimedOut,
+    NoInput,
+}
+
+struct Outcome {
+    day: usize,
+    part: Part,
+    status: Status,
+    elapsed: Duration,
+}
+
+impl Outcome {
+    fn solved(&self) -> bool {
+        return matches!(self.status, Status::Finished(Ok(_)));
+    }
+}
+
+// Solves every part of every day (or the ones asked for) on a pool of threads, then prints a table
+// of the answers. The exit code is 0 only if they were all solved in time.
+pub fn main(options: &AllOptions, config: &Config) -> i32 {
+    let days: Vec<usize> = if options.days.is_empty() {(1..=crate::DAYS.len()).collect()} else {options.days.clone()};
+    config::warn_unused(&options.params, &days);
+    let jobs = options.jobs.unwrap_or_else(|| thread::available_parallelism().map(|jobs| jobs.get()).unwrap_or(4));
+    let source = InputSource::Cached {example: options.example};
+
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut outcomes: Vec<Outcome> = Vec::new();
+    let mut missing: Vec<String> = Vec::new();
+    for &day in days.iter() {
+        match read_input(&source, day) {
+            Ok(input) => {
+                let input = Arc::new(input.text);
+                tasks.extend([Part::One, Part::Two].iter().map(|&part| {
+                    let params = Arc::new(config.params_for(day, part, &options.params));
+                    return Task {day: day, part: part, input: Arc::clone(&input), params: params};
+                }));
+            },
+            Err(message) => {
+                outcomes.extend([Part::One, Part::Two].iter().map(|&part| Outcome {day: day, part: part, status: Status::NoInput, elapsed: Duration::ZERO}));
+                missing.push(message);
+            },
+        }
+    }
+    if tasks.is_empty() {
+        eprintln!("{}", missing.first().map(|message| message.as_str()).unwrap_or("There are no days to run"));
+        return 1;
+    }
+    for message in missing.iter() {
+        log_warn!("{}", message);
+    }
+
+    let threads = if jobs == 1 {"thread"} else {"threads"};
+    println!("Solving {} parts on {} {}, giving each up to {}\n", tasks.len(), jobs, threads, format_duration(options.timeout));
+    let start = Instant::now();
+    outcomes.extend(run_pool(tasks, jobs, options.timeout));
+    let wall_time = start.elapsed();
+
+    // Day 25 only has the one part
+    outcomes.retain(|outcome| !matches!(outcome.status, Status::Finished(Err(SolveError::NoSuchPart(_, _)))));
+    outcomes.sort_by_key(|outcome| (outcome.day, outcome.part.as_int()));
+    print_table(&outcomes);
+
+    let count = |status: fn(&Status) -> bool| outcomes.iter().filter(|outcome| status(&outcome.status)).count();
+    let solved = count(|status| matches!(status, Status::Finished(Ok(_))));
+    let timed_out = count(|status| matches!(status, Status::TimedOut));
+    let no_input = count(|status| matches!(status, Status::NoInput));
+    let interrupted = count(|status| matches!(status, Status::Finished(Err(SolveError::Interrupted {..}))));
+    let failed = outcomes.len() - solved - timed_out - no_input - interrupted;
+    let solve_time: Duration = outcomes.iter().map(|outcome| outcome.elapsed).sum();
+    println!("\n{} solved, {} failed, {} timed out, {} without input", solved, failed, timed_out, no_input);
+    if interrupted > 0 {
+        println!("Interrupted before finishing {}", interrupted);
+    }
+    println!("{} of solving in {}", format_duration(solve_time), format_duration(wall_time));
+    if progress::is_interrupted() {
+        return INTERRUPTED_EXIT_CODE;
+    }
+    return if outcomes.iter().all(|outcome| outcome.solved()) {0} else {1};
+}
+
+// Each worker takes the next task off the queue until there are none left. The results come back in
+// the order they finish.
+fn run_pool(tasks: Vec<Task>, jobs: usize, timeout: Duration) -> Vec<Outcome> {
+    let num_workers = jobs.clamp(1, tasks.len().max(1));
+    let queue = Arc::new(Mutex::new(VecDeque::from(tasks)));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..num_workers {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            loop {
+                let task = match queue.lock().unwrap().pop_front() {
+                    Some(task) => task,
+                    None => return,
+                };
+                if sender.send(run_task(task, timeout)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    drop(sender);
+    return receiver.iter().collect();
+}
+
+// The solver gets a thread of its own so the worker can stop waiting for it. A solver that times out
+// is cancelled through its token. The shared searches and the solvers with long loops of their own
+// check for that and stop, but the rest carry on in the background until they finish or the
+// process exits, still taking up a core.
+fn run_task(task: Task, timeout: Duration) -> Outcome {
+    let (day, part) = (task.day, task.part);
+    // After Ctrl-C, whatever hasn't started yet doesn't get to
+    if progress::is_interrupted() {
+        return Outcome {day: day, part: part, status: Status::Finished(Err(SolveError::Interrupted {best: None})), elapsed: Duration::ZERO};
+    }
+    let (sender, receiver) = mpsc::channel();
+    let token = CancelToken::new();
+    let solver_token = token.clone();
+    let start = Instant::now();
+    thread::spawn(move || {
+        progress::set_cancel_token(solver_token);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            return crate::solve(task.day, task.part, &task.input, &task.params);
+        }));
+        let _ = sender.send((result, start.elapsed()));
+    });
+
+    let (status, elapsed) = match receiver.recv_timeout(timeout) {
+        Ok((Ok(result), elapsed)) => (Status::Finished(result), elapsed),
+        Ok((Err(_), elapsed)) => (Status::Panicked, elapsed),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            token.cancel();
+            (Status::TimedOut, timeout)
+        },
+        Err(mpsc::RecvTimeoutError::Disconnected) => (Status::Panicked, start.elapsed()),
+    };
+    log_info!("Day {:02} part {} done after {}", day, part, format_duration(elapsed));
+    return Outcome {day: day, part: part, status: status, elapsed: elapsed};
+}
+
+fn print_table(outcomes: &[Outcome]) {
+    let headers = ["Day", "Part", "Answer", "Time", "Result"];
+    let rows: Vec<Vec<String>> = outcomes.iter().map(|outcome| {
+        let (answer, result) = match &outcome.status {
+            Status::Finished(Ok(answer)) => (summarise(&answer.to_string()), "ok".to_string()),
+            Status::Finished(Err(SolveError::Interrupted {best: Some(best)})) => (summarise(&best.to_string()), "INTERRUPTED (best so far)".to_string()),
+            Status::Finished(Err(SolveError::Interrupted {best: None})) => ("-".to_string(), "INTERRUPTED".to_string()),
+            Status::Finished(Err(SolveError::Parse(error))) => ("-".to_string(), format!("ERROR: parse error at {}", error)),
+            Status::Finished(Err(error)) => ("-".to_string(), format!("ERROR: {}", error)),
+            Status::Panicked => ("-".to_string(), "ERROR: panicked".to_string()),
+            Status::TimedOut => ("-".to_string(), "TIMED OUT".to_string()),
+            Status::NoInput => ("-".to_string(), "no input".to_string()),
+        };
+        let time = if matches!(outcome.status, Status::NoInput) {"-".to_string()} else {format_duration(outcome.elapsed)};
+        return vec![format!("{:02}", outcome.day), outcome.part.to_string(), answer, time, result];
+    }).collect();
+
+    table::print_table(&headers, &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_tasks(day: usize, input: &str) -> Vec<Task> {
+        let input = Arc::new(input.to_string());
+        let params = Arc::new(Params::new());
+        return [Part::One, Part::Two].iter().map(|&part| Task {day: day, part: part, input: Arc::clone(&input), params: Arc::clone(&params)}).collect();
+    }
+
+    #[test]
+    fn pool_solves_everything() {
+        let mut tasks = get_tasks(1, include_str!("../examples/01.txt"));
+        tasks.extend(get_tasks(4, include_str!("../examples/04.txt")));
+        tasks.extend(get_tasks(13, include_str!("../examples/13.txt")));
+        let mut outcomes = run_pool(tasks, 2, DEFAULT_TIMEOUT);
+        outcomes.sort_by_key(|outcome| (outcome.day, outcome.part.as_int()));
+
+        let answers: Vec<(usize, u32, Option<Answer>)> = outcomes.iter().map(|outcome| {
+            let answer = match &outcome.status {
+                Status::Finished(Ok(answer)) => Some(answer.clone()),
+                _ => None,
+            };
+            return (outcome.day, outcome.part.as_int(), answer);
+        }).collect();
+        assert_eq!(answers[0], (1, 1, Some(Answer::from(24000))));
+        assert_eq!(answers[2..], [(4, 1, Some(Answer::from(2))), (4, 2, Some(Answer::from(4))), (13, 1, Some(Answer::from(13))), (13, 2, Some(Answer::from(140)))]);
+    }
+
+    #[test]
+    fn slow_parts_time_out() {
+        // Part 2 takes the best part of a minute on the example
+        let tasks = get_tasks(19, include_str!("../examples/19.txt")).into_iter().filter(|task| task.part == Part::Two).collect();
+        let outcomes = run_pool(tasks, 1, Duration::from_millis(50));
+        assert!(matches!(outcomes[0].status, Status::TimedOut));
+        assert_eq!(outcomes[0].elapsed, Duration::from_millis(50));
+    }
+}
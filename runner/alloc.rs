@@ -0,0 +1,52 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NUM_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static NUM_BYTES: AtomicU64 = AtomicU64::new(0);
+
+// Wraps the system allocator and counts every allocation; the aoc binary installs it as the global
+// allocator so the benchmarks can report allocations. Without it the counts just stay at zero.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        return System.alloc(layout);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // A growing realloc counts as a new allocation of the extra bytes
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_BYTES.fetch_add(new_size.saturating_sub(layout.size()) as u64, Ordering::Relaxed);
+        return System.realloc(ptr, layout, new_size);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocCount {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+pub fn get_alloc_count() -> AllocCount {
+    return AllocCount {
+        allocations: NUM_ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: NUM_BYTES.load(Ordering::Relaxed),
+    };
+}
+
+impl std::ops::Sub for AllocCount {
+    type Output = AllocCount;
+
+    fn sub(self, other: AllocCount) -> AllocCount {
+        return AllocCount {
+            allocations: self.allocations - other.allocations,
+            bytes: self.bytes - other.bytes,
+        };
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use super::alloc::{get_alloc_count, AllocCount};
+use super::manifest::{load_manifest, Case};
+use super::table;
+use super::verify::{format_duration, summarise};
+
+pub const DEFAULT_REPETITIONS: usize = 10;
+
+pub struct BenchOptions {
+    pub manifest: String,
+    pub repetitions: usize,
+    pub days: Vec<usize>,
+    pub report: Option<String>,
+    pub baseline: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub repetitions: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    pub allocs: AllocCount,
+}
+
+pub struct Timing {
+    pub case: Case,
+    pub answer: String,
+    pub stats: Result<Stats, String>,
+}
+
+impl Timing {
+    // Identifies the same benchmark across reports
+    pub fn get_key(&self) -> String {
+        return get_key(self.case.day, &self.case.part.to_string(), &self.case.input.display().to_string(), &self.get_params());
+    }
+
+    fn get_params(&self) -> String {
+        return if self.case.param_pairs.is_empty() {"-".to_string()} else {self.case.param_pairs.join(",")};
+    }
+}
+
+fn get_key(day: usize, part: &str, input: &str, params: &str) -> String {
+    return format!("{:02} {} {} {}", day, part, input, params);
+}
+
+// Times every case in the manifest, prints a table and optionally writes a report that later runs can be compared against
+pub fn main(options: &BenchOptions) -> i32 {
+    let cases = match load_manifest(&options.manifest) {
+        Ok(cases) => cases,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let baseline = match &options.baseline {
+        Some(file_name) => match fs::read_to_string(file_name) {
+            Ok(text) => Some(read_report(&text)),
+            Err(error) => {
+                eprintln!("Couldn't read '{}': {}", file_name, error);
+                return 1;
+            },
+        },
+        None => None,
+    };
+
+    let timings: Vec<Timing> = cases.into_iter()
+        .filter(|case| options.days.is_empty() || options.days.contains(&case.day))
+        .map(|case| time_case(case, options.repetitions))
+        .collect();
+    print_table(&timings, baseline.as_ref());
+
+    if let Some(file_name) = &options.report {
+        if let Err(error) = fs::write(file_name, format_report(&timings)) {
+            eprintln!("Couldn't write '{}': {}", file_name, error);
+            return 1;
+        }
+        println!("\nReport written to {}", file_name);
+    }
+    return if timings.iter().all(|timing| timing.stats.is_ok()) {0} else {1};
+}
+
+// One untimed warm-up run (which also counts the allocations), then the timed repetitions
+pub fn time_case(case: Case, repetitions: usize) -> Timing {
+    let input = match fs::read_to_string(&case.input) {
+        Ok(contents) => contents,
+        Err(error) => {
+            let message = format!("couldn't read {}: {}", case.input.display(), error);
+            return Timing {case: case, answer: String::new(), stats: Err(message)};
+        },
+    };
+
+    let before = get_alloc_count();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        return crate::solve(case.day, case.part, &input, &case.params);
+    }));
+    let allocs = get_alloc_count() - before;
+    let answer = match result {
+        Ok(Ok(answer)) => answer.to_string(),
+        Ok(Err(error)) => return Timing {case: case, answer: String::new(), stats: Err(error.to_string())},
+        Err(_) => return Timing {case: case, answer: String::new(), stats: Err("panicked".to_string())},
+    };
+
+    let mut times: Vec<Duration> = Vec::with_capacity(repetitions);
+    for _ in 0..repetitions.max(1) {
+        let start = Instant::now();
+        let _ = crate::solve(case.day, case.part, &input, &case.params);
+        times.push(start.elapsed());
+    }
+    let stats = get_stats(&mut times, allocs);
+    return Timing {case: case, answer: answer, stats: Ok(stats)};
+}
+
+fn get_stats(times: &mut [Duration], allocs: AllocCount) -> Stats {
+    times.sort();
+    let middle = times.len() / 2;
+    let median = if times.len().is_multiple_of(2) {(times[middle - 1] + times[middle]) / 2} else {times[middle]};
+    return Stats {
+        repetitions: times.len(),
+        min: times[0],
+        median: median,
+        max: times[times.len() - 1],
+        allocs: allocs,
+    };
+}
+
+fn print_table(timings: &[Timing], baseline: Option<&HashMap<String, u128>>) {
+    let mut headers = vec!["Day", "Part", "Input", "Params", "Answer", "Reps", "Min", "Median", "Max", "Allocs", "Bytes"];
+    if baseline.is_some() {
+        headers.push("vs baseline");
+    }
+    let rows: Vec<Vec<String>> = timings.iter().map(|timing| {
+        let case = &timing.case;
+        let file_name = case.input.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let mut row = vec![format!("{:02}", case.day), case.part.to_string(), file_name, case.param_pairs.join(" ")];
+        match &timing.stats {
+            Ok(stats) => {
+                row.push(summarise(&timing.answer));
+                row.push(stats.repetitions.to_string());
+                row.push(format_duration(stats.min));
+                row.push(format_duration(stats.median));
+                row.push(format_duration(stats.max));
+                row.push(stats.allocs.allocations.to_string());
+                row.push(format_bytes(stats.allocs.bytes));
+                if let Some(baseline) = baseline {
+                    row.push(get_change(stats.median.as_nanos(), baseline.get(&timing.get_key()).copied()));
+                }
+            },
+            Err(message) => {
+                row.push(format!("ERROR: {}", message));
+                row.resize(headers.len(), "-".to_string());
+            },
+        }
+        return row;
+    }).collect();
+    table::print_table(&headers, &rows);
+}
+
+fn get_change(median_ns: u128, baseline_ns: Option<u128>) -> String {
+    return match baseline_ns {
+        Some(baseline_ns) if baseline_ns > 0 => {
+            let change = (median_ns as f64 - baseline_ns as f64) / baseline_ns as f64 * 100.0;
+            format!("{:+.1}%", change)
+        },
+        _ => "new".to_string(),
+    };
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    else if bytes < 1024 * 1024 {
+        return format!("{:.1} KiB", bytes as f64 / 1024.0);
+    }
+    return format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0));
+}
+
+const REPORT_HEADER: &str = "day\tpart\tinput\tparams\treps\tmin_ns\tmedian_ns\tmax_ns\tallocs\talloc_bytes";
+
+// Tab-separated, one benchmark per line and in manifest order, so two reports can be compared with diff
+// or loaded back with read_report. Failed cases are left out.
+pub fn format_report(timings: &[Timing]) -> String {
+    let mut report = format!("{}\n", REPORT_HEADER);
+    for timing in timings {
+        if let Ok(stats) = &timing.stats {
+            let case = &timing.case;
+            report += &format!("{:02}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                case.day, case.part, case.input.display(), timing.get_params(), stats.repetitions,
+                stats.min.as_nanos(), stats.median.as_nanos(), stats.max.as_nanos(),
+                stats.allocs.allocations, stats.allocs.bytes);
+        }
+    }
+    return report;
+}
+
+// Maps each benchmark's key to its median in nanoseconds; lines that don't fit the format are skipped
+pub fn read_report(text: &str) -> HashMap<String, u128> {
+    let mut medians: HashMap<String, u128> = HashMap::new();
+    for line in text.lines().skip(1) {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 10 {
+            continue;
+        }
+        if let (Ok(day), Ok(median_ns)) = (fields[0].parse::<usize>(), fields[6].parse::<u128>()) {
+            medians.insert(get_key(day, fields[1], fields[2], fields[3]), median_ns);
+        }
+    }
+    return medians;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::common::{Params, Part};
+
+    use super::*;
+
+    fn get_case(day: usize, part: Part, param_pairs: &[&str]) -> Case {
+        let mut params = Params::new();
+        for pair in param_pairs {
+            params.insert_pair(pair).unwrap();
+        }
+        return Case {
+            line: 1,
+            day: day,
+            part: part,
+            input: PathBuf::from(format!("examples/{:02}.txt", day)),
+            expected: String::new(),
+            params: params,
+            param_pairs: param_pairs.iter().map(|pair| pair.to_string()).collect(),
+        };
+    }
+
+    #[test]
+    fn stats_of_odd_and_even_counts() {
+        let mut times: Vec<Duration> = [5, 1, 3].iter().map(|&ms| Duration::from_millis(ms)).collect();
+        let stats = get_stats(&mut times, AllocCount::default());
+        assert_eq!((stats.min, stats.median, stats.max), (Duration::from_millis(1), Duration::from_millis(3), Duration::from_millis(5)));
+
+        let mut times: Vec<Duration> = [4, 1, 2, 8].iter().map(|&ms| Duration::from_millis(ms)).collect();
+        let stats = get_stats(&mut times, AllocCount::default());
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.repetitions, 4);
+    }
+
+    #[test]
+    fn report_round_trip() {
+        let stats = Stats {
+            repetitions: 3,
+            min: Duration::from_nanos(100),
+            median: Duration::from_nanos(150),
+            max: Duration::from_nanos(400),
+            allocs: AllocCount {allocations: 7, bytes: 512},
+        };
+        let timings = vec![
+            Timing {case: get_case(1, Part::One, &[]), answer: "24000".to_string(), stats: Ok(stats.clone())},
+            Timing {case: get_case(15, Part::One, &["row=10"]), answer: "26".to_string(), stats: Ok(stats)},
+            Timing {case: get_case(2, Part::Two, &[]), answer: String::new(), stats: Err("panicked".to_string())},
+        ];
+        let report = format_report(&timings);
+        assert_eq!(report.lines().count(), 3);
+        assert!(report.contains("15\t1\texamples/15.txt\trow=10\t3\t100\t150\t400\t7\t512"));
+
+        let medians = read_report(&report);
+        assert_eq!(medians.len(), 2);
+        assert_eq!(medians.get(&timings[0].get_key()), Some(&150));
+        assert_eq!(medians.get(&timings[1].get_key()), Some(&150));
+        assert_eq!(medians.get(&timings[2].get_key()), None);
+    }
+
+    #[test]
+    fn change_against_baseline() {
+        assert_eq!(get_change(150, Some(100)), "+50.0%");
+        assert_eq!(get_change(75, Some(100)), "-25.0%");
+        assert_eq!(get_change(75, None), "new");
+    }
+}
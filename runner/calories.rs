@@ -0,0 +1,113 @@
+use crate::common::json::Json;
+use crate::day01::inventory::{get_top, read_inventory, Elf};
+use crate::day01::stats::{get_stats, Stats};
+
+use super::cli::Format;
+use super::input::{read_input, InputSource};
+use super::table;
+
+pub const DEFAULT_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+pub struct CaloriesOptions {
+    pub input: InputSource,
+    // Every elf is ranked unless this says otherwise
+    pub top: Option<usize>,
+    pub buckets: usize,
+    pub format: Format,
+}
+
+// The day 01 inventory: how the calories are spread between the elves, and who's carrying the most
+pub fn main(options: &CaloriesOptions) -> i32 {
+    let input = match read_input(&options.input, 1) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let elves = match read_inventory(&input.text) {
+        Ok(elves) => elves,
+        Err(error) => {
+            eprintln!("Day 01: couldn't parse '{}'", input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+    };
+    let totals: Vec<i64> = elves.iter().map(|elf| elf.total).collect();
+    let stats = get_stats(&totals, options.buckets);
+    let ranking = get_top(&elves, options.top.unwrap_or(elves.len()));
+
+    match options.format {
+        Format::Json => println!("{}", get_json(stats.as_ref(), &ranking)),
+        Format::Text => match stats {
+            Some(stats) => print_report(&stats, &ranking),
+            None => println!("There are no elves in '{}'", input.name),
+        },
+    }
+    return 0;
+}
+
+fn print_report(stats: &Stats, ranking: &[&Elf]) {
+    println!("{} elves carrying {} calories: {:.1} on average, {} at the median", stats.count, stats.total, stats.mean, stats.median);
+    let percentiles: Vec<String> = stats.percentiles.iter().map(|(p, value)| format!("p{} {}", p, value)).collect();
+    println!("Least {}, {}, most {}\n", stats.min, percentiles.join(", "), stats.max);
+
+    let most = stats.histogram.iter().map(|bucket| bucket.count).max().unwrap_or(0).max(1);
+    let rows: Vec<Vec<String>> = stats.histogram.iter().map(|bucket| {
+        let bar = "#".repeat((bucket.count * HISTOGRAM_WIDTH).div_ceil(most));
+        return vec![format!("{}-{}", bucket.low, bucket.high), bucket.count.to_string(), bar];
+    }).collect();
+    table::print_table(&["Calories", "Elves", ""], &rows);
+    println!();
+
+    let rows: Vec<Vec<String>> = ranking.iter().enumerate().map(|(rank, elf)| {
+        return vec![(rank + 1).to_string(), (elf.index + 1).to_string(), elf.line.to_string(), elf.snacks.len().to_string(), elf.total.to_string()];
+    }).collect();
+    table::print_table(&["Rank", "Elf", "Line", "Snacks", "Calories"], &rows);
+}
+
+fn get_json(stats: Option<&Stats>, ranking: &[&Elf]) -> Json {
+    let stats = stats.map(|stats| {
+        let percentiles = stats.percentiles.iter().fold(Json::object(), |json, (p, value)| json.with(&format!("p{}", p), *value));
+        let histogram: Vec<Json> = stats.histogram.iter()
+            .map(|bucket| Json::object().with("low", bucket.low).with("high", bucket.high).with("count", bucket.count))
+            .collect();
+        return Json::object()
+            .with("count", stats.count)
+            .with("total", stats.total)
+            .with("min", stats.min)
+            .with("max", stats.max)
+            .with("mean", stats.mean)
+            .with("median", stats.median)
+            .with("percentiles", percentiles)
+            .with("histogram", histogram);
+    });
+    let ranking: Vec<Json> = ranking.iter().enumerate()
+        .map(|(rank, elf)| Json::object()
+            .with("rank", rank + 1)
+            .with("elf", elf.index + 1)
+            .with("line", elf.line)
+            .with("snacks", elf.snacks.clone())
+            .with("calories", elf.total))
+        .collect();
+    return Json::object().with("stats", stats).with("ranking", ranking);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_report() {
+        let elves = read_inventory("100\n200\n\n50\n").unwrap();
+        let stats = get_stats(&[300, 50], 2);
+        let json = get_json(stats.as_ref(), &get_top(&elves, 1));
+        assert_eq!(json.to_string(), concat!(
+            r#"{"stats":{"count":2,"total":350,"min":50,"max":300,"mean":175,"median":175,"#,
+            r#""percentiles":{"p10":50,"p25":50,"p50":50,"p75":300,"p90":300},"#,
+            r#""histogram":[{"low":50,"high":175,"count":1},{"low":176,"high":301,"count":1}]},"#,
+            r#""ranking":[{"rank":1,"elf":1,"line":1,"snacks":[100,200],"calories":300}]}"#));
+        assert_eq!(get_json(None, &[]).to_string(), r#"{"stats":null,"ranking":[]}"#);
+    }
+}
@@ -0,0 +1,799 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::common::{Params, Part};
+use crate::common::log::Level;
+use crate::common::raster::Palette;
+
+use super::all::{self, AllOptions};
+use super::bench::{self, BenchOptions};
+use super::calories::{self, CaloriesOptions};
+use super::decode::DecodeOptions;
+use super::export::{self, PngOptions};
+use super::fuzz::{self, FuzzOptions, GenerateOptions};
+use super::input::InputSource;
+use super::record::{self, RecordOptions};
+use super::stepper::StepOptions;
+use super::tally::{self, TallyOptions};
+use super::tournament::{self, TournamentOptions};
+use super::verify;
+
+pub const USAGE: &str = "Usage:
+    aoc run <day> <part> [input] [--param key=value]... [--format text|json]
+    aoc all [--day N]... [--jobs N] [--timeout secs] [--example] [--param key=value]...
+                                solve both parts of every day (or the given days) on N threads (default
+                                one per core), giving up on any that take longer than the timeout
+                                (default 60) and printing a table of the answers at the end
+    aoc verify [manifest]       check every answer in the manifest (default examples/answers.txt)
+    aoc bench [manifest] [--reps N] [--day N]... [--report file] [--baseline file]
+                                time each case in the manifest and optionally write a report
+                                (tab-separated) or compare the medians against an earlier one
+    aoc png <day> <part> [input] <output> [--param key=value]... [--cell-size N] [--palette spec]
+                                draw the final state of day 10, 14, 17, 22, 23 or 24 as a PNG.
+                                The palette spec overrides colours, like '#=ffffff,o=e8c050,
+                                background=000000,red=ff8080' (a glyph, a colour name or background)
+    aoc record <day> <part> [input] <output> [--param key=value]... [--every N] [--max-frames N]
+               [--delay ms] [--cell-size N] [--palette spec]
+                                record day 9, 14, 17, 23 or 24 step by step, keeping every Nth step
+                                (default 1) up to a limit (default 500). An output ending in .gif
+                                is an animation; anything else is a directory of numbered PNGs
+    aoc step <day> <part> [input] [--param key=value]...
+                                step through day 9, 14, 17, 22, 23 or 24 interactively: forwards,
+                                backwards, to a step, and inspecting cells (h for the commands)
+    aoc calories [input] [--top N] [--buckets N] [--format text|json]
+                                summarise the day 01 inventory: the spread of calories between the
+                                elves (with N histogram buckets, default 10) and the elves ranked by
+                                what they're carrying (all of them, or the top N)
+    aoc tally <input>... [--top N] [--format text|json]
+                                total up day 01 inventories a line at a time, for ones too big to
+                                read in whole (like a decompressed stream on stdin). Each input is
+                                read on its own thread, bad lines are reported and skipped, and the
+                                top N elves (default 3) are listed
+    aoc decode [input] [--param key=value]... [--format text|json]
+                                score the day 02 strategy guide under every way of reading its
+                                second column, as moves and as outcomes, and print the usual,
+                                best, worst and expected scores
+    aoc tournament [input] [--player spec]... [--rounds N] [--seed N] [--param key=value]...
+                   [--format text|json]
+                                play day 02 strategies against each other, each pair for N rounds
+                                (default 1000), and print the leaderboard. A spec is a strategy,
+                                optionally named like 'careful=biased:3,1,1': guide and elf follow
+                                the guide's columns, random, biased:<weight for each move>,
+                                counter beats the last move, and markov learns the opponent's
+                                habits. The default is one of each (but biased)
+    aoc params [--day N]...     list the parameters of every day (or the given days), with their
+                                defaults from the puzzle text
+    aoc fuzz [--day N]... [--cases N] [--seed N] [--save dir]
+                                check the solvers for days 9, 11, 15, 16 and 22 against a brute force
+                                on random inputs (default 100 cases a day, seeded from the clock).
+                                Inputs that fail are written to the --save directory
+    aoc generate <day> [--seed N]
+                                print a random input for day 9, 11, 15, 16 or 22, and the
+                                parameters to run it with
+    aoc help
+
+The input can be a file, or - to read stdin (except for step, which reads its commands there).
+Without one, the input is looked up by day in the inputs directory (or $AOC_INPUTS), as 07.txt or
+7.txt. With --example, it's 07_example.txt there, or else the example in examples/.
+
+Long solvers (days 16, 19 and 20) show a progress bar while they run. Ctrl-C during run or all asks
+them to stop and print the best answer found so far; a second Ctrl-C quits straight away.
+
+Options for every command:
+    -v, -vv, -vvv               log more of what the solvers are doing to stderr
+    -q                          only log errors
+    --config file               read parameters from the file (default $AOC_CONFIG, if it's set)
+
+Parameters default to the values in the puzzle text (aoc params lists them). A config file sets them
+with key = value lines: at the top for every day, or under [day15] or [day15.part2] for one day or
+part. --param beats the config file, and the more specific sections beat the rest.
+
+Days 07, 11, 21 and 25 stop with an error if a number overflows 64 bits. With --param bigint=true
+they work it out exactly instead, a little more slowly.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        return match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("'{}' is not a valid format (expected text or json)", format)),
+        };
+    }
+}
+
+pub struct Args {
+    pub command: Command,
+    pub verbosity: Level,
+    pub config: Option<String>,
+}
+
+pub enum Command {
+    Help,
+    Run {day: usize, part: Part, input: InputSource, params: Params, format: Format},
+    All(AllOptions),
+    Verify {manifest: String},
+    Bench(BenchOptions),
+    Png(PngOptions),
+    Record(RecordOptions),
+    Step(StepOptions),
+    Calories(CaloriesOptions),
+    Tally(TallyOptions),
+    Decode(DecodeOptions),
+    Tournament(TournamentOptions),
+    Params {days: Vec<usize>},
+    Fuzz(FuzzOptions),
+    Generate(GenerateOptions),
+}
+
+pub fn parse_args(env_args: &[String]) -> Result<Args, String> {
+    let mut args: Vec<&str> = env_args.iter().skip(1).map(|x| x.as_str()).collect();
+    let verbosity = take_verbosity(&mut args);
+    let config = take_config(&mut args)?;
+    let command = match args.first() {
+        None | Some(&"help") | Some(&"--help") | Some(&"-h") => Ok(Command::Help),
+        Some(&"run") => parse_run(&args[1..]),
+        Some(&"all") => parse_all(&args[1..]),
+        Some(&"verify") => parse_verify(&args[1..]),
+        Some(&"bench") => parse_bench(&args[1..]),
+        Some(&"png") => parse_png(&args[1..]),
+        Some(&"record") => parse_record(&args[1..]),
+        Some(&"step") => parse_step(&args[1..]),
+        Some(&"calories") => parse_calories(&args[1..]),
+        Some(&"tally") => parse_tally(&args[1..]),
+        Some(&"decode") => parse_decode(&args[1..]),
+        Some(&"tournament") => parse_tournament(&args[1..]),
+        Some(&"params") => parse_params(&args[1..]),
+        Some(&"fuzz") => parse_fuzz(&args[1..]),
+        Some(&"generate") => parse_generate(&args[1..]),
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+    }?;
+    return Ok(Args {command: command, verbosity: verbosity, config: config});
+}
+
+// The verbosity flags can go anywhere, so they're pulled out before the command is parsed
+fn take_verbosity(args: &mut Vec<&str>) -> Level {
+    let mut num_v = 0;
+    let mut quiet = false;
+    args.retain(|arg| {
+        match *arg {
+            "-q" | "--quiet" => quiet = true,
+            "--verbose" => num_v += 1,
+            _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => num_v += arg.len() - 1,
+            _ => return true,
+        }
+        return false;
+    });
+    return if quiet {Level::Error} else {Level::from_verbosity(num_v)};
+}
+
+// Like the verbosity, the config file applies to whichever command is run
+fn take_config(args: &mut Vec<&str>) -> Result<Option<String>, String> {
+    let mut config: Option<String> = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--config" {
+            let path = args.get(i + 1).ok_or("--config needs a file".to_string())?;
+            config = Some(path.to_string());
+            args.drain(i..(i + 2));
+        }
+        else if let Some(path) = args[i].strip_prefix("--config=") {
+            config = Some(path.to_string());
+            args.remove(i);
+        }
+        else {
+            i += 1;
+        }
+    }
+    return Ok(config);
+}
+
+fn parse_run(args: &[&str]) -> Result<Command, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut params = Params::new();
+    let mut format = Format::Text;
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--param" || arg == "-p" {
+            let pair = args.get(i + 1).ok_or(format!("{} needs a key=value argument", arg))?;
+            params.insert_pair(pair)?;
+            i += 1;
+        }
+        else if let Some(pair) = arg.strip_prefix("--param=") {
+            params.insert_pair(pair)?;
+        }
+        else if arg == "--format" {
+            format = args.get(i + 1).ok_or("--format needs text or json".to_string())?.parse()?;
+            i += 1;
+        }
+        else if let Some(value) = arg.strip_prefix("--format=") {
+            format = value.parse()?;
+        }
+        else if arg == "--example" {
+            example = true;
+        }
+        else {
+            positional.push(arg);
+        }
+        i += 1;
+    }
+
+    if !(2..=3).contains(&positional.len()) {
+        return Err("run expects <day> <part> [input]".to_string());
+    }
+    let day: usize = parse_day(positional[0])?;
+    let part: Part = positional[1].parse()?;
+    let input = InputSource::from_arg(positional.get(2).copied(), example)?;
+    return Ok(Command::Run {day: day, part: part, input: input, params: params, format: format});
+}
+
+fn parse_all(args: &[&str]) -> Result<Command, String> {
+    let mut options = AllOptions {days: Vec::new(), jobs: None, timeout: all::DEFAULT_TIMEOUT, example: false, params: Params::new()};
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            options.example = true;
+            i += 1;
+            continue;
+        }
+        let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+        match arg {
+            "--day" => options.days.push(parse_day(value)?),
+            "--jobs" | "-j" => options.jobs = Some(parse_positive(value, "number of jobs")?),
+            "--timeout" => options.timeout = parse_timeout(value)?,
+            "--param" | "-p" => options.params.insert_pair(value)?,
+            _ => return Err(format!("Unknown option '{}'", arg)),
+        }
+        i += 2;
+    }
+    return Ok(Command::All(options));
+}
+
+fn parse_verify(args: &[&str]) -> Result<Command, String> {
+    return match args {
+        [] => Ok(Command::Verify {manifest: verify::DEFAULT_MANIFEST.to_string()}),
+        [manifest] => Ok(Command::Verify {manifest: manifest.to_string()}),
+        _ => Err("verify expects at most one manifest file".to_string()),
+    };
+}
+
+fn parse_bench(args: &[&str]) -> Result<Command, String> {
+    let mut options = BenchOptions {
+        manifest: verify::DEFAULT_MANIFEST.to_string(),
+        repetitions: bench::DEFAULT_REPETITIONS,
+        days: Vec::new(),
+        report: None,
+        baseline: None,
+    };
+    let mut manifest: Option<&str> = None;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg.starts_with("--") {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--reps" => {
+                    options.repetitions = match value.parse::<usize>() {
+                        Ok(repetitions) if repetitions > 0 => repetitions,
+                        _ => return Err(format!("'{}' is not a valid number of repetitions", value)),
+                    };
+                },
+                "--day" => options.days.push(parse_day(value)?),
+                "--report" => options.report = Some(value.to_string()),
+                "--baseline" => options.baseline = Some(value.to_string()),
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else if manifest.is_none() {
+            manifest = Some(arg);
+        }
+        else {
+            return Err("bench expects at most one manifest file".to_string());
+        }
+        i += 1;
+    }
+    if let Some(manifest) = manifest {
+        options.manifest = manifest.to_string();
+    }
+    return Ok(Command::Bench(options));
+}
+
+fn parse_png(args: &[&str]) -> Result<Command, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut params = Params::new();
+    let mut cell_size = export::DEFAULT_CELL_SIZE;
+    let mut palette = Palette::default();
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            example = true;
+        }
+        else if arg.starts_with("--") || arg == "-p" {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--param" | "-p" => params.insert_pair(value)?,
+                "--cell-size" => cell_size = parse_cell_size(value)?,
+                "--palette" => palette.apply_overrides(value)?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else {
+            positional.push(arg);
+        }
+        i += 1;
+    }
+
+    let (input, output) = match positional[..] {
+        [_, _, output] => (None, output),
+        [_, _, input, output] => (Some(input), output),
+        _ => return Err("png expects <day> <part> [input] <output>".to_string()),
+    };
+    return Ok(Command::Png(PngOptions {
+        day: parse_day(positional[0])?,
+        part: positional[1].parse()?,
+        input: InputSource::from_arg(input, example)?,
+        output: output.to_string(),
+        params: params,
+        cell_size: cell_size,
+        palette: palette,
+    }));
+}
+
+fn parse_record(args: &[&str]) -> Result<Command, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut params = Params::new();
+    let mut every: usize = 1;
+    let mut max_frames = record::DEFAULT_MAX_FRAMES;
+    let mut delay_ms = record::DEFAULT_DELAY_MS;
+    let mut cell_size = export::DEFAULT_CELL_SIZE;
+    let mut palette = Palette::default();
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            example = true;
+        }
+        else if arg.starts_with("--") || arg == "-p" {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--param" | "-p" => params.insert_pair(value)?,
+                "--every" => every = parse_positive(value, "step count")?,
+                "--max-frames" => max_frames = parse_positive(value, "number of frames")?,
+                "--delay" => delay_ms = parse_positive(value, "delay")? as u32,
+                "--cell-size" => cell_size = parse_cell_size(value)?,
+                "--palette" => palette.apply_overrides(value)?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else {
+            positional.push(arg);
+        }
+        i += 1;
+    }
+
+    let (input, output) = match positional[..] {
+        [_, _, output] => (None, output),
+        [_, _, input, output] => (Some(input), output),
+        _ => return Err("record expects <day> <part> [input] <output>".to_string()),
+    };
+    return Ok(Command::Record(RecordOptions {
+        day: parse_day(positional[0])?,
+        part: positional[1].parse()?,
+        input: InputSource::from_arg(input, example)?,
+        output: output.to_string(),
+        params: params,
+        every: every,
+        max_frames: max_frames,
+        delay_ms: delay_ms,
+        cell_size: cell_size,
+        palette: palette,
+    }));
+}
+
+fn parse_step(args: &[&str]) -> Result<Command, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut params = Params::new();
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--param" || arg == "-p" {
+            let pair = args.get(i + 1).ok_or(format!("{} needs a key=value argument", arg))?;
+            params.insert_pair(pair)?;
+            i += 1;
+        }
+        else if arg == "--example" {
+            example = true;
+        }
+        else {
+            positional.push(arg);
+        }
+        i += 1;
+    }
+
+    if !(2..=3).contains(&positional.len()) {
+        return Err("step expects <day> <part> [input]".to_string());
+    }
+    let input = InputSource::from_arg(positional.get(2).copied(), example)?;
+    if input == InputSource::Stdin {
+        return Err("step reads its commands from stdin, so its input has to come from a file".to_string());
+    }
+    return Ok(Command::Step(StepOptions {
+        day: parse_day(positional[0])?,
+        part: positional[1].parse()?,
+        input: input,
+        params: params,
+    }));
+}
+
+fn parse_calories(args: &[&str]) -> Result<Command, String> {
+    let mut options = CaloriesOptions {input: InputSource::Cached {example: false}, top: None, buckets: calories::DEFAULT_BUCKETS, format: Format::Text};
+    let mut input: Option<&str> = None;
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            example = true;
+        }
+        else if arg.starts_with("--") {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--top" => options.top = Some(parse_positive(value, "number of elves")?),
+                "--buckets" => options.buckets = parse_positive(value, "number of buckets")?,
+                "--format" => options.format = value.parse()?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else if input.is_none() {
+            input = Some(arg);
+        }
+        else {
+            return Err("calories expects at most one input".to_string());
+        }
+        i += 1;
+    }
+    options.input = InputSource::from_arg(input, example)?;
+    return Ok(Command::Calories(options));
+}
+
+fn parse_tally(args: &[&str]) -> Result<Command, String> {
+    let mut options = TallyOptions {inputs: Vec::new(), top: tally::DEFAULT_TOP, format: Format::Text};
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg.starts_with("--") {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--top" => options.top = parse_positive(value, "number of elves")?,
+                "--format" => options.format = value.parse()?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else {
+            options.inputs.push(arg.to_string());
+        }
+        i += 1;
+    }
+    if options.inputs.is_empty() {
+        return Err("tally expects at least one input (a file, or - for stdin)".to_string());
+    }
+    if options.inputs.iter().filter(|input| *input == "-").count() > 1 {
+        return Err("stdin can only be read once".to_string());
+    }
+    return Ok(Command::Tally(options));
+}
+
+fn parse_decode(args: &[&str]) -> Result<Command, String> {
+    let mut params = Params::new();
+    let mut format = Format::Text;
+    let mut input: Option<&str> = None;
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            example = true;
+        }
+        else if arg.starts_with("--") || arg == "-p" {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--param" | "-p" => params.insert_pair(value)?,
+                "--format" => format = value.parse()?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else if input.is_none() {
+            input = Some(arg);
+        }
+        else {
+            return Err("decode expects at most one input".to_string());
+        }
+        i += 1;
+    }
+    return Ok(Command::Decode(DecodeOptions {input: InputSource::from_arg(input, example)?, params: params, format: format}));
+}
+
+fn parse_tournament(args: &[&str]) -> Result<Command, String> {
+    let mut options = TournamentOptions {
+        input: InputSource::Cached {example: false},
+        players: Vec::new(),
+        rounds: tournament::DEFAULT_ROUNDS,
+        seed: None,
+        params: Params::new(),
+        format: Format::Text,
+    };
+    let mut input: Option<&str> = None;
+    let mut example = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        if arg == "--example" {
+            example = true;
+        }
+        else if arg.starts_with("--") || arg == "-p" {
+            let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+            match arg {
+                "--player" => options.players.push(value.parse()?),
+                "--rounds" => options.rounds = parse_positive(value, "number of rounds")?,
+                "--seed" => options.seed = Some(parse_seed(value)?),
+                "--param" | "-p" => options.params.insert_pair(value)?,
+                "--format" => options.format = value.parse()?,
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+            i += 1;
+        }
+        else if input.is_none() {
+            input = Some(arg);
+        }
+        else {
+            return Err("tournament expects at most one input".to_string());
+        }
+        i += 1;
+    }
+    if options.players.len() == 1 {
+        return Err("A tournament needs at least two players".to_string());
+    }
+    options.input = InputSource::from_arg(input, example)?;
+    return Ok(Command::Tournament(options));
+}
+
+fn parse_params(args: &[&str]) -> Result<Command, String> {
+    let mut days: Vec<usize> = Vec::new();
+    for pair in args.chunks(2) {
+        match pair {
+            ["--day", day] => days.push(parse_day(day)?),
+            _ => return Err("params expects [--day N]...".to_string()),
+        }
+    }
+    return Ok(Command::Params {days: days});
+}
+
+fn parse_fuzz(args: &[&str]) -> Result<Command, String> {
+    let mut options = FuzzOptions {days: Vec::new(), cases: fuzz::DEFAULT_CASES, seed: None, save: None};
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i];
+        let value = *args.get(i + 1).ok_or(format!("{} needs a value", arg))?;
+        match arg {
+            "--day" => options.days.push(parse_fuzz_day(value)?),
+            "--cases" => options.cases = parse_positive(value, "number of cases")?,
+            "--seed" => options.seed = Some(parse_seed(value)?),
+            "--save" => options.save = Some(value.to_string()),
+            _ => return Err(format!("Unknown option '{}'", arg)),
+        }
+        i += 2;
+    }
+    return Ok(Command::Fuzz(options));
+}
+
+fn parse_generate(args: &[&str]) -> Result<Command, String> {
+    return match args {
+        [day] => Ok(Command::Generate(GenerateOptions {day: parse_fuzz_day(day)?, seed: None})),
+        [day, "--seed", seed] | ["--seed", seed, day] => Ok(Command::Generate(GenerateOptions {day: parse_fuzz_day(day)?, seed: Some(parse_seed(seed)?)})),
+        _ => Err("generate expects <day> [--seed N]".to_string()),
+    };
+}
+
+fn parse_fuzz_day(value: &str) -> Result<usize, String> {
+    let day = parse_day(value)?;
+    if !fuzz::FUZZ_DAYS.contains(&day) {
+        return Err(format!("Day {} has no input generator (days 9, 11, 15, 16 and 22 do)", day));
+    }
+    return Ok(day);
+}
+
+fn parse_seed(value: &str) -> Result<u64, String> {
+    return value.parse::<u64>().map_err(|_| format!("'{}' is not a valid seed (expected a whole number)", value));
+}
+
+// In seconds, and fractions of one are fine
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    return match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("'{}' is not a valid timeout (expected a number of seconds)", value)),
+    };
+}
+
+fn parse_cell_size(value: &str) -> Result<u32, String> {
+    return match value.parse::<u32>() {
+        Ok(size) if (1..=64).contains(&size) => Ok(size),
+        _ => Err(format!("'{}' is not a valid cell size (expected 1 to 64)", value)),
+    };
+}
+
+fn parse_positive(value: &str, name: &str) -> Result<usize, String> {
+    return match value.parse::<usize>() {
+        Ok(number) if (number > 0) && (number <= u32::MAX as usize) => Ok(number),
+        _ => Err(format!("'{}' is not a valid {} (expected a positive whole number)", value, name)),
+    };
+}
+
+pub fn parse_day(day_str: &str) -> Result<usize, String> {
+    return match day_str.trim().parse::<usize>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("'{}' is not a valid day (expected 1 to 25)", day_str)),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_args(args: &str) -> Result<Args, String> {
+        let env_args: Vec<String> = format!("aoc {}", args).split_whitespace().map(|arg| arg.to_string()).collect();
+        return parse_args(&env_args);
+    }
+
+    #[test]
+    fn verbosity_flags_anywhere() {
+        assert_eq!(get_args("run 1 1 input.txt").unwrap().verbosity, Level::Warn);
+        assert_eq!(get_args("-v run 1 1 input.txt").unwrap().verbosity, Level::Info);
+        assert_eq!(get_args("run 1 -vv 1 input.txt").unwrap().verbosity, Level::Debug);
+        assert_eq!(get_args("run 1 1 input.txt -v -vvv").unwrap().verbosity, Level::Trace);
+        assert_eq!(get_args("verify -q -v").unwrap().verbosity, Level::Error);
+    }
+
+    #[test]
+    fn run_format() {
+        let formats: Vec<Format> = ["run 13 2 input.txt", "run 13 2 input.txt --format json", "run 13 2 --format=text input.txt"].iter()
+            .map(|args| match get_args(args).unwrap().command {
+                Command::Run {format, ..} => format,
+                _ => panic!("Expected a run command for '{}'", args),
+            })
+            .collect();
+        assert_eq!(formats, vec![Format::Text, Format::Json, Format::Text]);
+        assert!(get_args("run 13 2 input.txt --format xml").is_err());
+    }
+
+    #[test]
+    fn record_options() {
+        let options = match get_args("record 17 1 input.txt out.gif --every 5 --delay 40").unwrap().command {
+            Command::Record(options) => options,
+            _ => panic!("Expected a record command"),
+        };
+        assert_eq!((options.day, options.every, options.delay_ms), (17, 5, 40));
+        assert_eq!(options.max_frames, record::DEFAULT_MAX_FRAMES);
+        assert!(get_args("record 17 1 input.txt out.gif --every 0").is_err());
+        assert!(get_args("record 17 1").is_err());
+    }
+
+    #[test]
+    fn input_sources() {
+        let inputs: Vec<InputSource> = ["run 7 1 input.txt", "run 7 1 -", "run 7 1", "run 7 --example 1"].iter()
+            .map(|args| match get_args(args).unwrap().command {
+                Command::Run {input, ..} => input,
+                _ => panic!("Expected a run command for '{}'", args),
+            })
+            .collect();
+        assert_eq!(inputs, vec![
+            InputSource::File("input.txt".to_string()),
+            InputSource::Stdin,
+            InputSource::Cached {example: false},
+            InputSource::Cached {example: true},
+        ]);
+        match get_args("png 14 2 out.png").unwrap().command {
+            Command::Png(options) => assert_eq!((options.input, options.output.as_str()), (InputSource::Cached {example: false}, "out.png")),
+            _ => panic!("Expected a png command"),
+        }
+        assert!(get_args("run 7 1 input.txt --example").is_err());
+        assert!(get_args("step 23 1 -").is_err());
+    }
+
+    #[test]
+    fn fuzz_options() {
+        let options = match get_args("fuzz --day 22 --day 9 --seed 7").unwrap().command {
+            Command::Fuzz(options) => options,
+            _ => panic!("Expected a fuzz command"),
+        };
+        assert_eq!((options.days, options.cases, options.seed), (vec![22, 9], fuzz::DEFAULT_CASES, Some(7)));
+        assert!(get_args("fuzz --day 1").is_err());
+        assert!(get_args("fuzz --cases").is_err());
+        assert!(matches!(get_args("generate 15 --seed 3").unwrap().command, Command::Generate(GenerateOptions {day: 15, seed: Some(3)})));
+        assert!(get_args("generate 15 --seed x").is_err());
+    }
+
+    #[test]
+    fn all_options() {
+        let options = match get_args("all --day 16 --day 19 -j 3 --timeout 2.5 --example").unwrap().command {
+            Command::All(options) => options,
+            _ => panic!("Expected an all command"),
+        };
+        assert_eq!((options.days, options.jobs, options.timeout, options.example), (vec![16, 19], Some(3), Duration::from_millis(2500), true));
+        assert!(get_args("all --timeout 0").is_err());
+        assert!(get_args("all --jobs").is_err());
+    }
+
+    #[test]
+    fn config_anywhere() {
+        assert_eq!(get_args("run 15 1").unwrap().config, None);
+        assert_eq!(get_args("--config aoc.toml run 15 1").unwrap().config, Some("aoc.toml".to_string()));
+        assert_eq!(get_args("all --config=aoc.toml --day 15").unwrap().config, Some("aoc.toml".to_string()));
+        assert!(get_args("run 15 1 --config").is_err());
+        assert!(matches!(get_args("params --day 15 --day 17").unwrap().command, Command::Params {days} if days == vec![15, 17]));
+        assert!(get_args("params 15").is_err());
+    }
+
+    #[test]
+    fn calories_options() {
+        let options = match get_args("calories --top 3 elves.txt --format json").unwrap().command {
+            Command::Calories(options) => options,
+            _ => panic!("Expected a calories command"),
+        };
+        assert_eq!((options.input, options.top, options.buckets, options.format), (InputSource::File("elves.txt".to_string()), Some(3), calories::DEFAULT_BUCKETS, Format::Json));
+        assert!(get_args("calories --buckets 0").is_err());
+        assert!(get_args("calories a.txt b.txt").is_err());
+    }
+
+    #[test]
+    fn tally_options() {
+        let options = match get_args("tally a.txt - b.txt --top 10").unwrap().command {
+            Command::Tally(options) => options,
+            _ => panic!("Expected a tally command"),
+        };
+        assert_eq!((options.inputs, options.top), (vec!["a.txt".to_string(), "-".to_string(), "b.txt".to_string()], 10));
+        assert!(get_args("tally").is_err());
+        assert!(get_args("tally - -").is_err());
+    }
+
+    #[test]
+    fn tournament_options() {
+        let options = match get_args("tournament --example --player markov --player rocky=biased:1,0,0 --seed 5 --rounds 10").unwrap().command {
+            Command::Tournament(options) => options,
+            _ => panic!("Expected a tournament command"),
+        };
+        let names: Vec<&str> = options.players.iter().map(|player| player.name.as_str()).collect();
+        assert_eq!((names, options.seed, options.rounds), (vec!["markov", "rocky"], Some(5), 10));
+        assert!(get_args("tournament --player markov").is_err());
+        assert!(get_args("tournament --player clever --player markov").is_err());
+    }
+
+    #[test]
+    fn decode_options() {
+        let options = match get_args("decode --example -p outcome_scores=0,1,2 --format json").unwrap().command {
+            Command::Decode(options) => options,
+            _ => panic!("Expected a decode command"),
+        };
+        assert_eq!((options.input, options.params.pairs(), options.format), (InputSource::Cached {example: true}, vec!["outcome_scores=0,1,2".to_string()], Format::Json));
+        assert!(get_args("decode a.txt b.txt").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+use crate::common::{Params, Part};
+use crate::common::params::ParamInfo;
+use crate::log_warn;
+
+use super::table;
+
+// Used when there's no --config
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+// Parameters from a file, in a small subset of TOML: `key = value` lines (the value optionally
+// quoted), `#` comments, and sections. Values before any section apply to every day, and ones under
+// [day15] or [day15.part2] to just that day or part. The command line beats the most specific
+// section, which beats the day, which beats the top of the file.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    sections: HashMap<(Option<usize>, Option<Part>), Params>,
+}
+
+impl Config {
+    pub fn params_for(&self, day: usize, part: Part, cli: &Params) -> Params {
+        let mut params = Params::new();
+        for section in [(None, None), (Some(day), None), (Some(day), Some(part))] {
+            if let Some(values) = self.sections.get(&section) {
+                params.merge(values);
+            }
+        }
+        params.merge(cli);
+        return params;
+    }
+}
+
+// The file given with --config, or else the one in $AOC_CONFIG. Without either there's nothing to apply.
+pub fn load_config(path: Option<&str>) -> Result<Config, String> {
+    let path = match path.map(|path| path.to_string()).or_else(|| env::var(CONFIG_VAR).ok()) {
+        Some(path) => path,
+        None => return Ok(Config::default()),
+    };
+    let text = fs::read_to_string(&path).map_err(|error| format!("Couldn't read the config file '{}': {}", path, error))?;
+    return parse_config(&text).map_err(|message| format!("{} {}", path, message));
+}
+
+pub fn parse_config(text: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+    let mut section: (Option<usize>, Option<Part>) = (None, None);
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| format!("line {}: {}", i + 1, message);
+        if let Some(header) = line.strip_prefix('[') {
+            let header = strip_comment(header).strip_suffix(']').ok_or(error(format!("'{}' should look like [day15] or [day15.part2]", line)))?;
+            section = read_section(header.trim()).map_err(error)?;
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or(error(format!("'{}' should look like key = value", line)))?;
+        let key = key.trim();
+        let value = read_value(value.trim()).map_err(error)?;
+        check_value(section, key, &value).map_err(error)?;
+        config.sections.entry(section).or_default().insert(key, &value);
+    }
+    return Ok(config);
+}
+
+fn read_section(header: &str) -> Result<(Option<usize>, Option<Part>), String> {
+    let bad_section = || format!("'[{}]' isn't a section (expected something like [day15] or [day15.part2])", header);
+    let (day, part) = match header.split_once('.') {
+        Some((day, part)) => (day, Some(part)),
+        None => (header, None),
+    };
+    let day = match day.strip_prefix("day").and_then(|day| day.parse::<usize>().ok()) {
+        Some(day) if (1..=crate::DAYS.len()).contains(&day) => day,
+        _ => return Err(bad_section()),
+    };
+    let part = match part.map(|part| part.strip_prefix("part").and_then(|part| part.parse::<Part>().ok())) {
+        Some(Some(part)) => Some(part),
+        Some(None) => return Err(bad_section()),
+        None => None,
+    };
+    return Ok((Some(day), part));
+}
+
+// Quoted values can hold anything but the quote, and bare ones run up to a comment
+fn read_value(value: &str) -> Result<String, String> {
+    for quote in ['"', '\''] {
+        if let Some(quoted) = value.strip_prefix(quote) {
+            let (inside, rest) = quoted.split_once(quote).ok_or(format!("{} is missing its closing quote", value))?;
+            if !strip_comment(rest).is_empty() {
+                return Err(format!("'{}' after a quoted value", rest.trim()));
+            }
+            return Ok(inside.to_string());
+        }
+    }
+    return Ok(strip_comment(value).to_string());
+}
+
+fn strip_comment(text: &str) -> &str {
+    return text.split('#').next().unwrap_or("").trim();
+}
+
+fn get_infos(day: Option<usize>, part: Option<Part>) -> Vec<&'static ParamInfo> {
+    let days: Vec<usize> = match day {
+        Some(day) => vec![day],
+        None => (1..=crate::DAYS.len()).collect(),
+    };
+    let parts: Vec<Part> = match part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    return days.iter()
+        .flat_map(|&day| parts.iter().flat_map(move |&part| crate::params(day, part).iter().copied()))
+        .collect();
+}
+
+// A name nothing in the section reads is most likely a typo, so it's an error rather than ignored
+fn check_value(section: (Option<usize>, Option<Part>), key: &str, value: &str) -> Result<(), String> {
+    let infos: Vec<&ParamInfo> = get_infos(section.0, section.1).into_iter().filter(|info| info.name == key).collect();
+    let place = match section {
+        (Some(day), Some(part)) => format!("day {:02} part {}", day, part),
+        (Some(day), None) => format!("day {:02}", day),
+        _ => "any day".to_string(),
+    };
+    if infos.is_empty() {
+        return Err(format!("'{}' isn't a parameter of {}", key, place));
+    }
+    if !infos.iter().all(|info| info.accepts(value)) {
+        return Err(format!("'{}' isn't a valid value for parameter '{}'", value, key));
+    }
+    return Ok(());
+}
+
+// Unlike the config file, the command line only gets a warning, since a parameter for one day can
+// be handy to keep around when running another
+pub fn warn_unused(params: &Params, days: &[usize]) {
+    for name in params.names() {
+        if days.iter().any(|&day| get_infos(Some(day), None).iter().any(|info| info.name == name)) {
+            continue;
+        }
+        match days {
+            [day] => log_warn!("Day {:02} doesn't read a parameter called '{}'", day, name),
+            _ => log_warn!("None of the days read a parameter called '{}'", name),
+        }
+    }
+}
+
+// Every parameter of the given days (or all of them), with its default
+pub fn list_params(days: &[usize]) -> i32 {
+    let days: Vec<usize> = if days.is_empty() {(1..=crate::DAYS.len()).collect()} else {days.to_vec()};
+    let headers = ["Day", "Part", "Name", "Default", "About"];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for &day in days.iter() {
+        for part in [Part::One, Part::Two] {
+            for info in crate::params(day, part).iter() {
+                rows.push(vec![format!("{:02}", day), part.to_string(), info.name.to_string(), info.default.to_string(), info.about.to_string()]);
+            }
+        }
+    }
+    if rows.is_empty() {
+        println!("None of those days have parameters");
+        return 0;
+    }
+    table::print_table(&headers, &rows);
+    return 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+# Everything overflows on my input
+bigint = true
+
+[day15]
+row = 10   # the example's
+max_coord = 20
+
+[day15.part1]
+row = '11'
+
+[day13.part2]
+dividers = \"[[1]] [[3]]\"
+";
+
+    #[test]
+    fn sections_and_precedence() {
+        let config = parse_config(CONFIG).unwrap();
+        let mut cli = Params::new();
+        assert_eq!(config.params_for(15, Part::Two, &cli).pairs(), vec!["bigint=true", "max_coord=20", "row=10"]);
+        assert_eq!(config.params_for(15, Part::One, &cli).pairs(), vec!["bigint=true", "max_coord=20", "row=11"]);
+        assert_eq!(config.params_for(13, Part::Two, &cli).pairs(), vec!["bigint=true", "dividers=[[1]] [[3]]"]);
+        cli.insert("row", "12");
+        assert_eq!(config.params_for(15, Part::One, &cli).pairs(), vec!["bigint=true", "max_coord=20", "row=12"]);
+        assert_eq!(config.params_for(1, Part::One, &Params::new()).pairs(), vec!["bigint=true"]);
+    }
+
+    #[test]
+    fn bad_configs() {
+        assert_eq!(parse_config("[day15]\nrows = 10").unwrap_err(), "line 2: 'rows' isn't a parameter of day 15");
+        assert_eq!(parse_config("[day15.part2]\nrow = 10").unwrap_err(), "line 2: 'row' isn't a parameter of day 15 part 2");
+        assert_eq!(parse_config("\n\nrow = ten").unwrap_err(), "line 3: 'ten' isn't a valid value for parameter 'row'");
+        assert_eq!(parse_config("[day26]").unwrap_err(), "line 1: '[day26]' isn't a section (expected something like [day15] or [day15.part2])");
+        assert!(parse_config("[day09.part3]").is_err());
+        assert!(parse_config("[day09").is_err());
+        assert!(parse_config("knots 3").is_err());
+        assert!(parse_config("knots = \"3").is_err());
+        assert!(parse_config("[day09.part2]\nknots = \"3\" 4").is_err());
+    }
+}
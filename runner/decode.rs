@@ -0,0 +1,68 @@
+use crate::common::{Params, SolveError};
+use crate::common::json::Json;
+use crate::day02::decoder::{analyse, describe, Analysis, Decoding};
+
+use super::cli::Format;
+use super::input::{read_input, InputSource};
+use super::table;
+
+pub struct DecodeOptions {
+    pub input: InputSource,
+    pub params: Params,
+    pub format: Format,
+}
+
+// Scores the day 02 strategy guide under every way of reading its second column, for when it isn't
+// clear what the letters mean
+pub fn main(options: &DecodeOptions) -> i32 {
+    let input = match read_input(&options.input, 2) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let (guide, analyses) = match analyse(&options.params, &input.text) {
+        Ok(result) => result,
+        Err(SolveError::Parse(error)) => {
+            eprintln!("Day 02: couldn't parse '{}'", input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+        Err(error) => {
+            eprintln!("Day 02: {}", error);
+            return 1;
+        },
+    };
+
+    match options.format {
+        Format::Text => {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            for analysis in analyses.iter() {
+                for (which, decoding) in [("puzzle's", &analysis.usual), ("best", &analysis.best), ("worst", &analysis.worst)] {
+                    rows.push(vec![analysis.reading.name().to_string(), which.to_string(), decoding.score.to_string(), describe(&guide.letters, decoding)]);
+                }
+                let average = format!("average of {} decodings", analysis.num_decodings);
+                rows.push(vec![analysis.reading.name().to_string(), "expected".to_string(), format!("{:.1}", analysis.expected), average]);
+            }
+            table::print_table(&["Reading", "Decoding", "Score", "Letters"], &rows);
+        },
+        Format::Json => {
+            let letters: Vec<Json> = guide.letters.iter().map(|letter| Json::from(letter.to_string())).collect();
+            let analyses: Vec<Json> = analyses.iter().map(get_json).collect();
+            println!("{}", Json::object().with("letters", letters).with("readings", analyses));
+        },
+    }
+    return 0;
+}
+
+fn get_json(analysis: &Analysis) -> Json {
+    let decoding = |decoding: &Decoding| Json::object().with("score", decoding.score).with("meanings", decoding.meanings.clone());
+    return Json::object()
+        .with("reading", analysis.reading.name())
+        .with("decodings", analysis.num_decodings)
+        .with("puzzle", decoding(&analysis.usual))
+        .with("best", decoding(&analysis.best))
+        .with("worst", decoding(&analysis.worst))
+        .with("expected", analysis.expected);
+}
@@ -0,0 +1,54 @@
+use std::fs;
+
+use crate::common::{Params, Part, SolveError};
+use crate::common::raster::{rasterise, Palette};
+use crate::common::render::{Render, RenderOptions};
+
+use super::input::{read_input, InputSource};
+
+pub const DEFAULT_CELL_SIZE: u32 = 4;
+
+pub struct PngOptions {
+    pub day: usize,
+    pub part: Part,
+    pub input: InputSource,
+    pub output: String,
+    pub params: Params,
+    pub cell_size: u32,
+    pub palette: Palette,
+}
+
+// Draws the final state of a day's puzzle into a PNG
+pub fn main(options: &PngOptions) -> i32 {
+    let input = match read_input(&options.input, options.day) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let picture = match crate::picture(options.day, options.part, &input.text, &options.params) {
+        Ok(picture) => picture,
+        Err(SolveError::Parse(error)) => {
+            eprintln!("Day {:02} part {}: couldn't parse '{}'", options.day, options.part, input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+        Err(error) => {
+            eprintln!("Day {:02} part {}: {}", options.day, options.part, error);
+            return 1;
+        },
+    };
+    if picture.render_bounds().is_none() {
+        eprintln!("Day {:02} part {}: the picture is empty", options.day, options.part);
+        return 1;
+    }
+
+    let image = rasterise(&picture, &RenderOptions::default(), &options.palette, options.cell_size);
+    if let Err(error) = fs::write(&options.output, image.to_png()) {
+        eprintln!("Couldn't write '{}': {}", options.output, error);
+        return 1;
+    }
+    println!("Wrote {}x{} image to {}", image.width, image.height, options.output);
+    return 0;
+}
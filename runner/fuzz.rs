@@ -0,0 +1,169 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::random::Rng;
+
+use super::table;
+
+pub const DEFAULT_CASES: usize = 100;
+pub const FUZZ_DAYS: [usize; 5] = [9, 11, 15, 16, 22];
+
+pub struct FuzzOptions {
+    pub days: Vec<usize>,
+    pub cases: usize,
+    pub seed: Option<u64>,
+    pub save: Option<String>,
+}
+
+pub struct GenerateOptions {
+    pub day: usize,
+    pub seed: Option<u64>,
+}
+
+enum Verdict {
+    Pass,
+    // The brute force couldn't answer it either, so there's nothing to check against
+    Skip,
+    Fail(String),
+}
+
+struct Tally {
+    day: usize,
+    part: Part,
+    passed: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+// Checks each day's solver against its brute force on generated inputs. Case i uses the seed
+// plus i, so any failure can be generated again on its own with `aoc generate`.
+pub fn main(options: &FuzzOptions) -> i32 {
+    let seed = options.seed.unwrap_or_else(get_time_seed);
+    let days: Vec<usize> = if options.days.is_empty() {FUZZ_DAYS.to_vec()} else {options.days.clone()};
+    println!("Fuzzing with seed {} (pass --seed {} to run the same cases again)\n", seed, seed);
+
+    let mut tallies: Vec<Tally> = Vec::new();
+    for day in days {
+        let mut day_tallies: Vec<Tally> = [Part::One, Part::Two].iter().map(|&part| Tally {day: day, part: part, passed: 0, skipped: 0, failed: 0}).collect();
+        for i in 0..options.cases {
+            let case_seed = seed.wrapping_add(i as u64);
+            let (input, params) = match crate::generate(day, &mut get_rng(day, case_seed)) {
+                Ok(generated) => generated,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return 1;
+                },
+            };
+            for tally in day_tallies.iter_mut() {
+                match check_case(day, tally.part, &input, &params) {
+                    Verdict::Pass => tally.passed += 1,
+                    Verdict::Skip => tally.skipped += 1,
+                    Verdict::Fail(message) => {
+                        tally.failed += 1;
+                        println!("Day {:02} part {}, case seed {}: {}", day, tally.part, case_seed, message);
+                        println!("    aoc generate {} --seed {} > case.txt && aoc run {} {} case.txt {}", day, case_seed, day, tally.part, params.pairs().iter().map(|pair| format!("-p {}", pair)).collect::<Vec<String>>().join(" "));
+                        if let Some(directory) = &options.save {
+                            if let Err(message) = save_case(Path::new(directory), day, case_seed, &input) {
+                                eprintln!("{}", message);
+                            }
+                        }
+                    },
+                }
+            }
+        }
+        tallies.extend(day_tallies);
+    }
+
+    let headers = ["Day", "Part", "Cases", "Passed", "Skipped", "Failed"];
+    let rows: Vec<Vec<String>> = tallies.iter().map(|tally| {
+        return vec![
+            format!("{:02}", tally.day),
+            tally.part.to_string(),
+            options.cases.to_string(),
+            tally.passed.to_string(),
+            tally.skipped.to_string(),
+            tally.failed.to_string(),
+        ];
+    }).collect();
+    println!();
+    table::print_table(&headers, &rows);
+    return if tallies.iter().all(|tally| tally.failed == 0) {0} else {1};
+}
+
+// Prints one generated input, and the parameters to run it with
+pub fn generate(options: &GenerateOptions) -> i32 {
+    let seed = options.seed.unwrap_or_else(get_time_seed);
+    return match crate::generate(options.day, &mut get_rng(options.day, seed)) {
+        Ok((input, params)) => {
+            print!("{}", input);
+            eprintln!("Generated day {} with seed {}; run it with: {}", options.day, seed, get_param_args(&params));
+            0
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        },
+    };
+}
+
+// Different days get different numbers from the same case seed
+fn get_rng(day: usize, case_seed: u64) -> Rng {
+    return Rng::new(case_seed ^ ((day as u64) << 56));
+}
+
+pub fn get_time_seed() -> u64 {
+    return SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos() as u64).unwrap_or(0);
+}
+
+fn get_param_args(params: &Params) -> String {
+    let args: Vec<String> = params.pairs().iter().map(|pair| format!("-p {}", pair)).collect();
+    return if args.is_empty() {"no parameters".to_string()} else {args.join(" ")};
+}
+
+fn check_case(day: usize, part: Part, input: &str, params: &Params) -> Verdict {
+    let expected: Answer = match crate::brute_force(day, part, input, params) {
+        Ok(answer) => answer,
+        Err(_) => return Verdict::Skip,
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        return crate::solve(day, part, input, params);
+    }));
+    return match result {
+        Ok(Ok(answer)) if answer == expected => Verdict::Pass,
+        Ok(Ok(answer)) => Verdict::Fail(format!("the solver got {} but the brute force got {}", answer, expected)),
+        Ok(Err(SolveError::Parse(error))) => Verdict::Fail(format!("the solver couldn't parse it: {}", error)),
+        Ok(Err(error)) => Verdict::Fail(format!("the solver failed ({}) but the brute force got {}", error, expected)),
+        Err(_) => Verdict::Fail(format!("the solver panicked but the brute force got {}", expected)),
+    };
+}
+
+fn save_case(directory: &Path, day: usize, case_seed: u64, input: &str) -> Result<(), String> {
+    let path = directory.join(format!("{:02}_{}.txt", day, case_seed));
+    fs::create_dir_all(directory).map_err(|error| format!("Couldn't create '{}': {}", directory.display(), error))?;
+    return fs::write(&path, input).map_err(|error| format!("Couldn't write '{}': {}", path.display(), error));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_cases_pass() {
+        for day in FUZZ_DAYS {
+            let (input, params) = crate::generate(day, &mut get_rng(day, 2022)).unwrap();
+            assert_eq!(crate::generate(day, &mut get_rng(day, 2022)).unwrap().0, input, "day {} isn't reproducible", day);
+            for part in [Part::One, Part::Two] {
+                assert!(!matches!(check_case(day, part, &input, &params), Verdict::Fail(_)), "day {} part {}", day, part);
+            }
+        }
+    }
+
+    #[test]
+    fn only_some_days_have_generators() {
+        assert_eq!(crate::generate(1, &mut Rng::new(0)).err(), Some(SolveError::NoGenerator(1)));
+        assert_eq!(crate::generate(26, &mut Rng::new(0)).err(), Some(SolveError::NoSuchDay(26)));
+    }
+}
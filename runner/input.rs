@@ -0,0 +1,125 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+// Points the runner at a different cache directory
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUTS";
+const EXAMPLES_DIR: &str = "examples";
+
+// Where a command's puzzle input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(String),
+    // Looked up by day in the input directory: `inputs/07.txt`, or `inputs/07_example.txt` for the example
+    Cached {example: bool},
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path),
+            InputSource::Cached {example: false} => write!(f, "the cached input"),
+            InputSource::Cached {example: true} => write!(f, "the cached example"),
+        };
+    }
+}
+
+impl InputSource {
+    // `-` is stdin, anything else is a path, and nothing at all means the cache (or its example)
+    pub fn from_arg(arg: Option<&str>, example: bool) -> Result<InputSource, String> {
+        return match (arg, example) {
+            (None, example) => Ok(InputSource::Cached {example: example}),
+            (Some(_), true) => Err("--example can't be used with an input file".to_string()),
+            (Some("-"), false) => Ok(InputSource::Stdin),
+            (Some(path), false) => Ok(InputSource::File(path.to_string())),
+        };
+    }
+}
+
+// The input's text and a name for it in messages; the error is ready to print
+#[derive(Debug)]
+pub struct Input {
+    pub text: String,
+    pub name: String,
+}
+
+pub fn read_input(source: &InputSource, day: usize) -> Result<Input, String> {
+    return match source {
+        InputSource::Stdin => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|error| format!("Couldn't read stdin: {}", error))?;
+            Ok(Input {text: text, name: "stdin".to_string()})
+        },
+        InputSource::File(path) => read_file(Path::new(path)),
+        InputSource::Cached {example} => {
+            let input_dir = env::var(INPUT_DIR_VARIABLE).unwrap_or(DEFAULT_INPUT_DIR.to_string());
+            read_cached(Path::new(&input_dir), day, *example)
+        },
+    };
+}
+
+fn read_file(path: &Path) -> Result<Input, String> {
+    return match fs::read_to_string(path) {
+        Ok(text) => Ok(Input {text: text, name: path.display().to_string()}),
+        Err(error) => Err(format!("Couldn't read '{}': {}", path.display(), error)),
+    };
+}
+
+fn read_cached(input_dir: &Path, day: usize, example: bool) -> Result<Input, String> {
+    let candidates = get_cache_paths(input_dir, day, example);
+    return match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => read_file(path),
+        None => {
+            let looked_in: Vec<String> = candidates.iter().map(|path| path.display().to_string()).collect();
+            Err(format!("No {} for day {} (looked for {}). Save it there, or pass a file or - to read stdin",
+                if example {"example"} else {"input"}, day, looked_in.join(", ")))
+        },
+    };
+}
+
+// Both `07.txt` and `7.txt` work. The examples that come with the repo are the fallback for an
+// example that isn't in the cache.
+pub fn get_cache_paths(input_dir: &Path, day: usize, example: bool) -> Vec<PathBuf> {
+    let suffix = if example {"_example"} else {""};
+    let mut paths = vec![
+        input_dir.join(format!("{:02}{}.txt", day, suffix)),
+        input_dir.join(format!("{}{}.txt", day, suffix)),
+    ];
+    if example {
+        paths.push(Path::new(EXAMPLES_DIR).join(format!("{:02}.txt", day)));
+    }
+    paths.dedup();
+    return paths;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_from_args() {
+        assert_eq!(InputSource::from_arg(Some("-"), false), Ok(InputSource::Stdin));
+        assert_eq!(InputSource::from_arg(Some("in.txt"), false), Ok(InputSource::File("in.txt".to_string())));
+        assert_eq!(InputSource::from_arg(None, true), Ok(InputSource::Cached {example: true}));
+        assert!(InputSource::from_arg(Some("in.txt"), true).is_err());
+    }
+
+    #[test]
+    fn cache_lookup() {
+        let input_dir = env::temp_dir().join(format!("aoc_inputs_{}", std::process::id()));
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("7.txt"), "$ cd /\n").unwrap();
+
+        assert_eq!(get_cache_paths(&input_dir, 7, false), vec![input_dir.join("07.txt"), input_dir.join("7.txt")]);
+        assert_eq!(read_cached(&input_dir, 7, false).unwrap().text, "$ cd /\n");
+        // No example in the cache, so it comes from the repo's examples
+        assert_eq!(read_cached(&input_dir, 7, true).unwrap().name, Path::new("examples").join("07.txt").display().to_string());
+        assert!(read_cached(&input_dir, 8, false).unwrap_err().contains("08.txt"));
+        fs::remove_dir_all(&input_dir).unwrap();
+    }
+}
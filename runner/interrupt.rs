@@ -0,0 +1,25 @@
+use crate::common::progress;
+
+// The exit code for a run stopped by Ctrl-C, as a shell would report it
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+// The first Ctrl-C asks the solvers to stop and hand back what they have; a second one quits straight away.
+// std has no signal handling, so this goes to libc directly, which std links against anyway.
+#[cfg(unix)]
+pub fn catch_ctrl_c() {
+    const SIGINT: i32 = 2;
+    extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+        fn _exit(status: i32) -> !;
+    }
+    extern "C" fn on_interrupt(_signum: i32) {
+        if progress::interrupt() {
+            unsafe { _exit(INTERRUPTED_EXIT_CODE) };
+        }
+    }
+    unsafe { signal(SIGINT, on_interrupt) };
+}
+
+// Elsewhere Ctrl-C just ends the process as usual
+#[cfg(not(unix))]
+pub fn catch_ctrl_c() {}
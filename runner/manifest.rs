@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::common::{Params, Part};
+use crate::common::parse::{lines, Line, ParseError};
+
+use super::cli::parse_day;
+
+// One line of the manifest: `<day> <part> <input> <expected> [key=value]...`
+// An expected answer of `@file` means the answer is the contents of that file (for the CRT picture)
+#[derive(Debug, Clone)]
+pub struct Case {
+    pub line: usize,
+    pub day: usize,
+    pub part: Part,
+    pub input: PathBuf,
+    pub expected: String,
+    pub params: Params,
+    pub param_pairs: Vec<String>,
+}
+
+// Reads and parses the manifest file; the error is ready to print
+pub fn load_manifest(manifest_path: &str) -> Result<Vec<Case>, String> {
+    let text = fs::read_to_string(manifest_path)
+        .map_err(|error| format!("Couldn't read '{}': {}", manifest_path, error))?;
+    let base_dir = Path::new(manifest_path).parent().unwrap_or(Path::new(""));
+    return read_manifest(&text, base_dir)
+        .map_err(|error| format!("Couldn't parse '{}'\n{}", manifest_path, error.diagnostic()));
+}
+
+// Paths in the manifest are relative to the manifest itself
+pub fn read_manifest(text: &str, base_dir: &Path) -> Result<Vec<Case>, ParseError> {
+    let mut cases: Vec<Case> = Vec::new();
+    for line in lines(text) {
+        // Anything after a '#' is a comment, even at the end of a case
+        let content = Line {number: line.number, text: line.text.split('#').next().unwrap_or("").trim_end()};
+        if content.text.trim().is_empty() {
+            continue;
+        }
+        cases.push(read_case(&content, base_dir)?);
+    }
+    return Ok(cases);
+}
+
+fn read_case(line: &Line, base_dir: &Path) -> Result<Case, ParseError> {
+    let day_str = line.word(0, "a day")?;
+    let day: usize = parse_day(day_str).map_err(|_| line.error(day_str, "a day from 1 to 25"))?;
+    let part: Part = line.parse_word(1, "a part (1 or 2)")?;
+    let input = base_dir.join(line.word(2, "an input file")?);
+    let expected = line.word(3, "an expected answer")?;
+    let expected = match expected.strip_prefix('@') {
+        Some(file_name) => format!("@{}", base_dir.join(file_name).display()),
+        None => expected.to_string(),
+    };
+
+    let mut params = Params::new();
+    let mut param_pairs: Vec<String> = Vec::new();
+    for pair in line.text.split_whitespace().skip(4) {
+        params.insert_pair(pair).map_err(|_| line.error(pair, "a parameter like key=value"))?;
+        param_pairs.push(pair.to_string());
+    }
+
+    return Ok(Case {
+        line: line.number,
+        day: day,
+        part: part,
+        input: input,
+        expected: expected,
+        params: params,
+        param_pairs: param_pairs,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_error(text: &str) -> (usize, usize, String) {
+        let error = read_manifest(text, Path::new("")).unwrap_err();
+        return (error.line, error.column, error.expected);
+    }
+
+    #[test]
+    fn cases() {
+        let text = "# A comment\n\n01 1 01.txt 24000\n   # Indented comment\n10 2 10.txt @crt.txt # The picture\n15 1 15.txt 26 row=10 max_coord=20\n";
+        let cases = read_manifest(text, Path::new("examples")).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!((cases[0].line, cases[0].day, cases[0].part), (3, 1, Part::One));
+        assert_eq!((cases[0].input.clone(), cases[0].expected.as_str()), (Path::new("examples").join("01.txt"), "24000"));
+        // The trailing comment isn't a parameter, and the answer file is relative to the manifest too
+        assert_eq!((cases[1].line, cases[1].part), (5, Part::Two));
+        assert_eq!(cases[1].expected, format!("@{}", Path::new("examples").join("crt.txt").display()));
+        assert!(cases[1].param_pairs.is_empty());
+        assert_eq!(cases[2].param_pairs, vec!["row=10".to_string(), "max_coord=20".to_string()]);
+        assert_eq!(cases[2].params.get::<i64>("row"), Ok(Some(10)));
+        assert_eq!(cases[2].params.get::<i64>("max_coord"), Ok(Some(20)));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(get_error("01 1 01.txt 1\n26 1 26.txt 1\n"), (2, 1, "a day from 1 to 25".to_string()));
+        assert_eq!(get_error("\n01 3 01.txt 1\n"), (2, 4, "a part (1 or 2)".to_string()));
+        assert_eq!(get_error("01 1 01.txt # No answer\n"), (1, 12, "an expected answer".to_string()));
+        assert_eq!(get_error("# Header\n15 1 15.txt 26 row\n"), (2, 16, "a parameter like key=value".to_string()));
+        assert!(read_manifest("# Only comments\n\n", Path::new("")).unwrap().is_empty());
+    }
+}
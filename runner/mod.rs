@@ -0,0 +1,173 @@
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+
+use crate::common::{Answer, Params, Part, SolveError};
+use crate::common::json::Json;
+use crate::common::log;
+use crate::common::progress;
+
+pub mod all;
+pub mod alloc;
+pub mod bench;
+pub mod calories;
+pub mod cli;
+pub mod config;
+pub mod decode;
+pub mod export;
+pub mod fuzz;
+pub mod input;
+pub mod interrupt;
+pub mod manifest;
+pub mod record;
+pub mod stepper;
+pub mod tally;
+pub mod tournament;
+pub mod table;
+pub mod verify;
+use self::cli::{Command, Format};
+use self::input::InputSource;
+
+pub fn main(env_args: &[String]) -> i32 {
+    let args = match cli::parse_args(env_args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n", message);
+            eprintln!("{}", cli::USAGE);
+            return 2;
+        },
+    };
+
+    log::set_verbosity(args.verbosity);
+    if matches!(args.command, Command::Help) {
+        println!("{}", cli::USAGE);
+        return 0;
+    }
+    let config = match config::load_config(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 2;
+        },
+    };
+
+    return match args.command {
+        Command::Help => 0,
+        Command::Run {day, part, input, params, format} => {
+            interrupt::catch_ctrl_c();
+            progress::show_bars(io::stderr().is_terminal() && log::is_enabled(log::Level::Warn));
+            config::warn_unused(&params, &[day]);
+            run_day(day, part, &input, &config.params_for(day, part, &params), format)
+        },
+        Command::All(options) => {
+            interrupt::catch_ctrl_c();
+            all::main(&options, &config)
+        },
+        Command::Verify {manifest} => verify::main(&manifest),
+        Command::Bench(options) => bench::main(&options),
+        Command::Png(mut options) => {
+            config::warn_unused(&options.params, &[options.day]);
+            options.params = config.params_for(options.day, options.part, &options.params);
+            export::main(&options)
+        },
+        Command::Record(mut options) => {
+            config::warn_unused(&options.params, &[options.day]);
+            options.params = config.params_for(options.day, options.part, &options.params);
+            record::main(&options)
+        },
+        Command::Step(mut options) => {
+            config::warn_unused(&options.params, &[options.day]);
+            options.params = config.params_for(options.day, options.part, &options.params);
+            stepper::main(&options)
+        },
+        Command::Calories(options) => calories::main(&options),
+        Command::Tally(options) => tally::main(&options),
+        Command::Decode(mut options) => {
+            config::warn_unused(&options.params, &[2]);
+            options.params = config.params_for(2, Part::One, &options.params);
+            decode::main(&options)
+        },
+        Command::Tournament(mut options) => {
+            config::warn_unused(&options.params, &[2]);
+            options.params = config.params_for(2, Part::One, &options.params);
+            tournament::main(&options)
+        },
+        Command::Params {days} => config::list_params(&days),
+        Command::Fuzz(options) => fuzz::main(&options),
+        Command::Generate(options) => fuzz::generate(&options),
+    };
+}
+
+fn run_day(day: usize, part: Part, source: &InputSource, params: &Params, format: Format) -> i32 {
+    let input = match input::read_input(source, day) {
+        Ok(input) => input,
+        Err(message) => {
+            match format {
+                Format::Text => eprintln!("{}", message),
+                Format::Json => println!("{}", get_json_header(day, part).with("error", message)),
+            }
+            return 1;
+        },
+    };
+
+    log::start_recording();
+    let start = Instant::now();
+    let result = crate::solve(day, part, &input.text, params);
+    let elapsed = start.elapsed();
+    let details = log::take_details();
+
+    let failure_code = if matches!(result, Err(SolveError::Interrupted {..})) {interrupt::INTERRUPTED_EXIT_CODE} else {1};
+    if format == Format::Json {
+        println!("{}", get_json_result(day, part, &result, details, elapsed));
+        return if result.is_ok() {0} else {failure_code};
+    }
+    return match result {
+        Ok(answer) => {
+            print_answer(day, part, &answer);
+            0
+        },
+        Err(SolveError::Parse(error)) => {
+            eprintln!("Day {:02} part {}: couldn't parse '{}'", day, part, input.name);
+            eprintln!("{}", error.diagnostic());
+            1
+        },
+        Err(SolveError::Interrupted {best: Some(best)}) => {
+            eprintln!("Day {:02} part {} was interrupted. The best answer found so far:", day, part);
+            print_answer(day, part, &best);
+            failure_code
+        },
+        Err(error) => {
+            eprintln!("Day {:02} part {} failed: {}", day, part, error);
+            failure_code
+        },
+    };
+}
+
+fn print_answer(day: usize, part: Part, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Day {:02} part {}:\n{}", day, part, answer);
+    }
+    else {
+        println!("Day {:02} part {}: {}", day, part, answer);
+    }
+}
+
+fn get_json_header(day: usize, part: Part) -> Json {
+    return Json::object().with("day", day).with("part", part.as_int());
+}
+
+// One object per run: the answer (or the error), whatever intermediate values the solver recorded, and the time taken
+pub fn get_json_result(day: usize, part: Part, result: &Result<Answer, SolveError>, details: Vec<(String, Json)>, elapsed: Duration) -> Json {
+    let json = get_json_header(day, part);
+    let json = match result {
+        Ok(answer) => json.with("answer", answer),
+        Err(SolveError::Parse(error)) => json.with("error", error.to_string()).with("parse_error", Json::object()
+            .with("line", error.line)
+            .with("column", error.column)
+            .with("found", error.found.as_str())
+            .with("expected", error.expected.as_str())),
+        Err(SolveError::Interrupted {best: Some(best)}) => json.with("error", "interrupted").with("best_so_far", best),
+        Err(error) => json.with("error", error.to_string()),
+    };
+    return json.with("details", Json::Object(details)).with("elapsed_ns", elapsed.as_nanos() as u64);
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::common::{Params, Part, SolveError};
+use crate::common::gif::encode_gif;
+use crate::common::grid::Bounds;
+use crate::common::raster::{rasterise, Image, Palette, Rgb};
+use crate::common::recorder;
+use crate::common::render::{Render, RenderOptions};
+
+use super::input::{read_input, InputSource};
+
+pub const DEFAULT_MAX_FRAMES: usize = 500;
+pub const DEFAULT_DELAY_MS: u32 = 100;
+
+pub struct RecordOptions {
+    pub day: usize,
+    pub part: Part,
+    pub input: InputSource,
+    pub output: String,
+    pub params: Params,
+    pub every: usize,
+    pub max_frames: usize,
+    pub delay_ms: u32,
+    pub cell_size: u32,
+    pub palette: Palette,
+}
+
+// Runs a day with the recorder on and writes the frames out as an animated GIF (if the output ends
+// in .gif) or as numbered PNGs in the output directory
+pub fn main(options: &RecordOptions) -> i32 {
+    let input = match read_input(&options.input, options.day) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    recorder::start_recording(options.every, options.max_frames);
+    let result = crate::solve(options.day, options.part, &input.text, &options.params);
+    let frames = recorder::finish_recording();
+    match result {
+        Ok(answer) => println!("Day {:02} part {}: {}", options.day, options.part, answer),
+        Err(SolveError::Parse(error)) => {
+            eprintln!("Day {:02} part {}: couldn't parse '{}'", options.day, options.part, input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+        Err(error) => {
+            eprintln!("Day {:02} part {}: {}", options.day, options.part, error);
+            return 1;
+        },
+    }
+
+    // Every frame is drawn over the same area, so the animation doesn't jump about as the picture grows
+    let viewport: Option<Bounds<i64>> = frames.iter()
+        .filter_map(|frame| frame.render_bounds())
+        .reduce(|union, bounds| union.union(&bounds));
+    let viewport = match viewport {
+        Some(viewport) => viewport,
+        None => {
+            eprintln!("Day {:02} part {}: nothing was recorded (days 9, 14, 17, 23 and 24 can be recorded)", options.day, options.part);
+            return 1;
+        },
+    };
+    let render_options = RenderOptions::default().with_viewport(viewport);
+    let images: Vec<Image> = frames.iter().map(|frame| rasterise(frame, &render_options, &options.palette, options.cell_size)).collect();
+
+    let written = if options.output.ends_with(".gif") {write_gif(&images, options)} else {write_pngs(&images, &options.output)};
+    if let Err(message) = written {
+        eprintln!("{}", message);
+        return 1;
+    }
+    println!("Wrote {} frames of {}x{} to {}", images.len(), images[0].width, images[0].height, options.output);
+    return 0;
+}
+
+fn write_gif(images: &[Image], options: &RecordOptions) -> Result<(), String> {
+    let (width, height) = (images[0].width, images[0].height);
+    if (width > u16::MAX as u32) || (height > u16::MAX as u32) {
+        return Err(format!("{}x{} is too big for a GIF; try a smaller --cell-size", width, height));
+    }
+    let (palette, frames) = get_indexed_frames(images)?;
+    let delay_centis = (options.delay_ms / 10).min(u16::MAX as u32) as u16;
+    let gif = encode_gif(width as u16, height as u16, &palette, &frames, delay_centis);
+    return fs::write(&options.output, gif).map_err(|error| format!("Couldn't write '{}': {}", options.output, error));
+}
+
+fn write_pngs(images: &[Image], directory: &str) -> Result<(), String> {
+    fs::create_dir_all(directory).map_err(|error| format!("Couldn't create '{}': {}", directory, error))?;
+    for (i, image) in images.iter().enumerate() {
+        let path = Path::new(directory).join(format!("frame_{:05}.png", i));
+        fs::write(&path, image.to_png()).map_err(|error| format!("Couldn't write '{}': {}", path.display(), error))?;
+    }
+    return Ok(());
+}
+
+// GIFs index into a table of at most 256 colours, which is plenty for the palettes the days use
+fn get_indexed_frames(images: &[Image]) -> Result<(Vec<Rgb>, Vec<Vec<u8>>), String> {
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut indices: HashMap<Rgb, u8> = HashMap::new();
+    let mut frames: Vec<Vec<u8>> = Vec::new();
+    for image in images {
+        let mut frame: Vec<u8> = Vec::with_capacity(image.rgb.len() / 3);
+        for pixel in image.rgb.chunks(3) {
+            let rgb = Rgb(pixel[0], pixel[1], pixel[2]);
+            let index = match indices.get(&rgb) {
+                Some(index) => *index,
+                None if palette.len() < 256 => {
+                    indices.insert(rgb, palette.len() as u8);
+                    palette.push(rgb);
+                    (palette.len() - 1) as u8
+                },
+                None => return Err("The frames use more than 256 colours, which won't fit in a GIF".to_string()),
+            };
+            frame.push(index);
+        }
+        frames.push(frame);
+    }
+    return Ok((palette, frames));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_indexed_in_order_of_appearance() {
+        let first = Image {width: 2, height: 1, rgb: vec![1, 2, 3, 4, 5, 6]};
+        let second = Image {width: 2, height: 1, rgb: vec![4, 5, 6, 4, 5, 6]};
+        let (palette, frames) = get_indexed_frames(&[first, second]).unwrap();
+        assert_eq!(palette, vec![Rgb(1, 2, 3), Rgb(4, 5, 6)]);
+        assert_eq!(frames, vec![vec![0, 1], vec![1, 1]]);
+    }
+}
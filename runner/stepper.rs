@@ -0,0 +1,261 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::common::{Params, Part, SolveError};
+use crate::common::geometry::Point2;
+use crate::common::grid::Bounds;
+use crate::common::render::{stdout_supports_colour, RenderOptions};
+use crate::common::simulation::Simulation;
+
+use super::input::{read_input, InputSource};
+
+const PLAY_DELAY: Duration = Duration::from_millis(100);
+
+pub const HELP: &str = "Commands (Enter on its own steps forward once):
+    n [count]           step forward (default 1)
+    b [count]           step back (default 1)
+    j <step>            jump to a step
+    e                   run to the end
+    p [count]           play forward, drawing every step, to the end or for count steps
+    i <x> <y>           inspect the cell at column x, row y of what's shown (counting from 0)
+    v <x> <y> <w> <h>   only show a w by h part of the picture; v on its own shows all of it
+    h                   show this again
+    q                   quit";
+
+pub struct StepOptions {
+    pub day: usize,
+    pub part: Part,
+    pub input: InputSource,
+    pub params: Params,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum StepCommand {
+    Forward(usize),
+    Back(usize),
+    Jump(usize),
+    End,
+    Play(Option<usize>),
+    Inspect(i64, i64),
+    View(Option<(i64, i64, i64, i64)>),
+    Help,
+    Quit,
+}
+
+pub fn main(options: &StepOptions) -> i32 {
+    let input = match read_input(&options.input, options.day) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let simulation = match crate::simulation(options.day, options.part, &input.text, &options.params) {
+        Ok(simulation) => simulation,
+        Err(SolveError::Parse(error)) => {
+            eprintln!("Day {:02} part {}: couldn't parse '{}'", options.day, options.part, input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+        Err(error) => {
+            eprintln!("Day {:02} part {}: {}", options.day, options.part, error);
+            return 1;
+        },
+    };
+
+    // Commands can be piped in too, in which case the screen isn't cleared and playing doesn't wait
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let mut stepper = Stepper::new(simulation, stdout_supports_colour(), interactive);
+    if let Err(error) = run_session(&mut stepper, &mut io::stdin().lock(), &mut io::stdout().lock()) {
+        eprintln!("{}", error);
+        return 1;
+    }
+    return 0;
+}
+
+struct Stepper {
+    simulation: Box<dyn Simulation>,
+    step_num: usize,
+    finished: bool,
+    // x, y, width and height, relative to the top left of the whole picture
+    view: Option<(i64, i64, i64, i64)>,
+    colour: bool,
+    interactive: bool,
+}
+
+impl Stepper {
+    fn new(simulation: Box<dyn Simulation>, colour: bool, interactive: bool) -> Stepper {
+        return Stepper {simulation: simulation, step_num: 0, finished: false, view: None, colour: colour, interactive: interactive};
+    }
+
+    fn forward(&mut self, count: usize) {
+        for _ in 0..count {
+            if !self.simulation.step() {
+                self.finished = true;
+                return;
+            }
+            self.step_num += 1;
+        }
+    }
+
+    // Simulations only go forwards, so going back means starting again
+    fn jump(&mut self, target: usize) {
+        if target < self.step_num {
+            self.simulation.reset();
+            self.step_num = 0;
+            self.finished = false;
+        }
+        self.forward(target - self.step_num);
+    }
+
+    fn get_area(&self) -> Option<Bounds<i64>> {
+        let bounds = self.simulation.render_bounds()?;
+        return match self.view {
+            Some((x, y, width, height)) => {
+                let min = bounds.min + Point2::new(x, y);
+                Some(Bounds::new(min, min + Point2::new(width - 1, height - 1)))
+            },
+            None => Some(bounds),
+        };
+    }
+
+    fn inspect(&self, x: i64, y: i64) -> String {
+        return match self.get_area() {
+            Some(area) => self.simulation.describe(&(area.min + Point2::new(x, y))),
+            None => "There's nothing to inspect".to_string(),
+        };
+    }
+
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+        if self.interactive {
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        if let Some(area) = self.get_area() {
+            let options = RenderOptions::default().with_viewport(area).with_colour(self.colour);
+            self.simulation.render(out, &options)?;
+        }
+        let finished = if self.finished {" (finished)"} else {""};
+        writeln!(out, "Step {}{}: {}", self.step_num, finished, self.simulation.status())?;
+        return out.flush();
+    }
+}
+
+fn run_session(stepper: &mut Stepper, commands: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+    stepper.draw(out)?;
+    writeln!(out, "Type h for help")?;
+    loop {
+        write!(out, "> ")?;
+        out.flush()?;
+        let mut line = String::new();
+        if commands.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let command = match parse_command(&line) {
+            Ok(command) => command,
+            Err(message) => {
+                writeln!(out, "{}", message)?;
+                continue;
+            },
+        };
+        match command {
+            StepCommand::Forward(count) => stepper.forward(count),
+            StepCommand::Back(count) => stepper.jump(stepper.step_num.saturating_sub(count)),
+            StepCommand::Jump(step) => stepper.jump(step),
+            StepCommand::End => stepper.forward(usize::MAX),
+            StepCommand::Play(count) => {
+                for _ in 0..count.unwrap_or(usize::MAX) {
+                    stepper.forward(1);
+                    if stepper.finished {
+                        break;
+                    }
+                    stepper.draw(out)?;
+                    if stepper.interactive {
+                        thread::sleep(PLAY_DELAY);
+                    }
+                }
+            },
+            StepCommand::Inspect(x, y) => {
+                writeln!(out, "{}", stepper.inspect(x, y))?;
+                continue;
+            },
+            StepCommand::View(view) => stepper.view = view,
+            StepCommand::Help => {
+                writeln!(out, "{}", HELP)?;
+                continue;
+            },
+            StepCommand::Quit => return Ok(()),
+        }
+        stepper.draw(out)?;
+    }
+}
+
+fn parse_command(line: &str) -> Result<StepCommand, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let numbers: Vec<i64> = match words.iter().skip(1).map(|word| word.parse::<i64>()).collect() {
+        Ok(numbers) => numbers,
+        Err(_) => return Err(format!("'{}' should be a command followed by numbers", line.trim())),
+    };
+    let count = |default: usize| -> Result<usize, String> {
+        return match numbers[..] {
+            [] => Ok(default),
+            [count] if count >= 0 => Ok(count as usize),
+            _ => Err(format!("'{}' takes one count that isn't negative", words[0])),
+        };
+    };
+    return match (words.first().copied(), &numbers[..]) {
+        (None, _) => Ok(StepCommand::Forward(1)),
+        (Some("n" | "next"), _) => Ok(StepCommand::Forward(count(1)?)),
+        (Some("b" | "back"), _) => Ok(StepCommand::Back(count(1)?)),
+        (Some("j" | "jump"), [_]) => Ok(StepCommand::Jump(count(0)?)),
+        (Some("e" | "end"), []) => Ok(StepCommand::End),
+        (Some("p" | "play"), []) => Ok(StepCommand::Play(None)),
+        (Some("p" | "play"), _) => Ok(StepCommand::Play(Some(count(0)?))),
+        (Some("i" | "inspect"), [x, y]) => Ok(StepCommand::Inspect(*x, *y)),
+        (Some("v" | "view"), []) => Ok(StepCommand::View(None)),
+        (Some("v" | "view"), [x, y, width, height]) if (*width > 0) && (*height > 0) => Ok(StepCommand::View(Some((*x, *y, *width, *height)))),
+        (Some("h" | "help"), []) => Ok(StepCommand::Help),
+        (Some("q" | "quit"), []) => Ok(StepCommand::Quit),
+        (Some(command), _) => Err(format!("'{}' isn't a command or has the wrong numbers after it (h for help)", command)),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse_command("\n"), Ok(StepCommand::Forward(1)));
+        assert_eq!(parse_command("n 5"), Ok(StepCommand::Forward(5)));
+        assert_eq!(parse_command("back"), Ok(StepCommand::Back(1)));
+        assert_eq!(parse_command("j 100"), Ok(StepCommand::Jump(100)));
+        assert_eq!(parse_command("p"), Ok(StepCommand::Play(None)));
+        assert_eq!(parse_command("i 3 -1"), Ok(StepCommand::Inspect(3, -1)));
+        assert_eq!(parse_command("v 0 0 20 10"), Ok(StepCommand::View(Some((0, 0, 20, 10)))));
+        assert!(parse_command("j").is_err());
+        assert!(parse_command("n -2").is_err());
+        assert!(parse_command("v 0 0 0 10").is_err());
+        assert!(parse_command("fly").is_err());
+    }
+
+    #[test]
+    fn session_steps_back_and_forth() {
+        let input = include_str!("../examples/23.txt");
+        let simulation = crate::simulation(23, Part::One, input, &Params::new()).unwrap();
+        let mut stepper = Stepper::new(simulation, false, false);
+        let mut commands: &[u8] = b"n 10\nb 3\ni 0 0\ne\nq\n";
+        let mut output: Vec<u8> = Vec::new();
+        run_session(&mut stepper, &mut commands, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let statuses: Vec<&str> = output.lines().filter(|line| line.contains("Step ")).map(|line| line.trim_start_matches("> ")).collect();
+        assert_eq!(statuses, vec![
+            "Step 0: Round 0: 27 empty tiles around the elves",
+            "Step 10: Round 10: 110 empty tiles around the elves",
+            "Step 7: Round 7: 99 empty tiles around the elves",
+            "Step 10 (finished): Round 10: 110 empty tiles around the elves",
+        ]);
+        assert!(output.contains("empty ground"));
+    }
+}
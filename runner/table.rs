@@ -0,0 +1,22 @@
+// Prints rows as left-aligned columns under a dashed header line
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    // The last column is left ragged so long error messages don't pad every row
+    let last = headers.len() - 1;
+    let format_row = |cells: Vec<&str>| {
+        return cells.iter().enumerate()
+            .map(|(i, cell)| if i == last {cell.to_string()} else {format!("{:<width$}", cell, width = widths[i])})
+            .collect::<Vec<String>>()
+            .join("  ");
+    };
+    println!("{}", format_row(headers.to_vec()));
+    println!("{}", widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<String>>().join("  "));
+    for row in rows {
+        println!("{}", format_row(row.iter().map(|cell| cell.as_str()).collect()));
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::thread;
+
+use crate::common::json::Json;
+use crate::day01::tally::Tally;
+use crate::log_warn;
+
+use super::cli::Format;
+use super::table;
+
+pub const DEFAULT_TOP: usize = 3;
+
+pub struct TallyOptions {
+    // Files, or - for stdin (which can only be read once)
+    pub inputs: Vec<String>,
+    pub top: usize,
+    pub format: Format,
+}
+
+// Totals up day 01 inventories too big to read into memory, like generated ones or a decompressed
+// stream on stdin. Each input is a shard read on its own thread, and the tallies are merged at the end.
+pub fn main(options: &TallyOptions) -> i32 {
+    let results: Vec<io::Result<Tally>> = thread::scope(|scope| {
+        let handles: Vec<_> = options.inputs.iter().enumerate()
+            .map(|(shard, input)| scope.spawn(move || read_shard(input, shard, options.top)))
+            .collect();
+        return handles.into_iter().map(|handle| handle.join().expect("Reading a shard shouldn't panic")).collect();
+    });
+
+    let mut tally = Tally::new(options.top, 0);
+    for (input, result) in options.inputs.iter().zip(results) {
+        match result {
+            Ok(shard) => tally.merge(shard),
+            Err(error) => {
+                eprintln!("Couldn't read '{}': {}", input, error);
+                return 1;
+            },
+        }
+    }
+
+    for bad_line in tally.bad_lines() {
+        log_warn!("{} line {}: '{}' isn't a number of calories", options.inputs[bad_line.shard], bad_line.line, bad_line.text);
+    }
+    if tally.num_bad_lines > tally.bad_lines().len() {
+        log_warn!("...and {} more bad lines", tally.num_bad_lines - tally.bad_lines().len());
+    }
+    match options.format {
+        Format::Text => print_summary(&tally, &options.inputs),
+        Format::Json => println!("{}", get_json(&tally, &options.inputs)),
+    }
+    return 0;
+}
+
+fn read_shard(input: &str, shard: usize, top: usize) -> io::Result<Tally> {
+    let mut tally = Tally::new(top, shard);
+    if input == "-" {
+        tally.read(io::stdin().lock())?;
+    }
+    else {
+        tally.read(BufReader::new(File::open(input)?))?;
+    }
+    return Ok(tally);
+}
+
+fn print_summary(tally: &Tally, inputs: &[String]) {
+    let bad = if tally.num_bad_lines == 1 {"bad line"} else {"bad lines"};
+    println!("{} lines, {} elves, {} {}", tally.num_lines, tally.num_elves, tally.num_bad_lines, bad);
+    if let (Some(mean), Some(min), Some(max)) = (tally.mean(), tally.min, tally.max) {
+        println!("{} calories: {} to {} an elf, {:.1} on average\n", tally.total, min, max, mean);
+    }
+    let rows: Vec<Vec<String>> = tally.get_top().iter().enumerate().map(|(rank, elf)| {
+        return vec![(rank + 1).to_string(), inputs[elf.shard].clone(), (elf.index + 1).to_string(), elf.line.to_string(), elf.total.to_string()];
+    }).collect();
+    if !rows.is_empty() {
+        table::print_table(&["Rank", "Input", "Elf", "Line", "Calories"], &rows);
+    }
+}
+
+fn get_json(tally: &Tally, inputs: &[String]) -> Json {
+    let top: Vec<Json> = tally.get_top().iter().enumerate()
+        .map(|(rank, elf)| Json::object()
+            .with("rank", rank + 1)
+            .with("input", inputs[elf.shard].as_str())
+            .with("elf", elf.index + 1)
+            .with("line", elf.line)
+            .with("calories", elf.total))
+        .collect();
+    let bad_lines: Vec<Json> = tally.bad_lines().iter()
+        .map(|bad_line| Json::object().with("input", inputs[bad_line.shard].as_str()).with("line", bad_line.line).with("text", bad_line.text.as_str()))
+        .collect();
+    return Json::object()
+        .with("lines", tally.num_lines)
+        .with("elves", tally.num_elves)
+        .with("total", tally.total)
+        .with("min", tally.min)
+        .with("max", tally.max)
+        .with("mean", tally.mean())
+        .with("top", top)
+        .with("num_bad_lines", tally.num_bad_lines)
+        .with("bad_lines", bad_lines);
+}
@@ -0,0 +1,94 @@
+use crate::common::Params;
+use crate::common::json::Json;
+use crate::common::random::Rng;
+use crate::day02::game::get_game;
+use crate::day02::tournament::{play_tournament, read_guide_moves, Player, Standing};
+
+use super::cli::Format;
+use super::fuzz::get_time_seed;
+use super::input::{read_input, InputSource};
+use super::table;
+
+pub const DEFAULT_ROUNDS: usize = 1000;
+pub const DEFAULT_PLAYERS: [&str; 5] = ["guide", "elf", "random", "counter", "markov"];
+
+pub struct TournamentOptions {
+    pub input: InputSource,
+    // The default line-up when none are given
+    pub players: Vec<Player>,
+    pub rounds: usize,
+    pub seed: Option<u64>,
+    pub params: Params,
+    pub format: Format,
+}
+
+// Plays day 02 strategies against each other, every one against every other, and prints the
+// leaderboard. The guide is what the guide and elf players follow.
+pub fn main(options: &TournamentOptions) -> i32 {
+    let input = match read_input(&options.input, 2) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+    let game = match get_game(&options.params) {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("Day 02: {}", error);
+            return 1;
+        },
+    };
+    let guide = match read_guide_moves(&game, &input.text) {
+        Ok(guide) => guide,
+        Err(error) => {
+            eprintln!("Day 02: couldn't parse '{}'", input.name);
+            eprintln!("{}", error.diagnostic());
+            return 1;
+        },
+    };
+    let players: Vec<Player> = if options.players.is_empty() {
+        DEFAULT_PLAYERS.iter().map(|spec| spec.parse().expect("The default players should parse")).collect()
+    } else {
+        options.players.clone()
+    };
+
+    let seed = options.seed.unwrap_or_else(get_time_seed);
+    let standings = play_tournament(&game, &guide, &players, options.rounds, &mut Rng::new(seed));
+    match options.format {
+        Format::Text => {
+            println!("{} players, {} rounds a match, seed {} (pass --seed {} to play it again)\n", players.len(), options.rounds, seed, seed);
+            print_leaderboard(&standings);
+        },
+        Format::Json => println!("{}", get_json(&standings, options.rounds, seed)),
+    }
+    return 0;
+}
+
+fn print_leaderboard(standings: &[Standing]) {
+    let rows: Vec<Vec<String>> = standings.iter().enumerate().map(|(rank, standing)| {
+        return vec![
+            (rank + 1).to_string(),
+            standing.name.clone(),
+            standing.wins.to_string(),
+            standing.draws.to_string(),
+            standing.losses.to_string(),
+            standing.rounds_won.to_string(),
+            standing.score.to_string(),
+        ];
+    }).collect();
+    table::print_table(&["Rank", "Player", "Won", "Drawn", "Lost", "Rounds won", "Score"], &rows);
+}
+
+fn get_json(standings: &[Standing], rounds: usize, seed: u64) -> Json {
+    let standings: Vec<Json> = standings.iter()
+        .map(|standing| Json::object()
+            .with("player", standing.name.as_str())
+            .with("won", standing.wins)
+            .with("drawn", standing.draws)
+            .with("lost", standing.losses)
+            .with("rounds_won", standing.rounds_won)
+            .with("score", standing.score))
+        .collect();
+    return Json::object().with("rounds", rounds).with("seed", seed).with("leaderboard", standings);
+}
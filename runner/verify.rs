@@ -0,0 +1,185 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::common::SolveError;
+
+use super::manifest::{load_manifest, Case};
+use super::table;
+
+pub const DEFAULT_MANIFEST: &str = "examples/answers.txt";
+
+pub enum Status {
+    Pass,
+    Fail(String),
+    Error(String),
+}
+
+pub struct Outcome {
+    pub case: Case,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        return matches!(self.status, Status::Pass);
+    }
+}
+
+// Runs every case in the manifest and prints a table; the exit code is 0 only if they all pass
+pub fn main(manifest_path: &str) -> i32 {
+    let cases = match load_manifest(manifest_path) {
+        Ok(cases) => cases,
+        Err(message) => {
+            eprintln!("{}", message);
+            return 1;
+        },
+    };
+
+    let outcomes: Vec<Outcome> = cases.into_iter().map(run_case).collect();
+    print_table(&outcomes);
+
+    let failures = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+    let total_time: Duration = outcomes.iter().map(|outcome| outcome.elapsed).sum();
+    println!("\n{} passed, {} failed in {}", outcomes.len() - failures, failures, format_duration(total_time));
+    return if failures == 0 {0} else {1};
+}
+
+pub fn run_case(case: Case) -> Outcome {
+    let input = match fs::read_to_string(&case.input) {
+        Ok(contents) => contents,
+        Err(error) => {
+            let status = Status::Error(format!("couldn't read {}: {}", case.input.display(), error));
+            return Outcome {case: case, status: status, elapsed: Duration::ZERO};
+        },
+    };
+    let expected = match get_expected(&case.expected) {
+        Ok(expected) => expected,
+        Err(message) => return Outcome {case: case, status: Status::Error(message), elapsed: Duration::ZERO},
+    };
+
+    // A panicking solver is reported as a failure rather than taking the whole run down with it
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        return crate::solve(case.day, case.part, &input, &case.params);
+    }));
+    let elapsed = start.elapsed();
+
+    let status = match result {
+        Ok(Ok(answer)) => {
+            let answer = answer.to_string();
+            if answer.trim_end() == expected.trim_end() {Status::Pass} else {Status::Fail(answer)}
+        },
+        Ok(Err(SolveError::Parse(error))) => Status::Error(format!("parse error at {}", error)),
+        Ok(Err(error)) => Status::Error(error.to_string()),
+        Err(_) => Status::Error("panicked".to_string()),
+    };
+    return Outcome {case: case, status: status, elapsed: elapsed};
+}
+
+fn get_expected(expected: &str) -> Result<String, String> {
+    return match expected.strip_prefix('@') {
+        Some(file_name) => fs::read_to_string(file_name).map_err(|error| format!("couldn't read {}: {}", file_name, error)),
+        None => Ok(expected.to_string()),
+    };
+}
+
+fn print_table(outcomes: &[Outcome]) {
+    let headers = ["Day", "Part", "Input", "Params", "Expected", "Got", "Time", "Result"];
+    let rows: Vec<Vec<String>> = outcomes.iter().map(|outcome| {
+        let case = &outcome.case;
+        let file_name = case.input.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let expected = get_expected(&case.expected).unwrap_or(case.expected.to_string());
+        let (got, result) = match &outcome.status {
+            Status::Pass => (summarise(&expected), "pass".to_string()),
+            Status::Fail(answer) => (summarise(answer), "FAIL".to_string()),
+            Status::Error(message) => ("-".to_string(), format!("ERROR: {}", message)),
+        };
+        return vec![
+            format!("{:02}", case.day),
+            case.part.to_string(),
+            file_name,
+            case.param_pairs.join(" "),
+            summarise(&expected),
+            got,
+            format_duration(outcome.elapsed),
+            result,
+        ];
+    }).collect();
+
+    table::print_table(&headers, &rows);
+}
+
+// Multi-line answers (the CRT) don't fit in a table cell
+pub fn summarise(answer: &str) -> String {
+    let num_lines = answer.trim_end().lines().count();
+    if num_lines > 1 {
+        return format!("[{} lines]", num_lines);
+    }
+    return answer.trim().to_string();
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1000 {
+        return format!("{} µs", micros);
+    }
+    else if micros < 1_000_000 {
+        return format!("{:.1} ms", micros as f64 / 1000.0);
+    }
+    return format!("{:.2} s", duration.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::path::Path;
+
+    use super::*;
+    use crate::runner::manifest::read_manifest;
+
+    fn get_statuses(text: &str, base_dir: &Path) -> Vec<Status> {
+        return read_manifest(text, base_dir).unwrap().into_iter().map(|case| run_case(case).status).collect();
+    }
+
+    #[test]
+    fn pass_and_fail() {
+        let text = "01 1 01.txt 24000\n01 2 01.txt 1\n10 2 10.txt @10_part2_crt.txt\n15 1 15.txt 26 row=10\n";
+        let statuses = get_statuses(text, Path::new("examples"));
+        assert!(matches!(statuses[0], Status::Pass));
+        // A wrong answer says what the solver got
+        assert!(matches!(&statuses[1], Status::Fail(answer) if answer == "45000"));
+        assert!(matches!(statuses[2], Status::Pass));
+        assert!(matches!(statuses[3], Status::Pass));
+    }
+
+    #[test]
+    fn errors() {
+        let dir = env::temp_dir().join(format!("aoc_verify_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bad.txt"), "1000\nabc\n").unwrap();
+        fs::write(dir.join("good.txt"), "1000\n").unwrap();
+        let text = "01 1 missing.txt 1\n01 1 bad.txt 1\n01 1 good.txt @missing.txt\n01 2 good.txt 1 top=x\n";
+        let statuses = get_statuses(text, &dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let messages: Vec<String> = statuses.into_iter().map(|status| match status {
+            Status::Error(message) => message,
+            _ => panic!("every case should be an error"),
+        }).collect();
+        assert!(messages[0].starts_with("couldn't read") && messages[0].contains("missing.txt"));
+        assert!(messages[1].starts_with("parse error at line 2, column 1"), "{}", messages[1]);
+        assert!(messages[2].starts_with("couldn't read") && messages[2].contains("missing.txt"));
+        assert!(messages[3].contains("top"), "{}", messages[3]);
+    }
+
+    #[test]
+    fn table_cells() {
+        assert_eq!(summarise("  24000\n"), "24000");
+        assert_eq!(summarise("#..\n.#.\n..#\n"), "[3 lines]");
+        assert_eq!(format_duration(Duration::from_micros(999)), "999 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+}